@@ -0,0 +1,65 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    LitStr, Result, Token, Type,
+};
+
+/// The input of `assert_columns!(Type, ["column", ...])`.
+pub(crate) struct AssertColumns {
+    ty: Type,
+    columns: Punctuated<LitStr, Token![,]>,
+}
+
+impl Parse for AssertColumns {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ty = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let content;
+        bracketed!(content in input);
+        let columns = Punctuated::parse_terminated(&content)?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Self { ty, columns })
+    }
+}
+
+impl AssertColumns {
+    /// Generates a constant that fails to evaluate if the column names of the type are not the listed ones.
+    pub(crate) fn generate(self) -> Result<TokenStream2> {
+        let Self { ty, columns } = self;
+
+        let ty_name = quote!(#ty).to_string();
+        let names = columns.iter().map(LitStr::value).collect::<Vec<_>>();
+        let unknown_message = format!(
+            "assert_columns!: the column names of `{ty_name}` are not known at compile time"
+        );
+        let arms = names.iter().enumerate().map(|(i, name)| {
+            let message = format!("assert_columns!: column #{i} of `{ty_name}` should be `{name}`");
+            quote!(std::option::Option::Some(#i) => std::panic!(#message),)
+        });
+        let count_message = format!(
+            "assert_columns!: `{ty_name}` expects more than the {} columns listed",
+            names.len()
+        );
+
+        Ok(quote! {
+            const _: () = {
+                let std::option::Option::Some(names) = <#ty as postgres_from_row::FromRow>::COLUMN_NAMES else {
+                    std::panic!(#unknown_message);
+                };
+                match postgres_from_row::__private::column_names_mismatch(
+                    std::option::Option::Some(names),
+                    &[#(std::option::Option::Some(#names)),*],
+                ) {
+                    std::option::Option::None => {}
+                    #(#arms)*
+                    std::option::Option::Some(_) => std::panic!(#count_message),
+                }
+            };
+        })
+    }
+}
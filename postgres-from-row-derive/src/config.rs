@@ -0,0 +1,149 @@
+//! Workspace-wide defaults for the container attributes, read from a `from_row.toml` file.
+//!
+//! The file is searched for in the directory of the crate being compiled and then in each of its parents,
+//! so a single file at the root of a workspace applies to every crate in it:
+//!
+//! ```toml
+//! # the default of `#[from_row(rename_all = "..")]`
+//! rename_all = "camelCase"
+//! # the default of `#[from_row(crate = "..")]`
+//! crate = "my_facade::postgres_from_row"
+//! # skip verifying the columns of every struct, like `#[from_row(unchecked)]`
+//! unchecked = false
+//! # "strict" rejects non-`Option` fields reading nullable columns of the schema file, "lenient" allows them
+//! nullability = "strict"
+//! ```
+//!
+//! Attributes written on a struct take precedence over the file.
+
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+use darling::FromMeta;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::Result;
+
+use crate::rename::RenameRule;
+
+/// The name of the file containing the defaults.
+const CONFIG_FILE: &str = "from_row.toml";
+
+/// The configs already loaded, keyed by the manifest directory of the crate being compiled.
+///
+/// Every derive and `query_as!` of a crate reads the same file, so it is only read and parsed once.
+/// The key keeps crates apart when a single process expands the macros of a whole workspace.
+static LOADED: OnceLock<Mutex<HashMap<OsString, std::result::Result<Config, String>>>> =
+    OnceLock::new();
+
+/// The defaults read from `from_row.toml`, empty when there is no such file.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Config {
+    path: Option<PathBuf>,
+    pub(crate) rename_all: Option<RenameRule>,
+    /// Kept as a string because `syn` types can't be shared between threads.
+    krate: Option<String>,
+    pub(crate) unchecked: bool,
+    pub(crate) lenient_nullability: bool,
+}
+
+impl Config {
+    /// Finds and parses the `from_row.toml` of the crate being compiled, once per crate.
+    pub(crate) fn load() -> Result<Self> {
+        let Some(manifest_dir) = std::env::var_os("CARGO_MANIFEST_DIR") else {
+            return Ok(Self::default());
+        };
+        let mut loaded = LOADED
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        loaded
+            .entry(manifest_dir)
+            .or_insert_with_key(|manifest_dir| Self::read(Path::new(manifest_dir)))
+            .clone()
+            .map_err(|message| syn::Error::new(Span::call_site(), message))
+    }
+
+    /// The crate path given by the file, if any.
+    pub(crate) fn krate(&self) -> Option<syn::Path> {
+        // validated by `read`
+        self.krate
+            .as_deref()
+            .map(|krate| syn::parse_str(krate).unwrap())
+    }
+
+    /// Reads the `from_row.toml` of the crate in `manifest_dir`, returning the error message on failure.
+    fn read(manifest_dir: &Path) -> std::result::Result<Self, String> {
+        let Some(path) = manifest_dir
+            .ancestors()
+            .map(|dir| dir.join(CONFIG_FILE))
+            .find(|path| path.is_file())
+        else {
+            return Ok(Self::default());
+        };
+
+        let error = |message: String| format!("invalid `{}`: {message}", path.display());
+
+        let contents = std::fs::read_to_string(&path).map_err(|e| error(e.to_string()))?;
+        let table = contents
+            .parse::<toml::Table>()
+            .map_err(|e| error(e.to_string()))?;
+
+        let mut config = Self::default();
+        for (key, value) in &table {
+            let string = || {
+                value
+                    .as_str()
+                    .ok_or_else(|| error(format!("`{key}` should be a string")))
+            };
+            match key.as_str() {
+                "rename_all" => {
+                    config.rename_all =
+                        Some(RenameRule::from_string(string()?).map_err(|e| error(e.to_string()))?);
+                }
+                "crate" => {
+                    let krate = string()?;
+                    syn::parse_str::<syn::Path>(krate).map_err(|e| error(e.to_string()))?;
+                    config.krate = Some(krate.to_string());
+                }
+                "unchecked" => {
+                    config.unchecked = value
+                        .as_bool()
+                        .ok_or_else(|| error("`unchecked` should be a boolean".to_string()))?;
+                }
+                "nullability" => {
+                    config.lenient_nullability = match string()? {
+                        "strict" => false,
+                        "lenient" => true,
+                        other => {
+                            return Err(error(format!(
+                                "unknown nullability `{other}`, expected `strict` or `lenient`"
+                            )))
+                        }
+                    };
+                }
+                _ => return Err(error(format!("unknown key `{key}`"))),
+            }
+        }
+        config.path = Some(path);
+
+        Ok(config)
+    }
+
+    /// Returns tokens that make the compiler track the file, so that changes to it trigger a rebuild.
+    pub(crate) fn track(&self) -> TokenStream2 {
+        match &self.path {
+            Some(path) => {
+                let path = path.to_string_lossy();
+                quote!(
+                    const _: &[u8] = std::include_bytes!(#path);
+                )
+            }
+            None => quote!(),
+        }
+    }
+}
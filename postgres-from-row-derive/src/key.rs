@@ -0,0 +1,61 @@
+//! Generation of the `Key` companion struct of structs with a `#[from_row(key_struct)]` attribute.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::Result;
+
+use crate::{compared_fields, DeriveFromRow};
+
+/// Generates the `{Name}Key` struct and the `key` method, if the struct is marked with `key_struct`.
+///
+/// The key holds the fields compared when joining rows, the `key` fields if there are any,
+/// so values can be grouped or looked up in a `HashMap` by the same fields the join uses.
+pub(crate) fn generate(derive: &DeriveFromRow) -> Result<TokenStream2> {
+    if !derive.key_struct {
+        return Ok(quote!());
+    }
+
+    if !derive.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            derive.ident.span(),
+            "`#[from_row(key_struct)]` can't be used on generic structs",
+        ));
+    }
+
+    let ident = &derive.ident;
+    let key = format_ident!("{}Key", ident);
+    let vis = &derive.vis;
+
+    let idents = compared_fields(derive.fields()).collect::<Vec<_>>();
+    let declarations = derive
+        .fields()
+        .iter()
+        .filter(|f| idents.contains(&f.ident.as_ref().unwrap()))
+        .map(|f| {
+            let vis = &f.vis;
+            let ident = f.ident.as_ref().unwrap();
+            let ty = &f.ty;
+            quote!(#vis #ident: #ty)
+        });
+
+    let doc = format!(
+        "The fields compared when joining rows into a [`{ident}`], generated by `#[from_row(key_struct)]`."
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #vis struct #key {
+            #(#declarations),*
+        }
+
+        impl #ident {
+            /// Clones the fields compared when joining rows into a value.
+            #vis fn key(&self) -> #key {
+                #key {
+                    #(#idents: std::clone::Clone::clone(&self.#idents)),*
+                }
+            }
+        }
+    })
+}
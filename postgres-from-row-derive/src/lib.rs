@@ -1,10 +1,22 @@
-use std::str::FromStr;
-
-use darling::{ast::Data, Error, FromDeriveInput, FromField, ToTokens};
+use darling::{ast::Data, Error, FromDeriveInput, FromField, FromMeta, ToTokens};
 use proc_macro::TokenStream;
+use config::Config;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
-use syn::{parse_macro_input, DeriveInput, Result};
+use quote::{quote, quote_spanned};
+use rename::RenameRule;
+use syn::{ext::IdentExt, parse_macro_input, DeriveInput, Result};
+
+mod assert_columns;
+mod config;
+mod key;
+mod offsets;
+mod partial;
+mod query_as;
+mod rename;
+mod schema;
+mod table;
+mod text_enum;
+mod to_params;
 
 /// Calls the fallible entry point and writes any errors to the tokenstream.
 #[proc_macro_derive(FromRow, attributes(from_row))]
@@ -16,12 +28,68 @@ pub fn derive_from_row(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Implements `TextEnum` for a fieldless enum, storing each variant as its name or `#[from_row(rename = "..")]`.
+#[proc_macro_derive(TextEnum, attributes(from_row))]
+pub fn derive_text_enum(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    match try_derive_text_enum(&derive_input) {
+        Ok(result) => result,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
+/// Implements `ToParams` for a struct, and adds a `params` method returning its fields as statement parameters.
+#[proc_macro_derive(ToParams, attributes(to_params))]
+pub fn derive_to_params(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+    match try_derive_to_params(&derive_input) {
+        Ok(result) => result,
+        Err(err) => err.write_errors().into(),
+    }
+}
+
+/// Builds a `postgres_from_row::Query`, checking the select list of the query against the
+/// expected columns of the type at compile time.
+#[proc_macro]
+pub fn query_as(input: TokenStream) -> TokenStream {
+    let query_as = parse_macro_input!(input as query_as::QueryAs);
+    match query_as.generate() {
+        Ok(result) => result.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Fails to compile if the column names of a type, known at compile time, are not exactly the listed ones, in order.
+#[proc_macro]
+pub fn assert_columns(input: TokenStream) -> TokenStream {
+    let assert_columns = parse_macro_input!(input as assert_columns::AssertColumns);
+    match assert_columns.generate() {
+        Ok(result) => result.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 /// Fallible entry point for generating a `FromRow` implementation
 fn try_derive_from_row(input: &DeriveInput) -> std::result::Result<TokenStream, Error> {
-    let from_row_derive = DeriveFromRow::from_derive_input(input)?;
+    let mut from_row_derive = DeriveFromRow::from_derive_input(input)?;
+    from_row_derive.config = Config::load()?;
     Ok(from_row_derive.generate()?)
 }
 
+/// Fallible entry point for generating a `TextEnum` implementation
+fn try_derive_text_enum(input: &DeriveInput) -> std::result::Result<TokenStream, Error> {
+    let mut text_enum_derive = text_enum::DeriveTextEnum::from_derive_input(input)?;
+    text_enum_derive.config = Config::load()?;
+    Ok(text_enum_derive.generate()?.into())
+}
+
+/// Fallible entry point for generating a `ToParams` implementation
+fn try_derive_to_params(input: &DeriveInput) -> std::result::Result<TokenStream, Error> {
+    let mut to_params_derive = to_params::DeriveToParams::from_derive_input(input)?;
+    to_params_derive.config = Config::load()?;
+    Ok(to_params_derive.generate()?.into())
+}
+
 /// Main struct for deriving `FromRow` for a struct.
 #[derive(Debug, FromDeriveInput)]
 #[darling(
@@ -31,8 +99,65 @@ fn try_derive_from_row(input: &DeriveInput) -> std::result::Result<TokenStream,
 )]
 struct DeriveFromRow {
     ident: syn::Ident,
+    vis: syn::Visibility,
     generics: syn::Generics,
     data: Data<(), FromRowField>,
+    /// The table this struct is read from, used to validate the struct against the schema file
+    /// named by the `POSTGRES_FROM_ROW_SCHEMA` environment variable, when it is set.
+    table: Option<String>,
+    /// Skip verifying the columns entirely, for hot loops where the caller controls the sql.
+    #[darling(default)]
+    unchecked: bool,
+    /// Optionally call this function with a reference to the struct after it was constructed,
+    /// the error it returns is returned from the conversion.
+    validate: Option<syn::Expr>,
+    /// Optionally construct the struct by calling this function with the values of the fields in order,
+    /// instead of using a struct literal.
+    constructor: Option<syn::Expr>,
+    /// Replaces the where clause predicates generated for all fields with these, like serde's `bound` attribute.
+    /// An empty string removes them entirely.
+    bound: Option<String>,
+    /// The path of the `postgres_from_row` crate, for when it is renamed or re-exported from another crate.
+    #[darling(rename = "crate")]
+    krate: Option<syn::Path>,
+    /// Renames the column of every field that doesn't use `#[from_row(rename = "..")]` with this case convention.
+    rename_all: Option<RenameRule>,
+    /// Document the columns this struct expects on the generated impl, so they show up in `cargo doc`.
+    ///
+    /// A derive can't add attributes to the struct itself, and `#[doc(alias)]` is not allowed on impls,
+    /// so the layout is written as a doc comment of the `FromRow` impl instead.
+    #[darling(default)]
+    doc: bool,
+    /// Also implement `FromSimpleRow`, converting the text-format rows of `simple_query`.
+    #[darling(default)]
+    simple_query: bool,
+    /// Also implement `FromCopyRow`, converting the rows of a binary `COPY ... TO STDOUT`.
+    #[darling(default)]
+    copy_out: bool,
+    /// Also implement `ToCopyRow`, writing the fields with a binary `COPY ... FROM STDIN`.
+    #[darling(default)]
+    copy_in: bool,
+    /// Also implement `FromMockRow`, converting the rows built with `MockRow` in tests.
+    #[darling(default)]
+    mock: bool,
+    /// Also generate a `{Name}Partial` struct with every field wrapped in an `Option`,
+    /// which is `None` when the columns of the field are absent from the row.
+    #[darling(default)]
+    partial: bool,
+    /// Also generate a `{name}_columns` module, with a constant holding the offset of the first column of each field.
+    #[darling(default)]
+    offsets: bool,
+    /// Also generate a `{Name}Key` struct holding the fields compared when joining rows, with a `key` method returning it.
+    #[darling(default)]
+    key_struct: bool,
+    /// Allow several fields to read columns with the same name, including the columns of flattened and joined fields.
+    ///
+    /// The columns are still read by index, but converting by name would read the first of them for every field.
+    #[darling(default)]
+    allow_duplicate_columns: bool,
+    /// The defaults read from `from_row.toml`, see the `config` module.
+    #[darling(skip)]
+    config: Config,
 }
 
 impl DeriveFromRow {
@@ -42,29 +167,261 @@ impl DeriveFromRow {
             field.validate()?;
         }
 
-        for fields_but_last in self.fields().split_last().map(|x| x.1).unwrap_or(&[]) {
-            if fields_but_last.join {
+        let first_join = self.fields().iter().position(|f| f.join).unwrap_or(self.fields().len());
+        if let Some(field) = self.fields()[first_join..].iter().find(|f| !f.join) {
+            return Err(Error::custom(
+                r#"the fields with `#[from_row(join)]` must come after all the other fields"#,
+            )
+            .with_span(field.ident.as_ref().unwrap())
+            .into());
+        }
+
+        if self.fields().len() > 1 {
+            if let Some(field) = self.fields().iter().find(|f| (f.flatten || f.join) && reads_whole_row(&f.ty)) {
+                return Err(Error::custom(
+                    r#"`ViaTryFrom` reads the whole row, so it must be the only field of the struct"#,
+                )
+                .with_span(&field.ty)
+                .into());
+            }
+        }
+
+        if self.table.is_none() {
+            if let Some(field) = self.fields().iter().find(|f| f.primary_key) {
+                return Err(Error::custom(
+                    r#"`#[from_row(primary_key)]` can only be used on structs with `#[from_row(table = "..")]`"#,
+                )
+                .with_span(field.ident.as_ref().unwrap())
+                .into());
+            }
+        }
+
+        for field in self.fields().iter().filter(|_| self.copy_in) {
+            if field.from.is_some()
+                || field.try_from.is_some()
+                || field.from_fn.is_some()
+                || field.try_from_fn.is_some()
+                || field.enum_text
+            {
+                return Err(Error::custom(
+                    r#"can't combine `#[from_row(copy_in)]` with one of the `#[from_row(*from*)]` attributes or `#[from_row(enum_text)]`, since the conversion can't be reversed"#,
+                )
+                .with_span(field.ident.as_ref().unwrap())
+                .into());
+            }
+        }
+
+        for field in self.fields().iter().filter(|_| self.partial) {
+            if field.join {
+                return Err(Error::custom(
+                    r#"can't combine `#[from_row(partial)]` with `#[from_row(join)]`"#,
+                )
+                .with_span(field.ident.as_ref().unwrap())
+                .into());
+            }
+        }
+
+        for field in self.fields().iter().filter(|_| self.mock) {
+            if field.context {
                 return Err(Error::custom(
-                    r#"the attribute `#[from_row(join)]` can only be used on the last field"#,
+                    r#"can't combine `#[from_row(mock)]` with `#[from_row(context)]`"#,
                 )
+                .with_span(field.ident.as_ref().unwrap())
                 .into());
             }
         }
 
+        for (enabled, attribute) in [
+            (self.simple_query, "simple_query"),
+            (self.copy_out, "copy_out"),
+            (self.copy_in, "copy_in"),
+        ] {
+            for field in self.fields().iter().filter(|_| enabled) {
+                if field.join || field.context {
+                    return Err(Error::custom(format!(
+                        r#"can't combine `#[from_row({attribute})]` with `#[from_row(join)]` or `#[from_row(context)]`"#,
+                    ))
+                    .with_span(field.ident.as_ref().unwrap())
+                    .into());
+                }
+                if field.none_when.is_some() && attribute != "copy_in" {
+                    return Err(Error::custom(format!(
+                        r#"can't combine `#[from_row({attribute})]` with `#[from_row(none_when = "..")]`"#,
+                    ))
+                    .with_span(field.ident.as_ref().unwrap())
+                    .into());
+                }
+            }
+        }
+
         Ok(())
     }
 
+    /// Applies `rename_all` to the fields that are not renamed explicitly.
+    ///
+    /// Fields that end up reading the same column are rejected by `check_duplicate_columns`.
+    fn apply_rename_all(&mut self) {
+        let Some(rule) = self.rename_all.or(self.config.rename_all) else {
+            return;
+        };
+
+        let Data::Struct(fields) = &mut self.data else {
+            panic!("invalid shape");
+        };
+
+        for field in &mut fields.fields {
+            if field.rename.is_none() && !field.flatten && !field.join {
+                let name = field.ident.as_ref().unwrap().unraw().to_string();
+                field.rename = Some(rule.apply(&name));
+            }
+        }
+    }
+
+    /// Fails if two fields read columns with the same name, unless `allow_duplicate_columns` is set.
+    ///
+    /// The names of flattened and joined fields are only known once their types are, so they are compared by
+    /// the returned constant assertions, using the `COLUMN_NAMES` of their types, which are skipped for generic structs.
+    fn check_duplicate_columns(&self) -> Result<TokenStream2> {
+        if self.allow_duplicate_columns {
+            return Ok(quote!());
+        }
+
+        let columns = self.fields().iter().filter(|f| !f.flatten && !f.join);
+        for (i, field) in columns.clone().enumerate() {
+            let column_name = field.column_name();
+            if let Some(other) = columns
+                .clone()
+                .take(i)
+                .find(|other| other.column_name() == column_name)
+            {
+                let ident = field.ident.as_ref().unwrap();
+                let other_ident = other.ident.as_ref().unwrap();
+                #[cfg(feature = "rename-collision-check")]
+                if let Some(rule) = self.rename_all.or(self.config.rename_all) {
+                    let renamed = |ident: &syn::Ident| rule.apply(&ident.unraw().to_string()) == column_name;
+                    if renamed(ident) && renamed(other_ident) {
+                        return Err(Error::custom(format!(
+                            r#"the fields `{other_ident}` and `{ident}` both read the column `{column_name}` with `#[from_row(rename_all = "{}")]`"#,
+                            rule.name(),
+                        ))
+                        .with_span(ident)
+                        .into());
+                    }
+                }
+                return Err(Error::custom(format!(
+                    r#"the fields `{other_ident}` and `{ident}` both read the column `{column_name}`, use `#[from_row(allow_duplicate_columns)]` if this is intended"#,
+                ))
+                .with_span(ident)
+                .into());
+            }
+        }
+
+        if !self.generics.params.is_empty() {
+            return Ok(quote!());
+        }
+
+        let krate = self.krate();
+        let mut checks = Vec::new();
+        let nested = self.fields().iter().filter(|f| f.flatten || f.join);
+        for (i, field) in nested.clone().enumerate() {
+            if field.from_fn.is_some() || field.try_from_fn.is_some() {
+                continue;
+            }
+            let ident = field.ident.as_ref().unwrap();
+            let target_ty = field.target_ty()?;
+            let names = quote!(<#target_ty as #krate::FromRow>::COLUMN_NAMES);
+            for column in columns.clone() {
+                let column_name = column.column_name();
+                let message = format!(
+                    "the column `{column_name}` of the field `{}` is also read by the field `{ident}`, use `#[from_row(allow_duplicate_columns)]` if this is intended",
+                    column.ident.as_ref().unwrap(),
+                );
+                checks.push(quote_spanned! {ident.span()=>
+                    const _: () = std::assert!(!#krate::__private::contains_column_name(#names, #column_name), #message);
+                });
+            }
+            for other in nested.clone().take(i) {
+                if other.from_fn.is_some() || other.try_from_fn.is_some() {
+                    continue;
+                }
+                let other_ty = other.target_ty()?;
+                let message = format!(
+                    "the fields `{}` and `{ident}` both read a column with the same name, use `#[from_row(allow_duplicate_columns)]` if this is intended",
+                    other.ident.as_ref().unwrap(),
+                );
+                checks.push(quote_spanned! {ident.span()=>
+                    const _: () = std::assert!(!#krate::__private::column_names_overlap(<#other_ty as #krate::FromRow>::COLUMN_NAMES, #names), #message);
+                });
+            }
+        }
+
+        Ok(quote!(#(#checks)*))
+    }
+
+    /// Generates the lines binding every field when calling `try_from_row_joined`, or `try_from_mock_row_joined` with `mock`.
+    fn generate_try_from_row_bindings(&self, krate: &syn::Path, mock: bool) -> Result<TokenStream2> {
+        let mut bindings = TokenStream2::new();
+        let joins = self.fields().iter().filter(|f| f.join).count();
+        for (i, field) in self.fields().iter().enumerate() {
+            if joins > 1 && i + joins == self.fields().len() {
+                bindings.extend(self.generate_join_merge(krate, mock)?);
+            }
+            bindings.extend(field.generate_try_from_row(self.fields(), krate, mock)?);
+        }
+        Ok(bindings)
+    }
+
+    /// Generates the block merging a row into every joined field of the last value, when there are several of them.
+    ///
+    /// Each joined field reads its own columns and handles its duplicates independently, and the value is only
+    /// returned when the row does not belong to the last one. A row is accounted for by the first joined field,
+    /// so the `verify` feature does not count the null rows of the others.
+    fn generate_join_merge(&self, krate: &syn::Path, mock: bool) -> Result<TokenStream2> {
+        let comparisons = compared_fields(self.fields()).map(|ident| quote!(__last.#ident == #ident));
+        let merges = self
+            .fields()
+            .iter()
+            .filter(|f| f.join)
+            .enumerate()
+            .map(|(i, f)| f.generate_join_merge(krate, mock, i == 0))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(quote! {
+            if let std::option::Option::Some(__last) = __last.as_deref_mut().filter(|__last| true #(&& #comparisons)*) {
+                #(#merges)*
+                return std::result::Result::Ok(std::option::Option::None);
+            }
+        })
+    }
+
     /// Generates any additional where clause predicates needed for the fields in this struct.
-    fn predicates(&self) -> Result<Vec<TokenStream2>> {
+    ///
+    /// With a `companion`, the predicates needed by its trait are added too.
+    fn predicates(&self, companion: Option<Companion>) -> Result<Vec<TokenStream2>> {
+        if let Some(bound) = &self.bound {
+            return parse_bound(bound);
+        }
+
+        let krate = self.krate();
         let mut predicates = Vec::new();
 
         for field in self.fields() {
-            field.add_predicates(&mut predicates)?;
+            field.add_predicates(&mut predicates, &krate)?;
+            if let Some(companion) = companion {
+                field.add_companion_predicates(&mut predicates, &krate, companion)?;
+            }
         }
 
         Ok(predicates)
     }
 
+    /// The path of the `postgres_from_row` crate used by the generated code.
+    fn krate(&self) -> syn::Path {
+        self.krate
+            .clone()
+            .or_else(|| self.config.krate())
+            .unwrap_or_else(|| syn::parse_quote!(postgres_from_row))
+    }
+
     /// Provides a slice of this struct's fields.
     fn fields(&self) -> &[FromRowField] {
         match &self.data {
@@ -73,45 +430,196 @@ impl DeriveFromRow {
         }
     }
 
+    /// Generates the `COLUMN_NAMES` constant.
+    ///
+    /// The names of flattened and joined fields can only be concatenated at compile time when
+    /// the struct is not generic, otherwise the default of `None` is kept.
+    fn generate_column_names(&self) -> Result<TokenStream2> {
+        let ident = &self.ident;
+        let krate = self.krate();
+
+        if self.fields().iter().all(|f| !f.flatten && !f.join) {
+            let names = self.fields().iter().map(|f| f.column_name());
+            return Ok(quote! {
+                const COLUMN_NAMES: std::option::Option<&'static [std::option::Option<&'static str>]> =
+                    std::option::Option::Some(&[#(std::option::Option::Some(#names)),*]);
+            });
+        }
+
+        if !self.generics.params.is_empty() {
+            return Ok(quote!());
+        }
+
+        let parts = self
+            .fields()
+            .iter()
+            .map(|f| {
+                if f.flatten || f.join {
+                    let target_ty = f.target_ty()?;
+                    Ok(quote!(<#target_ty as #krate::FromRow>::COLUMN_NAMES))
+                } else {
+                    let column_name = f.column_name();
+                    Ok(quote!(std::option::Option::Some(&[std::option::Option::Some(#column_name)])))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(quote! {
+            const COLUMN_NAMES: std::option::Option<&'static [std::option::Option<&'static str>]> = {
+                const NAMES: &std::option::Option<[std::option::Option<&'static str>; <#ident as #krate::FromRow>::COLUMN_COUNT]> =
+                    &#krate::__private::concat_column_names(&[#(#parts),*]);
+                match NAMES {
+                    std::option::Option::Some(names) => std::option::Option::Some(names.as_slice()),
+                    std::option::Option::None => std::option::Option::None,
+                }
+            };
+        })
+    }
+
+    /// Generates `EXPECTED_COLUMNS`, concatenating the ones of flattened fields when the struct is not generic.
+    fn generate_expected_columns(&self) -> Result<TokenStream2> {
+        let ident = &self.ident;
+        let krate = self.krate();
+
+        if self.fields().iter().all(|f| !f.flatten && !f.join) {
+            let columns = self
+                .fields()
+                .iter()
+                .map(|f| f.generate_report_expected_columns_to_const_slice(&krate, &quote!(Self)))
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(quote! {
+                const EXPECTED_COLUMNS: std::option::Option<&'static [#krate::ExpectedColumn]> =
+                    std::option::Option::Some(&[#(#columns),*]);
+            });
+        }
+
+        if !self.generics.params.is_empty() {
+            return Ok(quote!());
+        }
+
+        let parts = self
+            .fields()
+            .iter()
+            .map(|f| {
+                if f.flatten || f.join {
+                    let target_ty = f.target_ty()?;
+                    let field_name = f.ident.as_ref().unwrap().to_string();
+                    Ok(quote!((
+                        <#target_ty as #krate::FromRow>::EXPECTED_COLUMNS,
+                        std::option::Option::Some(#field_name),
+                    )))
+                } else {
+                    // `Self` can't be named in the items of the constant
+                    let column = f.generate_report_expected_columns_to_const_slice(&krate, &quote!(#ident))?;
+                    Ok(quote!((std::option::Option::Some(&[#column]), std::option::Option::None)))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(quote! {
+            const EXPECTED_COLUMNS: std::option::Option<&'static [#krate::ExpectedColumn]> = {
+                const COLUMNS: &std::option::Option<[#krate::ExpectedColumn; <#ident as #krate::FromRow>::COLUMN_COUNT]> =
+                    &#krate::__private::concat_expected_columns(std::any::type_name::<#ident>, &[#(#parts),*]);
+                match COLUMNS {
+                    std::option::Option::Some(columns) => std::option::Option::Some(columns.as_slice()),
+                    std::option::Option::None => std::option::Option::None,
+                }
+            };
+        })
+    }
+
+    /// Generates the doc comment describing the columns this struct expects, for `#[from_row(doc)]`.
+    fn generate_docs(&self) -> Result<TokenStream2> {
+        if !self.doc {
+            return Ok(quote!());
+        }
+
+        let mut lines = vec![
+            format!("Reads the columns of `{}`, in this order:", self.ident),
+            String::new(),
+        ];
+        for field in self.fields() {
+            let field_name = field.ident.as_ref().unwrap().unraw();
+            let column_name = field.column_name();
+            let target_ty = type_to_string(&field.target_ty()?);
+            lines.push(if field.flatten {
+                format!("- the columns of `{target_ty}`, flattened into `{field_name}`")
+            } else if field.join {
+                format!("- the columns of `{target_ty}`, joined into `{field_name}`")
+            } else if field.enum_text {
+                let ty = type_to_string(&field.ty.to_token_stream());
+                format!("- `{column_name}`, as the text of `{ty}`")
+            } else if field.from_fn.is_some() || field.try_from_fn.is_some() {
+                format!("- `{column_name}`, converted into `{field_name}` with a function")
+            } else {
+                format!("- `{column_name}`, as `{target_ty}`")
+            });
+        }
+
+        Ok(quote!(#(#[doc = #lines])*))
+    }
+
     /// Generate the `FromRow` implementation.
-    fn generate(self) -> Result<TokenStream> {
+    fn generate(mut self) -> Result<TokenStream> {
         self.validate()?;
+        self.apply_rename_all();
+        let duplicate_checks = self.check_duplicate_columns()?;
 
         let ident = &self.ident;
+        let krate = self.krate();
 
         let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
         let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
-        let predicates = self.predicates()?;
+        let predicates = self.predicates(None)?;
 
         let generate_column_count_terms = self.fields()
             .iter()
-            .map(|f| f.generate_column_count())
+            .map(|f| f.generate_column_count(&krate))
             .collect::<syn::Result<Vec<_>>>()?;
 
-        let try_from_row_bindings = self.fields()
+        let try_from_row_bindings = self.generate_try_from_row_bindings(&krate, false)?;
+
+        let try_from_row_idents = self.fields().iter().map(|f| f.ident.as_ref().unwrap()).collect::<Vec<_>>();
+
+        let try_from_row_by_name_bindings = self.fields()
             .iter()
-            .map(|f| f.generate_try_from_row(self.fields()))
+            .map(|f| f.generate_try_from_row_by_name(&krate, false))
             .collect::<syn::Result<TokenStream2>>()?;
 
-        let try_from_row_idents = self.fields().iter().map(|f| f.ident.as_ref().unwrap());
-
         let report_expected_columns = if self.fields().iter().any(|x| x.flatten || x.join) {
             let report_expected_columns = self.fields()
                 .iter()
-                .map(|f| f.generate_report_expected_columns_to_vec())
+                .map(|f| f.generate_report_expected_columns_to_vec(&krate))
                 .collect::<syn::Result<TokenStream2>>()?;
-            quote! {
-                let mut expected = Vec::<postgres_from_row::ExpectedColumn>::with_capacity(Self::COLUMN_COUNT);
+            let build = quote! {
+                let mut expected = Vec::<#krate::ExpectedColumn>::with_capacity(Self::COLUMN_COUNT);
                 #report_expected_columns
-                postgres_from_row::ExpectedColumns::Owned(expected)
+            };
+            if self.generics.params.is_empty() {
+                // a static would be shared by every instantiation of a generic struct, so only non-generic structs are cached
+                quote! {
+                    if let std::option::Option::Some(columns) = Self::EXPECTED_COLUMNS {
+                        return #krate::ExpectedColumns::Borrowed(columns);
+                    }
+                    static COLUMNS: std::sync::OnceLock<std::vec::Vec<#krate::ExpectedColumn>> = std::sync::OnceLock::new();
+                    #krate::ExpectedColumns::Borrowed(COLUMNS.get_or_init(|| {
+                        #build
+                        expected
+                    }))
+                }
+            } else {
+                quote! {
+                    #build
+                    #krate::ExpectedColumns::Owned(expected)
+                }
             }
         } else {
             let report_expected_columns = self.fields()
                 .iter()
-                .map(|f| f.generate_report_expected_columns_to_const_slice())
+                .map(|f| f.generate_report_expected_columns_to_const_slice(&krate, &quote!(Self)))
                 .collect::<syn::Result<Vec<_>>>()?;
             quote! {
-                postgres_from_row::ExpectedColumns::Borrowed(const {
+                #krate::ExpectedColumns::Borrowed(const {
                     &[
                         #(#report_expected_columns),*
                     ]
@@ -119,24 +627,24 @@ impl DeriveFromRow {
             }
         };
 
-        let try_assert_matches = self.fields()
+        let dry_run = self.fields()
             .iter()
-            .map(|f| f.generate_try_assert_matches())
+            .map(|f| f.generate_dry_run(&krate))
             .collect::<syn::Result<TokenStream2>>()?;
 
-        Ok(quote! {
-            impl #impl_generics postgres_from_row::FromRow for #ident #ty_generics where #(#original_predicates),* #(#predicates),* {
-                const COLUMN_COUNT: std::primitive::usize = 0 #(+ #generate_column_count_terms)*;
-                fn try_from_row_joined(mut __last: std::option::Option<&mut Self>, __row: &postgres_from_row::tokio_postgres::Row, mut __i: std::primitive::usize) -> std::result::Result<std::option::Option<Self>, postgres_from_row::tokio_postgres::Error> {
-                    #try_from_row_bindings
-                    std::result::Result::Ok(std::option::Option::Some(Self {
-                        #(#try_from_row_idents),*
-                    }))
-                }
-                fn report_expected_columns() -> postgres_from_row::ExpectedColumns {
-                    #report_expected_columns
+        let try_assert_matches = if self.unchecked || self.config.unchecked {
+            quote! {
+                fn try_assert_matches(_: &[#krate::tokio_postgres::Column]) -> std::result::Result<(), ()> {
+                    std::result::Result::Ok(())
                 }
-                fn try_assert_matches(mut __columns: &[postgres_from_row::tokio_postgres::Column]) -> std::result::Result<(), ()> {
+            }
+        } else {
+            let try_assert_matches = self.fields()
+                .iter()
+                .map(|f| f.generate_try_assert_matches(&krate))
+                .collect::<syn::Result<TokenStream2>>()?;
+            quote! {
+                fn try_assert_matches(mut __columns: &[#krate::tokio_postgres::Column]) -> std::result::Result<(), ()> {
                     if __columns.len() != Self::COLUMN_COUNT {
                         return Err(());
                     }
@@ -144,6 +652,181 @@ impl DeriveFromRow {
                     std::result::Result::Ok(())
                 }
             }
+        };
+
+        let construct = match &self.constructor {
+            Some(constructor) => quote!((#constructor)(#(#try_from_row_idents),*)),
+            None => quote!(Self {
+                #(#try_from_row_idents),*
+            }),
+        };
+
+        let validate = match &self.validate {
+            Some(validate) => quote!((#validate)(&__this).map_err(#krate::Error::conversion)?;),
+            None => quote!(),
+        };
+
+        // the rows of the other joined fields are not counted, see `generate_join_merge`
+        let row_span = match self.fields().iter().find(|f| f.join) {
+            Some(field) => {
+                let ident = field.ident.as_ref().unwrap();
+                let ty = &field.ty;
+                quote! {
+                    fn row_span(&self) -> std::primitive::usize {
+                        <#ty as #krate::FromRow>::row_span(&self.#ident)
+                    }
+                }
+            }
+            _ => quote!(),
+        };
+
+        let joined = self.fields().iter().any(|f| f.join);
+        let column_names = self.generate_column_names()?;
+        let expected_columns = self.generate_expected_columns()?;
+
+        let simple_query = if self.simple_query {
+            let simple_predicates = self.predicates(Some(Companion::SimpleQuery))?;
+            let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
+            let bindings = self.fields()
+                .iter()
+                .map(|f| f.generate_try_from_simple_row(&krate))
+                .collect::<syn::Result<TokenStream2>>()?;
+            quote! {
+                impl #impl_generics #krate::simple::FromSimpleRow for #ident #ty_generics where #(#original_predicates),* #(#simple_predicates),* {
+                    fn try_from_simple_row(__row: &#krate::tokio_postgres::SimpleQueryRow) -> std::result::Result<Self, #krate::Error> {
+                        #bindings
+                        let __this = #construct;
+                        #validate
+                        std::result::Result::Ok(__this)
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let copy_out = if self.copy_out {
+            let copy_predicates = self.predicates(Some(Companion::CopyOut))?;
+            let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
+            let bindings = self.fields()
+                .iter()
+                .map(|f| f.generate_try_from_copy_row(&krate))
+                .collect::<syn::Result<TokenStream2>>()?;
+            quote! {
+                impl #impl_generics #krate::copy::FromCopyRow for #ident #ty_generics where #(#original_predicates),* #(#copy_predicates),* {
+                    #[allow(unused_mut, unused_assignments)]
+                    fn try_from_copy_row_at(__row: &#krate::tokio_postgres::binary_copy::BinaryCopyOutRow, mut __i: std::primitive::usize) -> std::result::Result<Self, #krate::Error> {
+                        #bindings
+                        let __this = #construct;
+                        #validate
+                        std::result::Result::Ok(__this)
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let copy_in = if self.copy_in {
+            let copy_predicates = self.predicates(Some(Companion::CopyIn))?;
+            let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
+            let values = self.fields()
+                .iter()
+                .map(|f| f.generate_copy_values(&krate))
+                .collect::<TokenStream2>();
+            quote! {
+                impl #impl_generics #krate::copy::ToCopyRow for #ident #ty_generics where #(#original_predicates),* #(#copy_predicates),* {
+                    fn copy_values<'__from_row_lifetime>(&'__from_row_lifetime self, __values: &mut std::vec::Vec<&'__from_row_lifetime (dyn #krate::tokio_postgres::types::ToSql + std::marker::Sync)>) {
+                        #values
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let mock = if self.mock {
+            let mock_predicates = self.predicates(Some(Companion::Mock))?;
+            let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
+            let bindings = self.generate_try_from_row_bindings(&krate, true)?;
+            let by_name_bindings = self.fields()
+                .iter()
+                .map(|f| f.generate_try_from_row_by_name(&krate, true))
+                .collect::<syn::Result<TokenStream2>>()?;
+            quote! {
+                impl #impl_generics #krate::test_util::FromMockRow for #ident #ty_generics where #(#original_predicates),* #(#mock_predicates),* {
+                    fn try_from_mock_row_joined(mut __last: std::option::Option<&mut Self>, __row: &#krate::test_util::MockRow, mut __i: std::primitive::usize) -> std::result::Result<std::option::Option<Self>, #krate::Error> {
+                        #bindings
+                        let __this = #construct;
+                        #validate
+                        std::result::Result::Ok(std::option::Option::Some(__this))
+                    }
+                    fn try_from_mock_row_by_name(__row: &#krate::test_util::MockRow) -> std::result::Result<Self, #krate::Error> {
+                        #by_name_bindings
+                        let __this = #construct;
+                        #validate
+                        std::result::Result::Ok(__this)
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let partial = partial::generate(&self)?;
+        let offsets = offsets::generate(&self)?;
+        let key_struct = key::generate(&self)?;
+        let table_impl = table::generate(&self)?;
+        let schema_check = schema::check(&self)?;
+        let docs = self.generate_docs()?;
+        let track_config = self.config.track();
+
+        Ok(quote! {
+            #schema_check
+            #duplicate_checks
+            #track_config
+
+            #docs
+            impl #impl_generics #krate::FromRow for #ident #ty_generics where #(#original_predicates),* #(#predicates),* {
+                const COLUMN_COUNT: std::primitive::usize = 0 #(+ #generate_column_count_terms)*;
+                const JOINED: std::primitive::bool = #joined;
+                #column_names
+                #expected_columns
+                fn try_from_row_joined(mut __last: std::option::Option<&mut Self>, __row: &#krate::tokio_postgres::Row, mut __i: std::primitive::usize) -> std::result::Result<std::option::Option<Self>, #krate::Error> {
+                    #try_from_row_bindings
+                    let __this = #construct;
+                    #validate
+                    std::result::Result::Ok(std::option::Option::Some(__this))
+                }
+                fn try_from_row_by_name(__row: impl #krate::AsRow) -> std::result::Result<Self, #krate::Error> {
+                    let __row = #krate::AsRow::as_row(&__row);
+                    let __usage = #krate::__private::UsageGuard::enter::<Self>(__row);
+                    #try_from_row_by_name_bindings
+                    let __this = #construct;
+                    #validate
+                    std::result::Result::Ok(__this)
+                }
+                fn report_expected_columns() -> #krate::ExpectedColumns {
+                    #report_expected_columns
+                }
+                #try_assert_matches
+                #row_span
+                #[allow(unused_mut, unused_assignments)]
+                fn dry_run(__columns: &[#krate::tokio_postgres::Column]) -> std::vec::Vec<#krate::FieldPlan> {
+                    let mut __i = 0;
+                    let mut __plans = std::vec::Vec::new();
+                    #dry_run
+                    __plans
+                }
+            }
+            #simple_query
+            #copy_out
+            #copy_in
+            #mock
+            #partial
+            #offsets
+            #key_struct
+            #table_impl
         }
         .into())
     }
@@ -155,6 +838,8 @@ impl DeriveFromRow {
 struct FromRowField {
     /// The identifier of this field.
     ident: Option<syn::Ident>,
+    /// The visibility of this field, given to the field of the `Partial` struct too.
+    vis: syn::Visibility,
     /// The type specified in this field.
     ty: syn::Type,
     /// Wether to flatten this field. Flattening means calling the `FromRow` implementation
@@ -163,6 +848,25 @@ struct FromRowField {
     flatten: bool,
     #[darling(default)]
     join: bool,
+    /// When joining, treat a child whose columns are all null as the absence of a child, as produced by a `LEFT JOIN`,
+    /// yielding an empty value (like an empty `Vec` or `None`) instead of decoding the nulls.
+    #[darling(default)]
+    left: bool,
+    /// How children of a joined `Vec` that duplicate one already joined are handled,
+    /// by default a row is merged into the last child when possible.
+    duplicates: Option<Duplicates>,
+    /// Compares children by this field when looking for duplicates, instead of comparing them entirely.
+    duplicates_by: Option<syn::Ident>,
+    /// When a flattened `Option<T>` is `None`, by default when all the columns of `T` are null.
+    none_when: Option<NullPolicy>,
+    /// When joining, a row belongs to the last value when its key fields are equal to the ones of the last value,
+    /// the other fields are not compared, and don't need to implement `PartialEq`.
+    #[darling(default)]
+    key: bool,
+    /// When joining, this field is not compared to decide if a row belongs to the last value,
+    /// so it doesn't need to implement `PartialEq`.
+    #[darling(default)]
+    skip_compare: bool,
     /// Optionaly use this type as the target for `FromRow` or `FromSql`, and then
     /// call `TryFrom::try_from` to convert it the `self.ty`.
     try_from: Option<String>,
@@ -173,9 +877,34 @@ struct FromRowField {
     /// Is not compatible with `flatten` since no column is needed there.
     rename: Option<String>,
     /// Optionally use this function to convert the value from the database into a struct field.
-    from_fn: Option<String>,
+    from_fn: Option<syn::Expr>,
     /// Optionally use this function to convert the value from the database into a struct field.
-    try_from_fn: Option<String>,
+    try_from_fn: Option<syn::Expr>,
+    /// The type of the column given to `from_fn` or `try_from_fn`, required unless the function is a closure
+    /// with the type of its first argument written out.
+    from_fn_ty: Option<String>,
+    /// Pass a `&postgres_from_row::ConversionContext` as the second argument of `from_fn` or `try_from_fn`.
+    #[darling(default)]
+    context: bool,
+    /// Trim the whitespace around the value read from the column, for `CHAR(n)` columns padded with spaces.
+    #[darling(default)]
+    trim: bool,
+    /// Convert the value read from the column to lowercase.
+    #[darling(default)]
+    lowercase: bool,
+    /// The column may be null even though the type it is decoded as can't hold `NULL`, because a conversion handles it.
+    /// Reports and nullability checks treat the column as nullable.
+    #[darling(default)]
+    nullable: bool,
+    /// This column is part of the primary key of the table of the struct, used by `Table::update_sql`.
+    #[darling(default)]
+    primary_key: bool,
+    /// Read the column as text and match it to a variant of a `TextEnum`, the field may also be an `Option` of one.
+    #[darling(default)]
+    enum_text: bool,
+    /// Replaces the where clause predicates generated for this field with these.
+    /// An empty string removes them entirely.
+    bound: Option<String>,
 }
 
 impl FromRowField {
@@ -219,42 +948,211 @@ impl FromRowField {
             .into());
         }
 
-        if self.rename.is_some() && self.flatten {
+        if self.from_fn_ty.is_some() && self.from_fn.is_none() && self.try_from_fn.is_none() {
             return Err(Error::custom(
-                r#"can't combine `#[from_row(flatten)]` with `#[from_row(rename = "..")]`"#,
+                r#"`#[from_row(from_fn_ty = "..")]` can only be used together with `#[from_row(from_fn = "..")]` or `#[from_row(try_from_fn = "..")]`"#,
             )
             .into());
         }
 
-        if self.rename.is_some() && self.join {
+        if self.context && (self.flatten || self.join) {
             return Err(Error::custom(
-                r#"can't combine `#[from_row(join)]` with `#[from_row(rename = "..")]`"#,
+                r#"can't combine `#[from_row(flatten)]` or `#[from_row(join)]` with `#[from_row(context)]`, since they don't read a single column"#,
             )
             .into());
         }
 
-        if self.flatten && self.join {
+        if self.context && self.from_fn.is_none() && self.try_from_fn.is_none() {
             return Err(Error::custom(
-                r#"can't combine `#[from_row(flatten)]` with `#[from_row(join)]`"#,
+                r#"`#[from_row(context)]` can only be used together with `#[from_row(from_fn = "..")]` or `#[from_row(try_from_fn = "..")]`"#,
             )
             .into());
         }
 
-        Ok(())
-    }
-
-    /// Returns a tokenstream of the type that should be returned from either
+        if (self.trim || self.lowercase) && (self.flatten || self.join) {
+            return Err(Error::custom(
+                r#"can't combine `#[from_row(flatten)]` or `#[from_row(join)]` with `#[from_row(trim)]` or `#[from_row(lowercase)]`"#,
+            )
+            .into());
+        }
+
+        if self.enum_text
+            && (self.flatten
+                || self.join
+                || self.from.is_some()
+                || self.try_from.is_some()
+                || self.from_fn.is_some()
+                || self.try_from_fn.is_some())
+        {
+            return Err(Error::custom(
+                r#"`#[from_row(enum_text)]` can't be combined with `#[from_row(flatten)]`, `#[from_row(join)]` or one of the `#[from_row(*from*)]` attributes"#,
+            )
+            .into());
+        }
+
+        if self.primary_key && (self.flatten || self.join) {
+            return Err(Error::custom(
+                r#"can't combine `#[from_row(flatten)]` or `#[from_row(join)]` with `#[from_row(primary_key)]`"#,
+            )
+            .into());
+        }
+
+        if self.nullable && (self.flatten || self.join) {
+            return Err(Error::custom(
+                r#"can't combine `#[from_row(flatten)]` or `#[from_row(join)]` with `#[from_row(nullable)]`"#,
+            )
+            .into());
+        }
+
+        if self.rename.is_some() && self.flatten {
+            return Err(Error::custom(
+                r#"can't combine `#[from_row(flatten)]` with `#[from_row(rename = "..")]`"#,
+            )
+            .into());
+        }
+
+        if self.rename.is_some() && self.join {
+            return Err(Error::custom(
+                r#"can't combine `#[from_row(join)]` with `#[from_row(rename = "..")]`"#,
+            )
+            .into());
+        }
+
+        if self.left && !self.join {
+            return Err(Error::custom(
+                r#"`#[from_row(left)]` can only be used together with `#[from_row(join)]`"#,
+            )
+            .into());
+        }
+
+        if self.none_when.is_some()
+            && (!self.flatten || schema::option_inner(&self.ty).is_none())
+        {
+            return Err(Error::custom(
+                r#"`#[from_row(none_when = "..")]` can only be used on a flattened `Option<T>`"#,
+            )
+            .into());
+        }
+
+        if self.none_when.is_some()
+            && (self.from.is_some()
+                || self.try_from.is_some()
+                || self.from_fn.is_some()
+                || self.try_from_fn.is_some())
+        {
+            return Err(Error::custom(
+                r#"can't combine `#[from_row(none_when = "..")]` with one of the `#[from_row(*from*)]` attributes"#,
+            )
+            .into());
+        }
+
+        if (self.duplicates.is_some() || self.duplicates_by.is_some()) && !self.join {
+            return Err(Error::custom(
+                r#"`#[from_row(duplicates = "..")]` can only be used together with `#[from_row(join)]`"#,
+            )
+            .into());
+        }
+
+        if !matches!(self.duplicates, None | Some(Duplicates::Merge))
+            && vec_inner_type(&self.ty).is_none()
+        {
+            return Err(Error::custom(
+                r#"`#[from_row(duplicates = "..")]` can only be used on `Vec` fields"#,
+            )
+            .with_span(&self.ty)
+            .into());
+        }
+
+        if self.duplicates_by.is_some()
+            && !matches!(self.duplicates, Some(Duplicates::Dedup | Duplicates::Error))
+        {
+            return Err(Error::custom(
+                r#"`#[from_row(duplicates_by = "..")]` can only be used with `#[from_row(duplicates = "dedup")]` or `#[from_row(duplicates = "error")]`"#,
+            )
+            .into());
+        }
+
+        if self.flatten && self.join {
+            return Err(Error::custom(
+                r#"can't combine `#[from_row(flatten)]` with `#[from_row(join)]`"#,
+            )
+            .into());
+        }
+
+        if (self.key || self.skip_compare) && self.join {
+            return Err(Error::custom(
+                r#"`#[from_row(key)]` and `#[from_row(skip_compare)]` can't be used on joined fields, which are never compared"#,
+            )
+            .into());
+        }
+
+        if self.key && self.skip_compare {
+            return Err(Error::custom(
+                r#"can't combine `#[from_row(key)]` with `#[from_row(skip_compare)]`"#,
+            )
+            .into());
+        }
+
+        if self.join && self.from_fn.is_none() && self.try_from_fn.is_none() {
+            let target_ty = match self.from.as_ref().or(self.try_from.as_ref()) {
+                Some(target_ty) => syn::parse_str(target_ty)?,
+                None => self.ty.clone(),
+            };
+            if !is_join_container(&target_ty) {
+                return Err(Error::custom(format!(
+                    r#"`#[from_row(join)]` can only be used on fields of type {}, which join several rows into one value"#,
+                    JOIN_CONTAINERS.map(|c| format!("`{c}`")).join(", "),
+                ))
+                .with_span(&self.ty)
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns a tokenstream of the type that should be returned from either
     /// `FromRow` (when using `flatten`) or `FromSql`.
     fn target_ty(&self) -> Result<TokenStream2> {
         if let Some(from) = &self.from {
             Ok(from.parse()?)
         } else if let Some(try_from) = &self.try_from {
             Ok(try_from.parse()?)
+        } else if let Some(from_fn_ty) = &self.from_fn_ty {
+            Ok(from_fn_ty.parse()?)
+        } else if let Some(function) = self.from_fn.as_ref().or(self.try_from_fn.as_ref()) {
+            match function {
+                syn::Expr::Closure(closure) => match closure.inputs.first() {
+                    Some(syn::Pat::Type(arg)) => Ok(arg.ty.to_token_stream()),
+                    _ => Err(Error::custom(
+                        r#"the type of the first argument of the closure must be written out, like `|value: f64| ..`, or given with `#[from_row(from_fn_ty = "..")]`"#,
+                    )
+                    .with_span(closure)
+                    .into()),
+                },
+                _ => Err(Error::custom(
+                    r#"the type of the column given to the function can't be inferred, add `#[from_row(from_fn_ty = "..")]`"#,
+                )
+                .with_span(function)
+                .into()),
+            }
+        } else if self.enum_text {
+            if schema::option_inner(&self.ty).is_some() {
+                Ok(quote!(std::option::Option<std::string::String>))
+            } else {
+                Ok(quote!(std::string::String))
+            }
         } else {
             Ok(self.ty.to_token_stream())
         }
     }
 
+    /// Whether this is a flattened `Result<T, E>`, which holds the error of its columns and can't be compared.
+    fn is_flattened_result(&self) -> bool {
+        self.flatten
+            && matches!(&self.ty, syn::Type::Path(path) if path.path.segments.last().is_some_and(|s| s.ident == "Result"))
+    }
+
     /// Returns the name that maps to the actuall sql column
     /// By default this is the same as the rust field name but can be overwritten by `#[from_row(rename = "..")]`.
     fn column_name(&self) -> String {
@@ -272,88 +1170,249 @@ impl FromRowField {
     /// type specified in the `[try]_from` attribute.
     ///
     /// Note: '__from_row_lifetime is used here to avoid conflicts with common user-specified lifetimes like 'a
-    fn add_predicates(&self, predicates: &mut Vec<TokenStream2>) -> Result<()> {
+    ///
+    /// When `bound` is specified, its predicates are pushed instead.
+    fn add_predicates(
+        &self,
+        predicates: &mut Vec<TokenStream2>,
+        krate: &syn::Path,
+    ) -> Result<()> {
+        if let Some(bound) = &self.bound {
+            predicates.extend(parse_bound(bound)?);
+            return Ok(());
+        }
+
         let target_ty = &self.target_ty()?;
         let ty = &self.ty;
 
         if self.try_from_fn.is_none() && self.from_fn.is_none() {
             predicates.push(if self.flatten || self.join {
-                quote! (#target_ty: postgres_from_row::FromRow)
+                quote! (#target_ty: #krate::FromRow)
             } else {
-                quote! (#target_ty: for<'__from_row_lifetime> postgres_from_row::tokio_postgres::types::FromSql<'__from_row_lifetime>)
+                quote! (#target_ty: for<'__from_row_lifetime> #krate::tokio_postgres::types::FromSql<'__from_row_lifetime>)
             });
         }
 
+        if self.left {
+            predicates.push(quote!(#ty: std::default::Default));
+        }
+
+        if self.enum_text {
+            let variant_ty = schema::option_inner(ty).unwrap_or(ty);
+            predicates.push(quote!(#variant_ty: #krate::TextEnum));
+        }
+
+        if (self.trim || self.lowercase) && self.try_from_fn.is_none() && self.from_fn.is_none() {
+            predicates.push(quote!(#target_ty: #krate::__private::Normalize));
+        }
+
         if self.from.is_some() {
             predicates.push(quote!(#ty: std::convert::From<#target_ty>))
         } else if self.try_from.is_some() {
             let try_from = quote!(std::convert::TryFrom<#target_ty>);
 
             predicates.push(quote!(#ty: #try_from));
-            predicates.push(quote!(postgres_from_row::tokio_postgres::Error: std::convert::From<<#ty as #try_from>::Error>));
-            predicates.push(quote!(<#ty as #try_from>::Error: std::fmt::Debug));
+            predicates.push(quote!(<#ty as #try_from>::Error: std::convert::Into<std::boxed::Box<dyn std::error::Error + std::marker::Send + std::marker::Sync>>));
+        }
+
+        Ok(())
+    }
+
+    /// Pushes the where clause predicates needed by the trait of `companion` for this field,
+    /// besides the ones of `add_predicates`.
+    ///
+    /// For `simple_query` this is `T: postgres_from_row::simple::FromText`, or `T: postgres_from_row::simple::FromSimpleRow`
+    /// when using `flatten`, for `copy_out` it's `T: postgres_from_row::copy::FromCopyRow` when using `flatten`,
+    /// for `copy_in` it's `T: postgres_from_row::copy::ToCopyRow` when using `flatten`, or `T: ToSql + Sync`,
+    /// and for `mock` it's `T: postgres_from_row::test_util::FromMockRow` when using `flatten` or `join`.
+    fn add_companion_predicates(
+        &self,
+        predicates: &mut Vec<TokenStream2>,
+        krate: &syn::Path,
+        companion: Companion,
+    ) -> Result<()> {
+        if self.bound.is_some() || self.try_from_fn.is_some() || self.from_fn.is_some() {
+            return Ok(());
+        }
+
+        let ty = &self.ty;
+        let target_ty = &self.target_ty()?;
+        match companion {
+            Companion::SimpleQuery if self.flatten => {
+                predicates.push(quote!(#target_ty: #krate::simple::FromSimpleRow))
+            }
+            Companion::SimpleQuery => predicates.push(quote!(#target_ty: #krate::simple::FromText)),
+            Companion::CopyOut if self.flatten => {
+                predicates.push(quote!(#target_ty: #krate::copy::FromCopyRow))
+            }
+            Companion::CopyOut => {}
+            Companion::CopyIn if self.flatten => {
+                predicates.push(quote!(#ty: #krate::copy::ToCopyRow))
+            }
+            Companion::CopyIn => predicates.push(
+                quote!(#ty: #krate::tokio_postgres::types::ToSql + std::marker::Sync),
+            ),
+            Companion::Mock if self.flatten || self.join => {
+                predicates.push(quote!(#target_ty: #krate::test_util::FromMockRow))
+            }
+            Companion::Mock => {}
         }
 
         Ok(())
     }
 
     /// Generate the expression that counts how many rows this field contributes to the total count
-    fn generate_column_count(&self) -> Result<TokenStream2> {
-        let target_ty = if self.from_fn.is_none() && self.try_from_fn.is_none() {
-            self.target_ty()?
-        } else {
-            quote!(_)
-        };
+    fn generate_column_count(&self, krate: &syn::Path) -> Result<TokenStream2> {
+        let target_ty = self.target_ty()?;
         if self.flatten || self.join {
-            Ok(quote!(<#target_ty as postgres_from_row::FromRow>::COLUMN_COUNT))
+            Ok(quote!(<#target_ty as #krate::FromRow>::COLUMN_COUNT))
         } else {
             Ok(quote!(1))
         }
     }
 
     /// Generate the line needed to retrieve this field from a row when calling `try_from_row`.
-    fn generate_try_from_row(&self, fields: &[FromRowField]) -> Result<TokenStream2> {
+    ///
+    /// With `mock`, the line reads from a `MockRow` when calling `try_from_mock_row_joined` instead.
+    fn generate_try_from_row(
+        &self,
+        fields: &[FromRowField],
+        krate: &syn::Path,
+        mock: bool,
+    ) -> Result<TokenStream2> {
         let ident = self.ident.as_ref().unwrap();
-        let field_ty = &self.ty;
-        let target_ty = if self.from_fn.is_none() && self.try_from_fn.is_none() {
-            self.target_ty()?
-        } else {
-            quote!(_)
-        };
+        let target_ty = self.target_ty()?;
+
+        let target_joined = try_from_row_joined_path(&target_ty, krate, mock);
+        let columns_are_null = columns_are_null_path(krate, mock);
 
-        let mut base = if self.flatten {
-            quote!(std::option::Option::expect(<#target_ty as postgres_from_row::FromRow>::try_from_row_joined(std::option::Option::None, __row, {
+        let base = if self.flatten {
+            let base = quote!(std::option::Option::expect(#target_joined(std::option::Option::None, __row, {
                 let j = __i;
-                __i += <#target_ty as postgres_from_row::FromRow>::COLUMN_COUNT;
+                __i += <#target_ty as #krate::FromRow>::COLUMN_COUNT;
                 j
-            })?, "when try_from_row_joined is called with last = None it should never return None"))
+            })?, "when try_from_row_joined is called with last = None it should never return None"));
+            match self.none_when {
+                Some(policy) => {
+                    let policy = policy.path(krate);
+                    let group_is_none = if mock {
+                        quote!(#krate::test_util::group_is_none)
+                    } else {
+                        quote!(#krate::__private::group_is_none)
+                    };
+                    let inner = schema::option_inner(&self.ty).unwrap();
+                    quote!(
+                        if #group_is_none::<#inner>(__row, __i, <#target_ty as #krate::FromRow>::COLUMN_COUNT, #policy)? {
+                            #krate::__private::null_row();
+                            __i += <#target_ty as #krate::FromRow>::COLUMN_COUNT;
+                            std::option::Option::None
+                        } else {
+                            #base
+                        }
+                    )
+                }
+                None => base,
+            }
         } else if self.join {
-            let comparisons = fields.iter().filter(|x| !x.join).map(|x| x.ident.as_ref().unwrap()).map(|ident| {
+            let comparisons = compared_fields(fields).map(|ident| {
                 quote!(__last.#ident == #ident)
-            });
-            quote!(
-                if let std::option::Option::Some(mut __last) = __last.as_deref_mut().filter(|__last| true #(&& #comparisons)*) {
-                    let item = <#target_ty as postgres_from_row::FromRow>::try_from_row_joined(std::option::Option::Some(&mut __last.#ident), __row, {
+            }).collect::<Vec<_>>();
+            let join_last = match self.duplicates {
+                None | Some(Duplicates::Merge) => quote!(
+                    let item = #target_joined(std::option::Option::Some(&mut __last.#ident), __row, {
                         let j = __i;
-                        __i += <#target_ty as postgres_from_row::FromRow>::COLUMN_COUNT;
+                        __i += <#target_ty as #krate::FromRow>::COLUMN_COUNT;
                         j
                     })?;
                     match item {
                         std::option::Option::None => return std::result::Result::Ok(std::option::Option::None),
                         std::option::Option::Some(item) => item,
                     }
+                ),
+                Some(duplicates) => {
+                    let elem = vec_inner_type(&self.ty).unwrap();
+                    let duplicates = match duplicates {
+                        Duplicates::Merge => unreachable!(),
+                        Duplicates::Keep => quote!(Keep),
+                        Duplicates::Dedup => quote!(Dedup),
+                        Duplicates::Error => quote!(Error),
+                    };
+                    let elem_joined = try_from_row_joined_path(elem, krate, mock);
+                    let same = match &self.duplicates_by {
+                        Some(by) => quote!(|__a: &#elem, __b: &#elem| __a.#by == __b.#by),
+                        None => quote!(|__a: &#elem, __b: &#elem| __a == __b),
+                    };
+                    quote!(
+                        #krate::__private::join_vec(&mut __last.#ident, #krate::__private::Duplicates::#duplicates, #same, |__last| #elem_joined(__last, __row, __i))?;
+                        return std::result::Result::Ok(std::option::Option::None);
+                    )
+                }
+            };
+            let new_child = quote!(
+                std::option::Option::expect(#target_joined(std::option::Option::None, __row, {
+                    let j = __i;
+                    __i += <#target_ty as #krate::FromRow>::COLUMN_COUNT;
+                    j
+                })?, "when try_from_row_joined is called with last = None it should never return None")
+            );
+            let join = quote!(
+                if let std::option::Option::Some(mut __last) = __last.as_deref_mut().filter(|__last| true #(&& #comparisons)*) {
+                    #join_last
                 } else {
-                    std::option::Option::expect(<#target_ty as postgres_from_row::FromRow>::try_from_row_joined(std::option::Option::None, __row, {
-                        let j = __i;
-                        __i += <#target_ty as postgres_from_row::FromRow>::COLUMN_COUNT;
-                        j
-                    })?, "when try_from_row_joined is called with last = None it should never return None")
+                    #new_child
                 }
-            )
+            );
+            let join_fields = fields.iter().filter(|f| f.join).collect::<Vec<_>>();
+            if join_fields.len() > 1 {
+                // merging into the last value is done for all the joined fields at once, see `generate_join_merge`
+                let new_child = if self.left {
+                    let ty = &self.ty;
+                    quote!(
+                        if #columns_are_null(__row, __i, <#target_ty as #krate::FromRow>::COLUMN_COUNT)? {
+                            #krate::__private::null_row();
+                            __i += <#target_ty as #krate::FromRow>::COLUMN_COUNT;
+                            <#ty as std::default::Default>::default()
+                        } else {
+                            #new_child
+                        }
+                    )
+                } else {
+                    new_child
+                };
+                if std::ptr::eq(join_fields[0], self) {
+                    new_child
+                } else {
+                    quote!({
+                        let __uncounted = #krate::__private::UncountedRows::start();
+                        #new_child
+                    })
+                }
+            } else if self.left {
+                let ty = &self.ty;
+                quote!(
+                    if #columns_are_null(__row, __i, <#target_ty as #krate::FromRow>::COLUMN_COUNT)? {
+                        #krate::__private::null_row();
+                        if __last.as_deref_mut().filter(|__last| true #(&& #comparisons)*).is_some() {
+                            return std::result::Result::Ok(std::option::Option::None);
+                        }
+                        __i += <#target_ty as #krate::FromRow>::COLUMN_COUNT;
+                        <#ty as std::default::Default>::default()
+                    } else {
+                        #join
+                    }
+                )
+            } else {
+                join
+            }
         } else {
+            let get = if mock {
+                quote!(#krate::test_util::MockRow::try_get::<#target_ty>)
+            } else {
+                quote!(#krate::tokio_postgres::Row::try_get::<_, #target_ty>)
+            };
             quote!(
                 // postgres_from_row::tokio_postgres::Row::try_get::<&str, #target_ty>(__row, #column_name)?
-                postgres_from_row::tokio_postgres::Row::try_get::<_, #target_ty>(__row, {
+                #get(__row, {
                     let j = __i;
                     __i += 1;
                     j
@@ -361,83 +1420,565 @@ impl FromRowField {
             )
         };
 
+        let field_name = ident.to_string();
+        let base = self.generate_conversion(base, krate)?;
+
+        // joined fields may return early, so their errors are not wrapped
+        let base = if self.join {
+            base
+        } else {
+            let column = if self.flatten {
+                quote!(std::option::Option::None)
+            } else if mock {
+                quote!(#krate::test_util::MockRow::column_name(__row, __column))
+            } else {
+                quote!(std::option::Option::Some(#krate::tokio_postgres::Row::columns(__row)[__column].name()))
+            };
+            quote!(
+                (|| {
+                    let __value = #base;
+                    std::result::Result::<_, #krate::Error>::Ok(__value)
+                })().map_err(|__error| {
+                    #krate::__private::field_error(__error, std::any::type_name::<Self>(), #field_name, #column)
+                })?
+            )
+        };
+
+        Ok(quote!(
+            let __column = __i;
+            let __timer = #krate::__private::FieldTimer::start(__column);
+            let #ident = #base;
+            __timer.finish(std::any::type_name::<Self>(), #field_name);
+        ))
+    }
+
+    /// Generate the lines merging a row into this joined field of the last value, see `DeriveFromRow::generate_join_merge`.
+    ///
+    /// Unless `counted`, the null rows skipped by this field are not counted by the `verify` feature.
+    fn generate_join_merge(&self, krate: &syn::Path, mock: bool, counted: bool) -> Result<TokenStream2> {
+        let ident = self.ident.as_ref().unwrap();
+        let target_ty = self.target_ty()?;
+        let target_joined = try_from_row_joined_path(&target_ty, krate, mock);
+        let columns_are_null = columns_are_null_path(krate, mock);
+
+        let mut merge = match self.duplicates {
+            None | Some(Duplicates::Merge) => {
+                let message = format!("a row could not be merged into the joined field `{ident}` of the last value, which is required when there are several joined fields");
+                quote!(
+                    if #target_joined(std::option::Option::Some(&mut __last.#ident), __row, __i)?.is_some() {
+                        return std::result::Result::Err(#krate::Error::conversion(#message));
+                    }
+                )
+            }
+            Some(duplicates) => {
+                let elem = vec_inner_type(&self.ty).unwrap();
+                let elem_joined = try_from_row_joined_path(elem, krate, mock);
+                let duplicates = match duplicates {
+                    Duplicates::Merge => unreachable!(),
+                    Duplicates::Keep => quote!(Keep),
+                    Duplicates::Dedup => quote!(Dedup),
+                    Duplicates::Error => quote!(Error),
+                };
+                let same = match &self.duplicates_by {
+                    Some(by) => quote!(|__a: &#elem, __b: &#elem| __a.#by == __b.#by),
+                    None => quote!(|__a: &#elem, __b: &#elem| __a == __b),
+                };
+                quote!(
+                    #krate::__private::join_vec(&mut __last.#ident, #krate::__private::Duplicates::#duplicates, #same, |__last| #elem_joined(__last, __row, __i))?;
+                )
+            }
+        };
+
+        if self.left {
+            merge = quote!(
+                if #columns_are_null(__row, __i, <#target_ty as #krate::FromRow>::COLUMN_COUNT)? {
+                    #krate::__private::null_row();
+                } else {
+                    #merge
+                }
+            );
+        }
+
+        let uncounted = if counted {
+            quote!()
+        } else {
+            quote!(let __uncounted = #krate::__private::UncountedRows::start();)
+        };
+
+        Ok(quote!({
+            #uncounted
+            #merge
+            __i += <#target_ty as #krate::FromRow>::COLUMN_COUNT;
+        }))
+    }
+
+    /// Generate the line needed to retrieve this field from a row when calling `try_from_row_by_name`.
+    ///
+    /// With `mock`, the line reads from a `MockRow` when calling `try_from_mock_row_by_name` instead.
+    fn generate_try_from_row_by_name(&self, krate: &syn::Path, mock: bool) -> Result<TokenStream2> {
+        let ident = self.ident.as_ref().unwrap();
+        let column_name = self.column_name();
+        let target_ty = self.target_ty()?;
+
+        let (helpers, by_name) = if mock {
+            (
+                quote!(#krate::test_util),
+                quote!(<#target_ty as #krate::test_util::FromMockRow>::try_from_mock_row_by_name(__row)?),
+            )
+        } else {
+            (
+                quote!(#krate::__private),
+                quote!(<#target_ty as #krate::FromRow>::try_from_row_by_name(__row)?),
+            )
+        };
+        let base = if self.left {
+            let ty = &self.ty;
+            quote!(
+                if #helpers::named_columns_are_null(__row, &<#target_ty as #krate::FromRow>::report_expected_columns())? {
+                    <#ty as std::default::Default>::default()
+                } else {
+                    #by_name
+                }
+            )
+        } else if let Some(policy) = self.none_when {
+            let policy = policy.path(krate);
+            let inner = schema::option_inner(&self.ty).unwrap();
+            quote!(
+                if #helpers::named_group_is_none::<#inner>(__row, &<#target_ty as #krate::FromRow>::report_expected_columns(), #policy)? {
+                    std::option::Option::None
+                } else {
+                    #by_name
+                }
+            )
+        } else if self.flatten || self.join {
+            by_name
+        } else if mock {
+            quote!(#krate::test_util::MockRow::try_get_by_name::<#target_ty>(__row, #column_name)?)
+        } else {
+            quote!(#krate::tokio_postgres::Row::try_get::<&str, #target_ty>(__row, #column_name)?)
+        };
+
+        let base = self.generate_conversion(base, krate)?;
+        let field_name = ident.to_string();
+        let column = if self.flatten || self.join {
+            quote!(std::option::Option::None)
+        } else {
+            quote!(std::option::Option::Some(#column_name))
+        };
+        let base = quote!(
+            (|| {
+                let __value = #base;
+                std::result::Result::<_, #krate::Error>::Ok(__value)
+            })().map_err(|__error| {
+                #krate::__private::field_error(__error, std::any::type_name::<Self>(), #field_name, #column)
+            })?
+        );
+
+        if self.context {
+            Ok(quote!(
+                let __column = #krate::__private::column_index(__row, #column_name);
+                let #ident = #base;
+            ))
+        } else {
+            Ok(quote!(let #ident = #base;))
+        }
+    }
+
+    /// Generate the line needed to retrieve this field from a text-format row when calling `try_from_simple_row`.
+    fn generate_try_from_simple_row(&self, krate: &syn::Path) -> Result<TokenStream2> {
+        let ident = self.ident.as_ref().unwrap();
+        let column_name = self.column_name();
+        let target_ty = self.target_ty()?;
+
+        let base = if self.flatten {
+            quote!(<#target_ty as #krate::simple::FromSimpleRow>::try_from_simple_row(__row)?)
+        } else {
+            quote!(#krate::simple::get::<#target_ty>(__row, #column_name)?)
+        };
+
+        let base = self.generate_conversion(base, krate)?;
+
+        Ok(quote!(let #ident = #base;))
+    }
+
+    /// Generate the line needed to retrieve this field from a binary copy row when calling `try_from_copy_row_at`.
+    fn generate_try_from_copy_row(&self, krate: &syn::Path) -> Result<TokenStream2> {
+        let ident = self.ident.as_ref().unwrap();
+        let target_ty = self.target_ty()?;
+
+        let base = if self.flatten {
+            quote!(<#target_ty as #krate::copy::FromCopyRow>::try_from_copy_row_at(__row, {
+                let j = __i;
+                __i += <#target_ty as #krate::FromRow>::COLUMN_COUNT;
+                j
+            })?)
+        } else {
+            quote!(#krate::tokio_postgres::binary_copy::BinaryCopyOutRow::try_get::<#target_ty>(__row, {
+                let j = __i;
+                __i += 1;
+                j
+            })?)
+        };
+
+        let base = self.generate_conversion(base, krate)?;
+
+        Ok(quote!(let #ident = #base;))
+    }
+
+    /// Generate the line pushing the values of this field when calling `copy_values`.
+    fn generate_copy_values(&self, krate: &syn::Path) -> TokenStream2 {
+        let ident = self.ident.as_ref().unwrap();
+        let ty = &self.ty;
+        if self.flatten {
+            quote!(<#ty as #krate::copy::ToCopyRow>::copy_values(&self.#ident, __values);)
+        } else {
+            quote!(__values.push(&self.#ident);)
+        }
+    }
+
+    /// Wraps the expression extracting the value from the row with the conversion of this field, if any.
+    ///
+    /// Expects the index of the column to be in `__column` when using `#[from_row(context)]`.
+    fn generate_conversion(
+        &self,
+        mut base: TokenStream2,
+        krate: &syn::Path,
+    ) -> Result<TokenStream2> {
+        let field_ty = &self.ty;
+        let field_name = self.ident.as_ref().unwrap().to_string();
+        let target_ty = self.target_ty()?;
+
+        if self.trim {
+            base = quote!(#krate::__private::trim(#base));
+        }
+
+        if self.lowercase {
+            base = quote!(#krate::__private::lowercase(#base));
+        }
+
+        if self.context {
+            // SAFETY: the context is a temporary of the call to the function, so it does not outlive the conversion
+            base = quote!(#base, &unsafe { #krate::ConversionContext::new(__row, __column, std::any::type_name::<Self>(), #field_name) });
+        }
+
         if let Some(from_fn) = &self.from_fn {
-            let from_fn = TokenStream2::from_str(from_fn)?;
-            base = quote!(#from_fn(#base));
+            base = quote!((#from_fn)(#base));
         } else if let Some(try_from_fn) = &self.try_from_fn {
-            let try_from_fn = TokenStream2::from_str(try_from_fn)?;
-            base = quote!(#try_from_fn(#base)?);
+            base = quote!((#try_from_fn)(#base).map_err(#krate::Error::conversion)?);
         } else if self.from.is_some() {
             base = quote!(<#field_ty as std::convert::From<#target_ty>>::from(#base));
         } else if self.try_from.is_some() {
-            base = quote!(<#field_ty as std::convert::TryFrom<#target_ty>>::try_from(#base)?);
+            base = quote!(<#field_ty as std::convert::TryFrom<#target_ty>>::try_from(#base).map_err(#krate::Error::conversion)?);
+        } else if self.enum_text {
+            base = match schema::option_inner(field_ty) {
+                Some(variant_ty) => quote!(std::option::Option::map(#base, #krate::__private::enum_text::<#variant_ty>).transpose()?),
+                None => quote!(#krate::__private::enum_text::<#field_ty>(#base)?),
+            };
         };
 
-        Ok(quote!(let #ident = #base;))
+        Ok(base)
     }
 
-    fn generate_report_expected_columns_to_vec(&self) -> Result<TokenStream2> {
+    fn generate_report_expected_columns_to_vec(&self, krate: &syn::Path) -> Result<TokenStream2> {
         let column_name = self.column_name();
-        let target_ty = if self.from_fn.is_none() && self.try_from_fn.is_none() {
-            self.target_ty()?
-        } else {
-            quote!(_)
-        };
+        let target_ty = self.target_ty()?;
         if self.flatten || self.join {
+            let field_name = self.ident.as_ref().unwrap().to_string();
             Ok(quote!(
-                match <#target_ty as postgres_from_row::FromRow>::report_expected_columns() {
-                    postgres_from_row::ExpectedColumns::Borrowed(slice) => {
-                        expected.extend_from_slice(slice);
-                    }
-                    postgres_from_row::ExpectedColumns::Owned(mut vec) => {
-                        expected.append(&mut vec);
-                    }
-                }
+                expected.extend(
+                    <#target_ty as #krate::FromRow>::report_expected_columns()
+                        .iter()
+                        .map(|column| column.in_field(std::any::type_name::<Self>, #field_name)),
+                );
             ))
         } else {
+            let expected_column = self.expected_column(krate, &quote!(Self), &target_ty, &column_name);
             Ok(quote!(
-                expected.push(postgres_from_row::ExpectedColumn::new::<#target_ty>(std::option::Option::Some(#column_name)));
+                expected.push(#expected_column);
             ))
         }
     }
 
-    fn generate_report_expected_columns_to_const_slice(&self) -> Result<TokenStream2> {
-        
+    fn generate_report_expected_columns_to_const_slice(
+        &self,
+        krate: &syn::Path,
+        owner: &TokenStream2,
+    ) -> Result<TokenStream2> {
         let column_name = self.column_name();
-        let target_ty = if self.from_fn.is_none() && self.try_from_fn.is_none() {
-            self.target_ty()?
-        } else {
-            // this does not work, obviously, this feature is broken for the time being
-            quote!(_)
-        };
+        let target_ty = self.target_ty()?;
         if self.flatten || self.join {
             unreachable!("generate_report_expected_columns_to_const_slice should not be called for flatten or join fields")
         }
-        Ok(quote!(
-            postgres_from_row::ExpectedColumn::new::<#target_ty>(std::option::Option::Some(#column_name))
-        ))
+        Ok(self.expected_column(krate, owner, &target_ty, &column_name))
     }
 
-    fn generate_try_assert_matches(&self) -> Result<TokenStream2> {
-        let column_name = self.column_name();
-        let target_ty = if self.from_fn.is_none() && self.try_from_fn.is_none() {
-            self.target_ty()?
+    /// Generates the `ExpectedColumn` of this field of `owner`, read as `target_ty`.
+    fn expected_column(
+        &self,
+        krate: &syn::Path,
+        owner: &TokenStream2,
+        target_ty: &TokenStream2,
+        column_name: &str,
+    ) -> TokenStream2 {
+        let field_name = self.ident.as_ref().unwrap().to_string();
+        let expected_column = expected_column(krate, target_ty, column_name);
+        let expected_column = quote!(#expected_column.in_field(std::any::type_name::<#owner>, #field_name));
+        if self.nullable {
+            quote!(#expected_column.assume_nullable())
         } else {
-            // this does not work, obviously, this feature is broken for the time being
-            quote!(_)
-        };
+            expected_column
+        }
+    }
+
+    /// Generate the lines that push the plan of this field in `dry_run`.
+    fn generate_dry_run(&self, krate: &syn::Path) -> Result<TokenStream2> {
+        let field_name = self.ident.as_ref().unwrap().to_string();
         if self.flatten || self.join {
+            let target_ty = self.target_ty()?;
+            let conversion = if self.flatten {
+                quote!(#krate::Conversion::Flatten)
+            } else {
+                quote!(#krate::Conversion::Join)
+            };
             Ok(quote!(
-                let (__column, __columns) = __columns.split_at(<#target_ty as postgres_from_row::FromRow>::COLUMN_COUNT);
-                <#target_ty as postgres_from_row::FromRow>::try_assert_matches(__column)?;
+                __plans.push(#krate::FieldPlan::nested(
+                    std::option::Option::Some(#field_name),
+                    __i,
+                    <#target_ty as #krate::FromRow>::COLUMN_COUNT,
+                    #conversion,
+                    <#target_ty as #krate::FromRow>::dry_run(__columns.get(__i..).unwrap_or(&[])),
+                ));
+                __i += <#target_ty as #krate::FromRow>::COLUMN_COUNT;
+            ))
+        } else {
+            let column_name = self.column_name();
+            let conversion = if self.from.is_some() {
+                quote!(#krate::Conversion::From)
+            } else if self.try_from.is_some() {
+                quote!(#krate::Conversion::TryFrom)
+            } else if self.from_fn.is_some() {
+                quote!(#krate::Conversion::FromFn)
+            } else if self.try_from_fn.is_some() {
+                quote!(#krate::Conversion::TryFromFn)
+            } else if self.enum_text {
+                quote!(#krate::Conversion::EnumText)
+            } else {
+                quote!(#krate::Conversion::Direct)
+            };
+            Ok(quote!(
+                __plans.push(#krate::FieldPlan::column(
+                    std::option::Option::Some(#field_name),
+                    __columns,
+                    __i,
+                    std::option::Option::Some(#column_name),
+                    #conversion,
+                ));
+                __i += 1;
+            ))
+        }
+    }
+
+    fn generate_try_assert_matches(&self, krate: &syn::Path) -> Result<TokenStream2> {
+        let column_name = self.column_name();
+        let target_ty = self.target_ty()?;
+        if self.flatten || self.join {
+            Ok(quote!(
+                let (__column, __columns) = __columns.split_at(<#target_ty as #krate::FromRow>::COLUMN_COUNT);
+                <#target_ty as #krate::FromRow>::try_assert_matches(__column)?;
             ))
         } else {
             Ok(quote!(
                 let (__column, __columns) = __columns.split_first().unwrap();
-                if __column.name() != #column_name || !<#target_ty as postgres_from_row::tokio_postgres::types::FromSql>::accepts(__column.type_()) {
+                if __column.name() != #column_name || !<#target_ty as #krate::tokio_postgres::types::FromSql>::accepts(__column.type_()) {
                     return std::result::Result::Err(());
                 }
             ))
         }
     }
 }
+
+/// A trait implemented next to `FromRow` when requested by a container attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Companion {
+    /// `FromSimpleRow`, see `#[from_row(simple_query)]`.
+    SimpleQuery,
+    /// `FromCopyRow`, see `#[from_row(copy_out)]`.
+    CopyOut,
+    /// `ToCopyRow`, see `#[from_row(copy_in)]`.
+    CopyIn,
+    /// `FromMockRow`, see `#[from_row(mock)]`.
+    Mock,
+}
+
+/// The policy given to `#[from_row(join, duplicates = "..")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+enum Duplicates {
+    /// Merge a row into the last child when possible, the default.
+    #[darling(rename = "merge")]
+    Merge,
+    /// Every row is a new child.
+    #[darling(rename = "keep")]
+    Keep,
+    /// Drop children that are the same as one already joined.
+    #[darling(rename = "dedup")]
+    Dedup,
+    /// Fail when a child is the same as one already joined.
+    #[darling(rename = "error")]
+    Error,
+}
+
+/// The policy given to `#[from_row(flatten, none_when = "..")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromMeta)]
+enum NullPolicy {
+    /// `None` when all the columns are null, like the `FromRow` implementation of `Option<T>`.
+    #[darling(rename = "all_null")]
+    All,
+    /// `None` when any of the columns is null.
+    #[darling(rename = "any_null")]
+    Any,
+    /// `None` when the first column is null, failing if some of the other columns are not.
+    #[darling(rename = "key_null")]
+    Key,
+}
+
+impl NullPolicy {
+    /// The path of the matching `__private::NullPolicy` variant.
+    fn path(self, krate: &syn::Path) -> TokenStream2 {
+        match self {
+            NullPolicy::All => quote!(#krate::__private::NullPolicy::AllNull),
+            NullPolicy::Any => quote!(#krate::__private::NullPolicy::AnyNull),
+            NullPolicy::Key => quote!(#krate::__private::NullPolicy::KeyNull),
+        }
+    }
+}
+
+/// Parses the where clause predicates of a `bound` attribute.
+fn parse_bound(bound: &str) -> Result<Vec<TokenStream2>> {
+    let predicates = syn::parse::Parser::parse_str(
+        syn::punctuated::Punctuated::<syn::WherePredicate, syn::Token![,]>::parse_terminated,
+        bound,
+    )?;
+    Ok(predicates.iter().map(ToTokens::to_token_stream).collect())
+}
+
+/// Formats a type for documentation, without the spaces `to_string` puts between every token.
+fn type_to_string(ty: &TokenStream2) -> String {
+    ty.to_string()
+        .replace(" < ", "<")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+}
+
+/// The fields compared to decide if a row belongs to the last value when joining,
+/// the `key` fields if there are any, otherwise every field that is not joined, a flattened `Result` or marked with `skip_compare`.
+fn compared_fields(fields: &[FromRowField]) -> impl Iterator<Item = &syn::Ident> {
+    let keys = fields.iter().any(|f| f.key);
+    fields
+        .iter()
+        .filter(move |f| if keys { f.key } else { !f.join && !f.skip_compare && !f.is_flattened_result() })
+        .map(|f| f.ident.as_ref().unwrap())
+}
+
+/// Generates the `ExpectedColumn` of a column decoded as `ty`.
+///
+/// `Json<T>` and `Option<Json<T>>` are recognized by name, so that reports can show `T`.
+fn expected_column(krate: &syn::Path, ty: &TokenStream2, column_name: &str) -> TokenStream2 {
+    match syn::parse2::<syn::Type>(ty.clone())
+        .ok()
+        .as_ref()
+        .and_then(json_inner_type)
+    {
+        Some(inner) => quote!(
+            #krate::ExpectedColumn::new_json::<#ty, #inner>(std::option::Option::Some(#column_name))
+        ),
+        None => quote!(
+            #krate::ExpectedColumn::new::<#ty>(std::option::Option::Some(#column_name))
+        ),
+    }
+}
+
+/// Returns `T` if `ty` is `Vec<T>`.
+fn vec_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let last = path.path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+    match args.args.iter().collect::<Vec<_>>()[..] {
+        [syn::GenericArgument::Type(inner)] if last.ident == "Vec" => Some(inner),
+        _ => None,
+    }
+}
+
+/// The path of the function converting a row into `ty` given the last value, for `FromRow` or `FromMockRow` with `mock`.
+fn try_from_row_joined_path(ty: &dyn ToTokens, krate: &syn::Path, mock: bool) -> TokenStream2 {
+    if mock {
+        quote!(<#ty as #krate::test_util::FromMockRow>::try_from_mock_row_joined)
+    } else {
+        quote!(<#ty as #krate::FromRow>::try_from_row_joined)
+    }
+}
+
+/// The path of the function checking whether some columns of a row are all null, or of a mock row with `mock`.
+fn columns_are_null_path(krate: &syn::Path, mock: bool) -> TokenStream2 {
+    if mock {
+        quote!(#krate::test_util::columns_are_null)
+    } else {
+        quote!(#krate::__private::columns_are_null)
+    }
+}
+
+/// The types that can be used with `#[from_row(join)]`.
+const JOIN_CONTAINERS: [&str; 4] = ["Vec<T>", "Option<T>", "BTreeMap<K, V>", "Result<T, E>"];
+
+/// Whether `ty` is one of the `JOIN_CONTAINERS`, judging by the last segment of its path.
+fn is_join_container(ty: &syn::Type) -> bool {
+    let syn::Type::Path(path) = ty else {
+        return false;
+    };
+    path.path.segments.last().is_some_and(|last| {
+        matches!(last.arguments, syn::PathArguments::AngleBracketed(_))
+            && ["Vec", "Option", "BTreeMap", "Result"]
+                .iter()
+                .any(|container| last.ident == container)
+    })
+}
+
+/// Whether `ty` is a `ViaTryFrom<T>`, or a container of one, which reads every column of the row.
+fn reads_whole_row(ty: &syn::Type) -> bool {
+    let syn::Type::Path(path) = ty else {
+        return false;
+    };
+    path.path.segments.last().is_some_and(|last| {
+        last.ident == "ViaTryFrom"
+            || matches!(&last.arguments, syn::PathArguments::AngleBracketed(args) if args.args.iter().any(|arg| {
+                matches!(arg, syn::GenericArgument::Type(inner) if reads_whole_row(inner))
+            }))
+    })
+}
+
+/// Returns `T` if `ty` is `Json<T>` or `Option<Json<T>>`.
+fn json_inner_type(ty: &syn::Type) -> Option<syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let last = path.path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+    let [syn::GenericArgument::Type(inner)] = args.args.iter().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    match last.ident.to_string().as_str() {
+        "Json" => Some(inner.clone()),
+        "Option" => json_inner_type(inner),
+        _ => None,
+    }
+}
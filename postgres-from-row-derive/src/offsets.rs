@@ -0,0 +1,72 @@
+//! Generation of the column offset constants of structs with a `#[from_row(offsets)]` attribute.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, Result};
+
+use crate::DeriveFromRow;
+
+/// Generates the `{name}_columns` module, with the offset of the first column read by each field.
+///
+/// The offsets of the fields after a flattened or joined field add up the `COLUMN_COUNT` of its type.
+pub(crate) fn generate(derive: &DeriveFromRow) -> Result<TokenStream2> {
+    if !derive.offsets {
+        return Ok(quote!());
+    }
+
+    if !derive.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            derive.ident.span(),
+            "`#[from_row(offsets)]` can't be used on generic structs, since their offsets are not known",
+        ));
+    }
+
+    let ident = &derive.ident;
+    let vis = &derive.vis;
+    let krate = derive.krate();
+    let module = format_ident!("{}_columns", snake_case(&ident.unraw().to_string()));
+
+    let mut offset = quote!(0);
+    let mut constants = Vec::new();
+    for field in derive.fields() {
+        let field_ident = field.ident.as_ref().unwrap();
+        let name = format_ident!("{}", field_ident.unraw().to_string().to_ascii_uppercase());
+        let count = field.generate_column_count(&krate)?;
+        let doc = if field.flatten || field.join {
+            format!("The offset of the first column of `{field_ident}`.")
+        } else {
+            format!("The offset of the column `{}`.", field.column_name())
+        };
+        constants.push(quote! {
+            #[doc = #doc]
+            pub const #name: usize = #offset;
+        });
+        offset = quote!(#name + #count);
+    }
+
+    let doc = format!(
+        "The offsets of the columns read by each field of [`{ident}`], generated by `#[from_row(offsets)]`."
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[allow(unused_imports)]
+        #vis mod #module {
+            use super::*;
+
+            #(#constants)*
+        }
+    })
+}
+
+/// Converts the name of a struct from PascalCase to snake_case.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
@@ -0,0 +1,143 @@
+//! Generation of the `Partial` companion struct of structs with a `#[from_row(partial)]` attribute.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::Result;
+
+use crate::DeriveFromRow;
+
+/// Generates the `Partial` struct and its implementations, if the struct is marked with `partial`.
+///
+/// Every field of the partial struct is an `Option`, which is `None` when the columns of the field
+/// are absent from the row, so it can be read from a `SELECT` of any subset of the columns.
+pub(crate) fn generate(derive: &DeriveFromRow) -> Result<TokenStream2> {
+    if !derive.partial {
+        return Ok(quote!());
+    }
+
+    let ident = &derive.ident;
+    let partial = format_ident!("{}Partial", ident);
+    let vis = &derive.vis;
+    let krate = derive.krate();
+    let generics = &derive.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let original_predicates = where_clause
+        .map(|w| w.predicates.iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+    let predicates = derive.predicates(None)?;
+
+    let fields = derive.fields();
+    let idents = fields
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let declarations = fields.iter().map(|f| {
+        let vis = &f.vis;
+        let ident = f.ident.as_ref().unwrap();
+        let ty = &f.ty;
+        quote!(#vis #ident: std::option::Option<#ty>)
+    });
+
+    let bindings = fields
+        .iter()
+        .map(|f| {
+            let ident = f.ident.as_ref().unwrap();
+            let binding = f.generate_try_from_row_by_name(&krate, false)?;
+            let present = if f.flatten {
+                let target_ty = f.target_ty()?;
+                quote!(#krate::__private::has_named_columns(__row, &<#target_ty as #krate::FromRow>::report_expected_columns()))
+            } else {
+                let column_name = f.column_name();
+                quote!(#krate::__private::has_column(__row, #column_name))
+            };
+            Ok(quote! {
+                let #ident = if #present {
+                    #binding
+                    std::option::Option::Some(#ident)
+                } else {
+                    std::option::Option::None
+                };
+            })
+        })
+        .collect::<Result<TokenStream2>>()?;
+
+    let masked = fields.iter().filter(|f| !f.flatten).map(|f| {
+        let ident = f.ident.as_ref().unwrap();
+        let column_name = f.column_name();
+        quote! {
+            if self.#ident.is_some() {
+                __mask.insert(#column_name);
+            }
+        }
+    });
+
+    let doc = format!(
+        "The fields of [`{ident}`] whose columns were present in a row, generated by `#[from_row(partial)]`."
+    );
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #partial #generics #where_clause {
+            #(#declarations),*
+        }
+
+        impl #impl_generics std::default::Default for #partial #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#idents: std::option::Option::None),*
+                }
+            }
+        }
+
+        impl #impl_generics std::convert::From<#ident #ty_generics> for #partial #ty_generics #where_clause {
+            fn from(__value: #ident #ty_generics) -> Self {
+                Self {
+                    #(#idents: std::option::Option::Some(__value.#idents)),*
+                }
+            }
+        }
+
+        impl #impl_generics #partial #ty_generics #where_clause {
+            /// Overwrites the fields of `target` with the ones that are present.
+            #vis fn apply_to(self, target: &mut #ident #ty_generics) {
+                #(
+                    if let std::option::Option::Some(__value) = self.#idents {
+                        target.#idents = __value;
+                    }
+                )*
+            }
+
+            /// The columns of the fields that are present, not counting flattened fields.
+            #vis fn field_mask(&self) -> #krate::FieldMask {
+                let mut __mask = #krate::FieldMask::new();
+                #(#masked)*
+                __mask
+            }
+        }
+
+        impl #impl_generics #krate::FromRow for #partial #ty_generics where #(#original_predicates,)* #(#predicates),* {
+            const COLUMN_COUNT: std::primitive::usize = 0;
+            const JOINED: std::primitive::bool = false;
+            fn try_from_row_joined(_: std::option::Option<&mut Self>, __row: &#krate::tokio_postgres::Row, _: std::primitive::usize) -> std::result::Result<std::option::Option<Self>, #krate::Error> {
+                #bindings
+                std::result::Result::Ok(std::option::Option::Some(Self {
+                    #(#idents),*
+                }))
+            }
+            fn report_expected_columns() -> #krate::ExpectedColumns {
+                let mut __columns = <#ident #ty_generics as #krate::FromRow>::report_expected_columns().into_owned();
+                for __column in &mut __columns {
+                    __column.set_nullable();
+                }
+                __columns.into()
+            }
+            fn try_assert_matches(__columns: &[#krate::tokio_postgres::Column]) -> std::result::Result<(), ()> {
+                if #krate::__private::partial_matches(&Self::report_expected_columns(), __columns) {
+                    std::result::Result::Ok(())
+                } else {
+                    std::result::Result::Err(())
+                }
+            }
+        }
+    })
+}
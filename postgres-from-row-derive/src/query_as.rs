@@ -0,0 +1,337 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Expr, LitStr, Result, Token, Type,
+};
+
+use crate::config::Config;
+
+/// The input of `query_as!(Type, "SQL", params...)`.
+pub(crate) struct QueryAs {
+    ty: Type,
+    sql: LitStr,
+    params: Punctuated<Expr, Token![,]>,
+}
+
+impl Parse for QueryAs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ty = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let sql = input.parse()?;
+        let params = if input.is_empty() {
+            Punctuated::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            Punctuated::parse_terminated(input)?
+        };
+        Ok(Self { ty, sql, params })
+    }
+}
+
+impl QueryAs {
+    /// Generates the column check and the `Query` constructor.
+    ///
+    /// The paths start with the `crate` of `from_row.toml`, like the ones of the derive.
+    pub(crate) fn generate(self) -> Result<TokenStream2> {
+        let Self { ty, sql, params } = self;
+        let config = Config::load()?;
+        let track_config = config.track();
+        let krate = config
+            .krate()
+            .unwrap_or_else(|| syn::parse_quote!(postgres_from_row));
+
+        let columns = select_list_columns(&sql.value())
+            .map_err(|message| syn::Error::new(sql.span(), message))?;
+
+        let check = columns.map(|columns| {
+            let ty_name = quote!(#ty).to_string();
+            let found = columns.iter().map(|name| match name {
+                Some(name) => quote!(std::option::Option::Some(#name)),
+                None => quote!(std::option::Option::None),
+            });
+            let arms = columns.iter().enumerate().map(|(i, name)| {
+                let message = match name {
+                    Some(name) => format!(
+                        "query_as!: column #{i} (`{name}`) of the query does not match the columns expected by `{ty_name}`"
+                    ),
+                    None => format!(
+                        "query_as!: column #{i} of the query does not match the columns expected by `{ty_name}`"
+                    ),
+                };
+                quote!(std::option::Option::Some(#i) => std::panic!(#message),)
+            });
+            let count_message = format!(
+                "query_as!: the query selects {} columns, which does not match the columns expected by `{ty_name}`",
+                columns.len()
+            );
+            quote! {
+                const _: () = match #krate::__private::column_names_mismatch(
+                    <#ty as #krate::FromRow>::COLUMN_NAMES,
+                    &[#(#found),*],
+                ) {
+                    std::option::Option::None => {}
+                    #(#arms)*
+                    std::option::Option::Some(_) => std::panic!(#count_message),
+                };
+            }
+        });
+
+        let params = params.iter().map(|param| {
+            quote!(&#param as &(dyn #krate::tokio_postgres::types::ToSql + std::marker::Sync))
+        });
+
+        Ok(quote! {
+            {
+                #track_config
+                #check
+                #krate::Query::<#ty>::new(#sql, std::vec![#(#params),*])
+            }
+        })
+    }
+}
+
+/// Extracts the output column names of the outermost `SELECT` (or `RETURNING`) list of `sql`.
+///
+/// Returns `None` when the number of columns can't be known (e.g. `*`), and `None` for
+/// individual columns whose name can't be determined without a database (e.g. unaliased expressions).
+fn select_list_columns(sql: &str) -> std::result::Result<Option<Vec<Option<String>>>, String> {
+    let tokens = tokenize(sql)?;
+
+    let start = tokens
+        .iter()
+        .position(|t| t.depth == 0 && t.is_keyword("select"))
+        .or_else(|| {
+            tokens
+                .iter()
+                .position(|t| t.depth == 0 && t.is_keyword("returning"))
+        })
+        .ok_or_else(|| "could not find a `SELECT` or `RETURNING` list in this query".to_string())?;
+
+    let mut tokens = &tokens[start + 1..];
+
+    // skip `ALL`, `DISTINCT` and `DISTINCT ON (...)`
+    if let [first, rest @ ..] = tokens {
+        if first.is_keyword("all") {
+            tokens = rest;
+        } else if first.is_keyword("distinct") {
+            tokens = rest;
+            if let [on, rest @ ..] = tokens {
+                if on.is_keyword("on") {
+                    let end = rest
+                        .iter()
+                        .position(|t| t.depth == 0 && t.text == ")")
+                        .ok_or_else(|| "unbalanced `DISTINCT ON (...)`".to_string())?;
+                    tokens = &rest[end + 1..];
+                }
+            }
+        }
+    }
+
+    const TERMINATORS: &[&str] = &[
+        "from",
+        "into",
+        "where",
+        "group",
+        "having",
+        "window",
+        "order",
+        "limit",
+        "offset",
+        "fetch",
+        "for",
+        "union",
+        "intersect",
+        "except",
+    ];
+
+    let end = tokens
+        .iter()
+        .position(|t| {
+            t.depth == 0 && (t.text == ";" || TERMINATORS.iter().any(|k| t.is_keyword(k)))
+        })
+        .unwrap_or(tokens.len());
+
+    let mut columns = Vec::new();
+    for item in tokens[..end].split(|t| t.depth == 0 && t.text == ",") {
+        if item.is_empty() {
+            return Err("empty column in the select list of this query".to_string());
+        }
+        if item.last().is_some_and(|t| t.text == "*") {
+            return Ok(None);
+        }
+        columns.push(column_name(item));
+    }
+
+    Ok(Some(columns))
+}
+
+/// Determines the name postgres will give to a single item of a select list.
+fn column_name(item: &[SqlToken]) -> Option<String> {
+    const KEYWORDS: &[&str] = &["end", "null", "true", "false", "and", "or", "not", "is"];
+
+    match item {
+        // `expr AS alias`
+        [.., as_, alias] if as_.depth == 0 && as_.is_keyword("as") => alias.identifier(),
+        // `expr alias`
+        [expr, alias]
+            if expr.identifier().is_some()
+                && alias.identifier().is_some()
+                && !KEYWORDS.iter().any(|k| alias.is_keyword(k)) =>
+        {
+            alias.identifier()
+        }
+        // `column` or `table.column`
+        [column] => column.identifier(),
+        [.., table, dot, column]
+            if dot.text == "."
+                && table.identifier().is_some()
+                && item.len() % 2 == 1
+                && item.iter().enumerate().all(|(i, t)| {
+                    if i % 2 == 0 {
+                        t.identifier().is_some()
+                    } else {
+                        t.text == "."
+                    }
+                }) =>
+        {
+            column.identifier()
+        }
+        _ => None,
+    }
+}
+
+/// A single lexical token of an sql query.
+struct SqlToken {
+    text: String,
+    /// How many parentheses this token is nested in.
+    depth: usize,
+    quoted: bool,
+}
+
+impl SqlToken {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        !self.quoted && self.text.eq_ignore_ascii_case(keyword)
+    }
+
+    /// Returns the identifier this token represents as postgres would resolve it.
+    fn identifier(&self) -> Option<String> {
+        if self.quoted {
+            return Some(self.text.clone());
+        }
+        let mut chars = self.text.chars();
+        let first = chars.next()?;
+        if (first.is_alphabetic() || first == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        {
+            Some(self.text.to_lowercase())
+        } else {
+            None
+        }
+    }
+}
+
+/// Splits an sql query into tokens, keeping track of parentheses and skipping comments.
+fn tokenize(sql: &str) -> std::result::Result<Vec<SqlToken>, String> {
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut chars = sql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '-' if chars.peek() == Some(&'-') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if last == '*' => break,
+                        Some(c) => last = c,
+                        None => return Err("unterminated comment in query".to_string()),
+                    }
+                }
+            }
+            '(' | '[' => {
+                tokens.push(SqlToken {
+                    text: c.to_string(),
+                    depth,
+                    quoted: false,
+                });
+                depth += 1;
+            }
+            ')' | ']' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| "unbalanced parentheses in query".to_string())?;
+                tokens.push(SqlToken {
+                    text: c.to_string(),
+                    depth,
+                    quoted: false,
+                });
+            }
+            '"' | '\'' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some(q) if q == c && chars.peek() == Some(&c) => {
+                            chars.next();
+                            text.push(c);
+                        }
+                        Some(q) if q == c => break,
+                        Some(other) => text.push(other),
+                        None => return Err("unterminated quote in query".to_string()),
+                    }
+                }
+                if c == '"' {
+                    tokens.push(SqlToken {
+                        text,
+                        depth,
+                        quoted: true,
+                    });
+                } else {
+                    // string literals are never identifiers
+                    tokens.push(SqlToken {
+                        text: format!("'{text}'"),
+                        depth,
+                        quoted: false,
+                    });
+                }
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '$' => {
+                let mut text = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' || c == '$' {
+                        text.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(SqlToken {
+                    text,
+                    depth,
+                    quoted: false,
+                });
+            }
+            c => tokens.push(SqlToken {
+                text: c.to_string(),
+                depth,
+                quoted: false,
+            }),
+        }
+    }
+
+    if depth != 0 {
+        return Err("unbalanced parentheses in query".to_string());
+    }
+
+    Ok(tokens)
+}
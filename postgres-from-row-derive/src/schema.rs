@@ -0,0 +1,243 @@
+//! Offline validation of structs against a checked-in schema file.
+//!
+//! When the `POSTGRES_FROM_ROW_SCHEMA` environment variable is set, structs with a
+//! `#[from_row(table = "..")]` attribute are checked against the table declared in that file.
+//!
+//! The file is json, mapping table names to their columns:
+//!
+//! ```json
+//! {
+//!     "users": {
+//!         "user_id": { "type": "int4", "nullable": false },
+//!         "username": { "type": "text", "nullable": true }
+//!     }
+//! }
+//! ```
+
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Result;
+
+use crate::{DeriveFromRow, FromRowField};
+
+/// The environment variable containing the path to the schema file,
+/// relative paths are resolved from the directory of the crate being compiled.
+const SCHEMA_ENV: &str = "POSTGRES_FROM_ROW_SCHEMA";
+
+/// Checks the struct against the schema file, if there is one and the struct has a table.
+///
+/// Returns tokens that make the compiler track the schema file, so that changes to it trigger a rebuild.
+pub(crate) fn check(derive: &DeriveFromRow) -> Result<TokenStream2> {
+    let Some(table) = &derive.table else {
+        return Ok(quote!());
+    };
+    let Some(path) = std::env::var_os(SCHEMA_ENV) else {
+        return Ok(quote!());
+    };
+
+    let mut path = PathBuf::from(path);
+    if path.is_relative() {
+        if let Some(manifest_dir) = std::env::var_os("CARGO_MANIFEST_DIR") {
+            path = PathBuf::from(manifest_dir).join(path);
+        }
+    }
+
+    let error = |message: String| syn::Error::new_spanned(&derive.ident, message);
+
+    let contents = std::fs::read_to_string(&path).map_err(|e| {
+        error(format!(
+            "could not read the schema file `{}`: {e}",
+            path.display()
+        ))
+    })?;
+    let schema: serde_json::Value = serde_json::from_str(&contents).map_err(|e| {
+        error(format!(
+            "could not parse the schema file `{}`: {e}",
+            path.display()
+        ))
+    })?;
+
+    let columns = schema
+        .get(table)
+        .or_else(|| {
+            // allow `public.users` to be declared as `users` and vice versa
+            let unqualified = table.strip_prefix("public.")?;
+            schema.get(unqualified)
+        })
+        .or_else(|| schema.get(format!("public.{table}")))
+        .and_then(|table| table.as_object())
+        .ok_or_else(|| {
+            error(format!(
+                "table `{table}` is not declared in the schema file `{}`",
+                path.display()
+            ))
+        })?;
+
+    let mut errors: Option<syn::Error> = None;
+    for field in derive.fields() {
+        if field.flatten || field.join {
+            continue;
+        }
+        if let Err(e) = check_field(field, table, columns, derive.config.lenient_nullability) {
+            match &mut errors {
+                Some(errors) => errors.combine(e),
+                None => errors = Some(e),
+            }
+        }
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let path = path.to_string_lossy();
+    Ok(quote! {
+        const _: &[u8] = std::include_bytes!(#path);
+    })
+}
+
+/// Checks a single field against the columns of its table.
+fn check_field(
+    field: &FromRowField,
+    table: &str,
+    columns: &serde_json::Map<String, serde_json::Value>,
+    lenient_nullability: bool,
+) -> Result<()> {
+    let ident = field.ident.as_ref().unwrap();
+    let column_name = field.column_name();
+
+    let Some(column) = columns.get(&column_name) else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            format!("column `{column_name}` does not exist in table `{table}`"),
+        ));
+    };
+
+    // fields converted by a function can't be reasoned about
+    if field.from_fn.is_some() || field.try_from_fn.is_some() {
+        return Ok(());
+    }
+
+    // variants of a `TextEnum` are read as text
+    let ty = match (&field.from, &field.try_from) {
+        _ if field.enum_text => syn::parse2(field.target_ty()?)?,
+        (Some(ty), _) | (_, Some(ty)) => syn::parse_str(ty)?,
+        (None, None) => field.ty.clone(),
+    };
+
+    let (inner, optional) = match option_inner(&ty) {
+        Some(inner) => (inner, true),
+        None => (&ty, false),
+    };
+
+    if let Some(column_type) = column.get("type").and_then(|t| t.as_str()) {
+        if let Some(accepted) = accepted_types(inner) {
+            if !accepted.iter().any(|t| t.eq_ignore_ascii_case(column_type)) {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    format!(
+                        "column `{column_name}` of table `{table}` has type `{column_type}`, expected one of: {}",
+                        accepted.join(", ")
+                    ),
+                ));
+            }
+        }
+    }
+
+    let nullable = column
+        .get("nullable")
+        .and_then(|n| n.as_bool())
+        .unwrap_or(false);
+    if nullable && !optional && !field.nullable && !lenient_nullability {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            format!("column `{column_name}` of table `{table}` is nullable, but this field is not an `Option` or `#[from_row(nullable)]`"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns `T` if the type is `Option<T>`.
+pub(crate) fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = last_segment(ty)?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn last_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+    match ty {
+        syn::Type::Path(path) => path.path.segments.last(),
+        syn::Type::Reference(reference) => last_segment(&reference.elem),
+        syn::Type::Group(group) => last_segment(&group.elem),
+        syn::Type::Paren(paren) => last_segment(&paren.elem),
+        _ => None,
+    }
+}
+
+/// The postgres types a well known rust type can be decoded from,
+/// `None` for types that are not known, which are not checked.
+fn accepted_types(ty: &syn::Type) -> Option<&'static [&'static str]> {
+    if let syn::Type::Reference(reference) = ty {
+        if let syn::Type::Slice(_) = &*reference.elem {
+            return Some(&["bytea"]);
+        }
+    }
+    let segment = last_segment(ty)?;
+    Some(match segment.ident.to_string().as_str() {
+        "bool" => &["bool", "boolean"],
+        "i8" => &["char"],
+        "i16" => &["int2", "smallint"],
+        "i32" => &["int4", "integer", "int"],
+        "i64" => &["int8", "bigint"],
+        "u32" => &["oid"],
+        "f32" => &["float4", "real"],
+        "f64" => &["float8", "double precision"],
+        "String" | "str" => &[
+            "text",
+            "varchar",
+            "character varying",
+            "bpchar",
+            "character",
+            "name",
+            "citext",
+            "unknown",
+        ],
+        "Vec" if is_bytes(segment) => &["bytea"],
+        "Uuid" => &["uuid"],
+        "Json" | "Value" => &["json", "jsonb"],
+        "NaiveDateTime" | "PrimitiveDateTime" => &["timestamp", "timestamp without time zone"],
+        "SystemTime" => &[
+            "timestamp",
+            "timestamp without time zone",
+            "timestamptz",
+            "timestamp with time zone",
+        ],
+        "DateTime" | "OffsetDateTime" => &["timestamptz", "timestamp with time zone"],
+        "NaiveDate" | "Date" => &["date"],
+        "NaiveTime" | "Time" => &["time", "time without time zone"],
+        "Decimal" | "BigDecimal" => &["numeric", "decimal"],
+        "IpAddr" => &["inet"],
+        _ => return None,
+    })
+}
+
+/// Checks if `Vec<T>` is `Vec<u8>`.
+fn is_bytes(segment: &syn::PathSegment) -> bool {
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return false;
+    };
+    matches!(
+        args.args.first(),
+        Some(syn::GenericArgument::Type(syn::Type::Path(path))) if path.path.is_ident("u8")
+    )
+}
@@ -0,0 +1,141 @@
+//! Generation of the `Table` implementation of structs with a `#[from_row(table = "..")]` attribute.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Result;
+
+use crate::DeriveFromRow;
+
+/// Generates the `Table` implementation, if the struct has a table.
+pub(crate) fn generate(derive: &DeriveFromRow) -> Result<TokenStream2> {
+    let Some(table) = &derive.table else {
+        return Ok(quote!());
+    };
+
+    let ident = &derive.ident;
+    let krate = derive.krate();
+    let (impl_generics, ty_generics, where_clause) = derive.generics.split_for_impl();
+    let original_predicates = where_clause.map(|w| &w.predicates).into_iter();
+    let predicates = derive.predicates(None)?;
+
+    let columns = table_columns(derive);
+    let primary_key = derive
+        .fields()
+        .iter()
+        .filter(|f| f.primary_key)
+        .map(|f| quote_identifier(&f.column_name()));
+    let list = columns.join(", ");
+    let placeholders = (1..=columns.len())
+        .map(|i| format!("${i}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let insert = if columns.is_empty() {
+        format!("INSERT INTO {table} DEFAULT VALUES")
+    } else {
+        format!("INSERT INTO {table} ({list}) VALUES ({placeholders})")
+    };
+
+    Ok(quote! {
+        impl #impl_generics #krate::Table for #ident #ty_generics where #(#original_predicates),* #(#predicates),* {
+            const TABLE: &'static str = #table;
+            const TABLE_COLUMNS: &'static [&'static str] = &[#(#columns),*];
+            const PRIMARY_KEY: &'static [&'static str] = &[#(#primary_key),*];
+            fn insert_sql() -> &'static str {
+                #insert
+            }
+        }
+    })
+}
+
+/// The columns of the table read by the struct, quoted when needed.
+pub(crate) fn table_columns(derive: &DeriveFromRow) -> Vec<String> {
+    derive
+        .fields()
+        .iter()
+        .filter(|f| !f.flatten && !f.join)
+        .map(|f| quote_identifier(&f.column_name()))
+        .collect()
+}
+
+/// The reserved keywords of postgres that are likely to be used as column names, which must always be quoted.
+const RESERVED: &[&str] = &[
+    "all",
+    "analyse",
+    "analyze",
+    "and",
+    "any",
+    "array",
+    "as",
+    "asc",
+    "both",
+    "case",
+    "cast",
+    "check",
+    "collate",
+    "column",
+    "constraint",
+    "create",
+    "current_date",
+    "current_role",
+    "current_time",
+    "current_timestamp",
+    "current_user",
+    "default",
+    "desc",
+    "distinct",
+    "do",
+    "else",
+    "end",
+    "except",
+    "false",
+    "for",
+    "foreign",
+    "from",
+    "grant",
+    "group",
+    "having",
+    "in",
+    "intersect",
+    "into",
+    "leading",
+    "limit",
+    "null",
+    "offset",
+    "on",
+    "only",
+    "or",
+    "order",
+    "primary",
+    "references",
+    "select",
+    "table",
+    "then",
+    "to",
+    "trailing",
+    "true",
+    "union",
+    "unique",
+    "user",
+    "using",
+    "when",
+    "where",
+    "window",
+    "with",
+];
+
+/// Quotes an identifier, unless it would be read the same without quotes.
+pub(crate) fn quote_identifier(name: &str) -> String {
+    let plain = !RESERVED.contains(&name)
+        && name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if plain {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
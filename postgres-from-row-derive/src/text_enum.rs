@@ -0,0 +1,95 @@
+use darling::{ast::Data, util::Ignored, FromDeriveInput, FromVariant};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{ext::IdentExt, Result};
+
+use crate::config::Config;
+
+/// Main struct for deriving `TextEnum` for a fieldless enum.
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(from_row), supports(enum_unit))]
+pub(crate) struct DeriveTextEnum {
+    ident: syn::Ident,
+    generics: syn::Generics,
+    data: Data<TextEnumVariant, Ignored>,
+    /// The path of the `postgres_from_row` crate, for when it is renamed or re-exported from another crate.
+    #[darling(rename = "crate")]
+    krate: Option<syn::Path>,
+    /// The defaults read from `from_row.toml`, see the `config` module.
+    #[darling(skip)]
+    pub(crate) config: Config,
+}
+
+/// A single variant of an enum that derives `TextEnum`.
+#[derive(Debug, FromVariant)]
+#[darling(attributes(from_row))]
+struct TextEnumVariant {
+    ident: syn::Ident,
+    /// Override the text this variant is stored as, instead of using its name.
+    rename: Option<String>,
+}
+
+impl TextEnumVariant {
+    /// Returns the text this variant is stored as.
+    fn text(&self) -> String {
+        self.rename
+            .clone()
+            .unwrap_or_else(|| self.ident.unraw().to_string())
+    }
+}
+
+impl DeriveTextEnum {
+    /// Generate the `TextEnum` implementation.
+    pub(crate) fn generate(self) -> Result<TokenStream2> {
+        let ident = &self.ident;
+        let krate = self
+            .krate
+            .clone()
+            .or_else(|| self.config.krate())
+            .unwrap_or_else(|| syn::parse_quote!(postgres_from_row));
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let Data::Enum(variants) = &self.data else {
+            panic!("invalid shape");
+        };
+
+        for (i, variant) in variants.iter().enumerate() {
+            let text = variant.text();
+            if let Some(other) = variants[..i].iter().find(|other| other.text() == text) {
+                return Err(syn::Error::new(
+                    variant.ident.span(),
+                    format!(
+                        "the variants `{}` and `{}` are both stored as `{text}`",
+                        other.ident, variant.ident
+                    ),
+                ));
+            }
+        }
+
+        let idents = variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+        let texts = variants
+            .iter()
+            .map(TextEnumVariant::text)
+            .collect::<Vec<_>>();
+        let track_config = self.config.track();
+
+        Ok(quote! {
+            #track_config
+
+            impl #impl_generics #krate::TextEnum for #ident #ty_generics #where_clause {
+                const VARIANTS: &'static [&'static str] = &[#(#texts),*];
+                fn from_text(__text: &str) -> std::option::Option<Self> {
+                    match __text {
+                        #(#texts => std::option::Option::Some(Self::#idents),)*
+                        _ => std::option::Option::None,
+                    }
+                }
+                fn as_text(&self) -> &'static str {
+                    match *self {
+                        #(Self::#idents => #texts,)*
+                    }
+                }
+            }
+        })
+    }
+}
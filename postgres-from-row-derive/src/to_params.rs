@@ -0,0 +1,94 @@
+use darling::{ast::Data, util::Ignored, FromDeriveInput, FromField};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{ext::IdentExt, Result};
+
+use crate::config::Config;
+
+/// Main struct for deriving `ToParams` for a struct.
+#[derive(Debug, FromDeriveInput)]
+#[darling(attributes(to_params), supports(struct_named))]
+pub(crate) struct DeriveToParams {
+    ident: syn::Ident,
+    generics: syn::Generics,
+    data: Data<Ignored, ToParamsField>,
+    /// The path of the `postgres_from_row` crate, for when it is renamed or re-exported from another crate.
+    #[darling(rename = "crate")]
+    krate: Option<syn::Path>,
+    /// The defaults read from `from_row.toml`, see the `config` module.
+    #[darling(skip)]
+    pub(crate) config: Config,
+}
+
+/// A single field of a struct that derives `ToParams`.
+#[derive(Debug, FromField)]
+#[darling(attributes(to_params))]
+struct ToParamsField {
+    ident: Option<syn::Ident>,
+    ty: syn::Type,
+    /// Leave this field out of the parameters.
+    #[darling(default)]
+    skip: bool,
+    /// Override the name of the parameter instead of using the name of the field.
+    rename: Option<String>,
+}
+
+impl ToParamsField {
+    /// Returns the name of the parameter.
+    fn param_name(&self) -> String {
+        self.rename
+            .clone()
+            .unwrap_or_else(|| self.ident.as_ref().unwrap().unraw().to_string())
+    }
+}
+
+impl DeriveToParams {
+    /// Generate the `ToParams` implementation and the inherent `params` method.
+    pub(crate) fn generate(self) -> Result<TokenStream2> {
+        let ident = &self.ident;
+        let krate = self
+            .krate
+            .clone()
+            .or_else(|| self.config.krate())
+            .unwrap_or_else(|| syn::parse_quote!(postgres_from_row));
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let original_predicates = where_clause
+            .map(|w| w.predicates.iter().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        let Data::Struct(fields) = &self.data else {
+            panic!("invalid shape");
+        };
+        let fields = fields.iter().filter(|f| !f.skip).collect::<Vec<_>>();
+
+        let idents = fields.iter().map(|f| f.ident.as_ref().unwrap());
+        let names = fields.iter().map(|f| f.param_name());
+        let predicates = fields
+            .iter()
+            .map(|f| {
+                let ty = &f.ty;
+                quote!(#ty: #krate::tokio_postgres::types::ToSql + std::marker::Sync)
+            })
+            .collect::<Vec<_>>();
+        let count = fields.len();
+        let track_config = self.config.track();
+
+        Ok(quote! {
+            #track_config
+
+            impl #impl_generics #ident #ty_generics where #(#original_predicates,)* #(#predicates),* {
+                /// The fields of this struct as the parameters of a statement, in field order.
+                pub fn params(&self) -> [&(dyn #krate::tokio_postgres::types::ToSql + std::marker::Sync); #count] {
+                    [#(&self.#idents),*]
+                }
+            }
+
+            impl #impl_generics #krate::ToParams for #ident #ty_generics where #(#original_predicates,)* #(#predicates),* {
+                const PARAM_NAMES: &'static [&'static str] = &[#(#names),*];
+                fn to_params(&self) -> std::vec::Vec<&(dyn #krate::tokio_postgres::types::ToSql + std::marker::Sync)> {
+                    self.params().to_vec()
+                }
+            }
+        })
+    }
+}
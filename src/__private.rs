@@ -0,0 +1,540 @@
+//! Items used by the code generated by the derive and the macros of this crate, not public api.
+
+/// Records that `error` happened reading `field` of the struct named `type_name`, from `column`.
+pub fn field_error(
+    error: crate::Error,
+    type_name: &'static str,
+    field: &'static str,
+    column: Option<&str>,
+) -> crate::Error {
+    crate::error::FieldError::wrap(error, type_name, field, column)
+}
+
+/// Concatenates the `COLUMN_NAMES` of the fields of a struct.
+///
+/// Returns `None` if the names of any of the parts are not known.
+pub const fn concat_column_names<const N: usize>(
+    parts: &[Option<&'static [Option<&'static str>]>],
+) -> Option<[Option<&'static str>; N]> {
+    let mut names = [None; N];
+    let mut i = 0;
+    let mut p = 0;
+    while p < parts.len() {
+        let Some(part) = parts[p] else {
+            return None;
+        };
+        let mut j = 0;
+        while j < part.len() {
+            names[i] = part[j];
+            i += 1;
+            j += 1;
+        }
+        p += 1;
+    }
+    assert!(i == N, "COLUMN_NAMES does not add up to COLUMN_COUNT");
+    Some(names)
+}
+
+/// Concatenates the `EXPECTED_COLUMNS` of the fields of a struct named by `owner`.
+///
+/// The columns of the parts with a field are nested in that field, see [`ExpectedColumn::in_field`](crate::ExpectedColumn::in_field).
+///
+/// Returns `None` if the columns of any of the parts are not known.
+pub const fn concat_expected_columns<const N: usize>(
+    owner: fn() -> &'static str,
+    parts: &[(
+        Option<&'static [crate::ExpectedColumn]>,
+        Option<&'static str>,
+    )],
+) -> Option<[crate::ExpectedColumn; N]> {
+    const PLACEHOLDER: crate::ExpectedColumn = crate::ExpectedColumn {
+        column_name: None,
+        type_name: || "",
+        accepts: |_| false,
+        nullable: |_| false,
+        json: false,
+        path: None,
+    };
+    let mut columns = [PLACEHOLDER; N];
+    let mut i = 0;
+    let mut p = 0;
+    while p < parts.len() {
+        let (Some(part), field) = parts[p] else {
+            return None;
+        };
+        let mut j = 0;
+        while j < part.len() {
+            columns[i] = match field {
+                Some(field) => part[j].in_field(owner, field),
+                None => part[j],
+            };
+            i += 1;
+            j += 1;
+        }
+        p += 1;
+    }
+    assert!(i == N, "EXPECTED_COLUMNS does not add up to COLUMN_COUNT");
+    Some(columns)
+}
+
+/// Compares the expected column names of a type with the names found in a query.
+///
+/// Returns the index of the first column that does not match, columns that are `None` on either side
+/// are not compared, if the lengths differ the length of the shortest is returned.
+///
+/// If the expected names are not known, this always returns `None`.
+pub const fn column_names_mismatch(
+    expected: Option<&[Option<&str>]>,
+    found: &[Option<&str>],
+) -> Option<usize> {
+    let Some(expected) = expected else {
+        return None;
+    };
+    let mut i = 0;
+    while i < expected.len() && i < found.len() {
+        if let (Some(expected), Some(found)) = (expected[i], found[i]) {
+            if !str_eq(expected, found) {
+                return Some(i);
+            }
+        }
+        i += 1;
+    }
+    if expected.len() != found.len() {
+        Some(i)
+    } else {
+        None
+    }
+}
+
+/// Whether `names` contains `name`, used by the derive to detect columns read by several fields.
+pub const fn contains_column_name(names: Option<&[Option<&str>]>, name: &str) -> bool {
+    let Some(names) = names else {
+        return false;
+    };
+    let mut i = 0;
+    while i < names.len() {
+        if let Some(found) = names[i] {
+            if str_eq(found, name) {
+                return true;
+            }
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Whether two lists of column names have a name in common, used by the derive to detect columns read by several fields.
+pub const fn column_names_overlap(a: Option<&[Option<&str>]>, b: Option<&[Option<&str>]>) -> bool {
+    let Some(a) = a else {
+        return false;
+    };
+    let mut i = 0;
+    while i < a.len() {
+        if let Some(name) = a[i] {
+            if contains_column_name(b, name) {
+                return true;
+            }
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Measures the time it takes to decode a field, when the `stats` feature is enabled and an observer is installed.
+#[cfg(feature = "stats")]
+pub struct FieldTimer {
+    start: Option<std::time::Instant>,
+    column: usize,
+}
+
+#[cfg(feature = "stats")]
+impl FieldTimer {
+    #[inline]
+    pub fn start(column: usize) -> Self {
+        Self {
+            start: crate::observer::observer().map(|_| std::time::Instant::now()),
+            column,
+        }
+    }
+
+    #[inline]
+    pub fn finish(self, type_name: &'static str, field: &'static str) {
+        if let (Some(start), Some(observer)) = (self.start, crate::observer::observer()) {
+            observer.field_decoded(&crate::observer::FieldDecode {
+                type_name,
+                field,
+                column: self.column,
+                elapsed: start.elapsed(),
+            });
+        }
+    }
+}
+
+/// Measures the time it takes to decode a field, when the `stats` feature is enabled and an observer is installed.
+#[cfg(not(feature = "stats"))]
+pub struct FieldTimer;
+
+#[cfg(not(feature = "stats"))]
+impl FieldTimer {
+    #[inline(always)]
+    pub fn start(_column: usize) -> Self {
+        Self
+    }
+
+    #[inline(always)]
+    pub fn finish(self, _type_name: &'static str, _field: &'static str) {}
+}
+
+/// Records which columns of the row are used by a conversion by name, when the `usage` feature is enabled.
+#[cfg(feature = "usage")]
+pub struct UsageGuard(crate::usage::UsageGuard);
+
+#[cfg(feature = "usage")]
+impl UsageGuard {
+    #[inline]
+    pub fn enter<T: crate::FromRow>(row: &tokio_postgres::Row) -> Self {
+        Self(crate::usage::UsageGuard::enter::<T>(row))
+    }
+}
+
+/// Records which columns of the row are used by a conversion by name, when the `usage` feature is enabled.
+#[cfg(not(feature = "usage"))]
+pub struct UsageGuard;
+
+#[cfg(not(feature = "usage"))]
+impl UsageGuard {
+    #[inline(always)]
+    pub fn enter<T: crate::FromRow>(_row: &tokio_postgres::Row) -> Self {
+        Self
+    }
+}
+
+/// Accepts a column of any type without decoding it, to check whether it is null.
+pub(crate) struct AnyColumn;
+
+impl<'a> tokio_postgres::types::FromSql<'a> for AnyColumn {
+    fn from_sql(
+        _: &tokio_postgres::types::Type,
+        _: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(AnyColumn)
+    }
+
+    fn accepts(_: &tokio_postgres::types::Type) -> bool {
+        true
+    }
+}
+
+/// Checks whether the `count` columns starting at `index` are all null, used by `#[from_row(join, left)]`.
+pub fn columns_are_null(
+    row: &tokio_postgres::Row,
+    index: usize,
+    count: usize,
+) -> Result<bool, crate::Error> {
+    for i in index..index + count {
+        if row.try_get::<_, Option<AnyColumn>>(i)?.is_some() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Checks whether the named columns of `expected` are all null, used by `#[from_row(join, left)]` when converting by name.
+pub fn named_columns_are_null(
+    row: &tokio_postgres::Row,
+    expected: &[crate::ExpectedColumn],
+) -> Result<bool, crate::Error> {
+    for name in expected.iter().filter_map(|column| column.column_name()) {
+        if row.try_get::<_, Option<AnyColumn>>(name)?.is_some() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// When a flattened `Option<T>` is `None`, see `#[from_row(flatten, none_when = "..")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NullPolicy {
+    /// `None` when all the columns of `T` are null.
+    AllNull,
+    /// `None` when any of the columns of `T` is null.
+    AnyNull,
+    /// `None` when the first column of `T` is null, which must then be the case for all of them.
+    KeyNull,
+}
+
+impl NullPolicy {
+    /// Whether the group of columns with these nulls is `None`, failing when only some of them are null
+    /// with `KeyNull` and its key is null.
+    pub fn is_none(self, nulls: &[bool], type_name: &str) -> Result<bool, crate::Error> {
+        match self {
+            NullPolicy::AllNull => Ok(nulls.iter().all(|null| *null)),
+            NullPolicy::AnyNull => Ok(nulls.iter().any(|null| *null)),
+            NullPolicy::KeyNull => match nulls.split_first() {
+                Some((true, rest)) if !rest.iter().all(|null| *null) => {
+                    Err(crate::Error::conversion(format!(
+                        "the key column of `{}` is null, but some of its other columns are not",
+                        crate::type_names().apply(type_name)
+                    )))
+                }
+                Some((key, _)) => Ok(*key),
+                None => Ok(false),
+            },
+        }
+    }
+}
+
+/// Whether the `count` columns of `T` starting at `index` are `None` by `policy`, used by `#[from_row(flatten, none_when = "..")]`.
+pub fn group_is_none<T>(
+    row: &tokio_postgres::Row,
+    index: usize,
+    count: usize,
+    policy: NullPolicy,
+) -> Result<bool, crate::Error> {
+    let nulls = (index..index + count)
+        .map(|i| Ok(row.try_get::<_, Option<AnyColumn>>(i)?.is_none()))
+        .collect::<Result<Vec<_>, crate::Error>>()?;
+    policy.is_none(&nulls, std::any::type_name::<T>())
+}
+
+/// Whether the named columns of `expected` are `None` by `policy`, used by `#[from_row(flatten, none_when = "..")]`
+/// when converting by name.
+pub fn named_group_is_none<T>(
+    row: &tokio_postgres::Row,
+    expected: &[crate::ExpectedColumn],
+    policy: NullPolicy,
+) -> Result<bool, crate::Error> {
+    let nulls = expected
+        .iter()
+        .filter_map(|column| column.column_name())
+        .map(|name| Ok(row.try_get::<_, Option<AnyColumn>>(name)?.is_none()))
+        .collect::<Result<Vec<_>, crate::Error>>()?;
+    policy.is_none(&nulls, std::any::type_name::<T>())
+}
+
+/// Whether the row has a column with this name, used by `#[from_row(partial)]`.
+pub fn has_column(row: &tokio_postgres::Row, name: &str) -> bool {
+    row.columns().iter().any(|column| column.name() == name)
+}
+
+/// Whether the row has every named column of `expected`, used by `#[from_row(partial)]` for flattened fields.
+pub fn has_named_columns(row: &tokio_postgres::Row, expected: &[crate::ExpectedColumn]) -> bool {
+    expected
+        .iter()
+        .filter_map(|column| column.column_name())
+        .all(|name| has_column(row, name))
+}
+
+/// Checks that the columns are the expected ones, in order, with accepted types, used by `impl_from_row!`.
+pub fn columns_match(
+    expected: &[crate::ExpectedColumn],
+    columns: &[tokio_postgres::Column],
+) -> bool {
+    expected.len() == columns.len()
+        && expected.iter().zip(columns).all(|(expected, column)| {
+            expected.column_name() == Some(column.name()) && expected.accepts(column.type_())
+        })
+}
+
+/// Checks that every column is one of the named columns of `expected` with an accepted type,
+/// used by `#[from_row(partial)]`, which reads any subset of its columns.
+pub fn partial_matches(
+    expected: &[crate::ExpectedColumn],
+    columns: &[tokio_postgres::Column],
+) -> bool {
+    columns.iter().all(|column| {
+        expected.iter().any(|expected| {
+            expected.column_name() == Some(column.name()) && expected.accepts(column.type_())
+        })
+    })
+}
+
+/// Records that a row was null and did not become a value, for the `verify` feature.
+#[inline(always)]
+pub fn null_row() {
+    #[cfg(feature = "verify")]
+    crate::verify::null_row();
+}
+
+/// Stops counting null rows for the `verify` feature until dropped, used for the joined fields after the first one
+/// when a struct has several of them, since every row is already accounted for by the first one.
+#[cfg(feature = "verify")]
+pub struct UncountedRows(usize);
+
+#[cfg(feature = "verify")]
+impl UncountedRows {
+    #[inline]
+    pub fn start() -> Self {
+        Self(crate::verify::null_rows())
+    }
+}
+
+#[cfg(feature = "verify")]
+impl Drop for UncountedRows {
+    fn drop(&mut self) {
+        crate::verify::set_null_rows(self.0);
+    }
+}
+
+/// Stops counting null rows for the `verify` feature until dropped, used for the joined fields after the first one
+/// when a struct has several of them, since every row is already accounted for by the first one.
+#[cfg(not(feature = "verify"))]
+pub struct UncountedRows;
+
+#[cfg(not(feature = "verify"))]
+impl UncountedRows {
+    #[inline(always)]
+    pub fn start() -> Self {
+        Self
+    }
+}
+
+/// What to do with a child that duplicates one already joined into a `Vec`, see `#[from_row(join, duplicates = "..")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Every row is a new child, even if it could be merged into the last one.
+    Keep,
+    /// Children that are the same as one already joined are dropped.
+    Dedup,
+    /// Children that are the same as one already joined are an error.
+    Error,
+}
+
+/// Joins a row into the `Vec` of a joined field, handling duplicate children with `duplicates`.
+///
+/// The child is read by `join`, given the last child it may be merged into, usually with `try_from_row_joined`.
+///
+/// Children are the same when `same` returns true, by default when they are equal.
+///
+/// A new child is compared with every child already joined, not just the last one, since the cross product of
+/// sibling joins repeats children that are not consecutive, so joining `n` children makes `O(n²)` comparisons.
+pub fn join_vec<T: crate::FromRow>(
+    vec: &mut Vec<T>,
+    duplicates: Duplicates,
+    same: impl Fn(&T, &T) -> bool,
+    join: impl FnOnce(Option<&mut T>) -> Result<Option<T>, crate::Error>,
+) -> Result<(), crate::Error> {
+    let last = match duplicates {
+        Duplicates::Keep => None,
+        Duplicates::Dedup | Duplicates::Error => vec.last_mut(),
+    };
+    let item = match join(last) {
+        Ok(Some(item)) => item,
+        Ok(None) => return Ok(()),
+        Err(e) if e.is_null() => {
+            null_row();
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+    if duplicates != Duplicates::Keep && vec.iter().any(|child| same(child, &item)) {
+        if duplicates == Duplicates::Error {
+            return Err(crate::Error::conversion(format!(
+                "a duplicate `{}` was joined",
+                std::any::type_name::<T>()
+            )));
+        }
+        for _ in 0..item.row_span() {
+            null_row();
+        }
+        return Ok(());
+    }
+    vec.push(item);
+    Ok(())
+}
+
+/// Matches the text read by `#[from_row(enum_text)]` to a variant of `T`.
+pub fn enum_text<T: crate::TextEnum>(text: String) -> Result<T, crate::Error> {
+    T::from_text(&text).ok_or_else(|| {
+        crate::Error::conversion(format!(
+            "`{text}` is not a variant of `{}`, expected one of {:?}",
+            std::any::type_name::<T>(),
+            T::VARIANTS
+        ))
+    })
+}
+
+/// Returns the index of the column with this name, used to build a `ConversionContext` when converting by name.
+///
+/// The column is looked up like `Row::try_get` does, ignoring the ascii case when no name matches exactly.
+/// When there is no such column, reading it fails before the context is created, so the index is never used.
+pub fn column_index(row: &tokio_postgres::Row, name: &str) -> usize {
+    let columns = row.columns();
+    columns
+        .iter()
+        .position(|column| column.name() == name)
+        .or_else(|| {
+            columns
+                .iter()
+                .position(|column| column.name().eq_ignore_ascii_case(name))
+        })
+        .unwrap_or(usize::MAX)
+}
+
+/// The normalizations applied by `#[from_row(trim)]` and `#[from_row(lowercase)]`.
+pub trait Normalize {
+    fn trim(self) -> Self;
+    fn lowercase(self) -> Self;
+}
+
+impl Normalize for String {
+    fn trim(mut self) -> Self {
+        self.truncate(self.trim_end().len());
+        let start = self.len() - self.trim_start().len();
+        self.drain(..start);
+        self
+    }
+
+    fn lowercase(self) -> Self {
+        if self.chars().any(char::is_uppercase) {
+            self.to_lowercase()
+        } else {
+            self
+        }
+    }
+}
+
+impl<T: Normalize> Normalize for Option<T> {
+    fn trim(self) -> Self {
+        self.map(T::trim)
+    }
+
+    fn lowercase(self) -> Self {
+        self.map(T::lowercase)
+    }
+}
+
+impl<T: Normalize> Normalize for Vec<T> {
+    fn trim(self) -> Self {
+        self.into_iter().map(T::trim).collect()
+    }
+
+    fn lowercase(self) -> Self {
+        self.into_iter().map(T::lowercase).collect()
+    }
+}
+
+/// Calls [`Normalize::trim`], as a function so that the type can be inferred.
+pub fn trim<T: Normalize>(value: T) -> T {
+    value.trim()
+}
+
+/// Calls [`Normalize::lowercase`], as a function so that the type can be inferred.
+pub fn lowercase<T: Normalize>(value: T) -> T {
+    value.lowercase()
+}
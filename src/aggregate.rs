@@ -0,0 +1,51 @@
+use crate::{Error, RowError};
+
+/// Merges the rows of a parent/child join into the parents, each with the children of its rows,
+/// like the fields marked with `#[from_row(join)]` are merged by the derive.
+///
+/// Consecutive rows with the same key, as returned by `key_fn`, belong to the same parent, so the query
+/// should be ordered by the key. The parent is read with `parent_fn` from the first of its rows,
+/// and a child is read with `child_fn` from each of them, skipping the rows where it returns `None`,
+/// like the rows of a `LEFT JOIN` without a match.
+///
+/// The rows are usually [`tokio_postgres::Row`]s, but can be anything the functions read from,
+/// which makes this usable with hand-written mappers and queries built at runtime.
+///
+/// ```ignore
+/// let rows = client
+///     .query("SELECT u.user_id, u.username, t.todo_id, t.text FROM users u LEFT JOIN todos t USING (user_id) ORDER BY u.user_id", &[])
+///     .await?;
+/// let users: Vec<(User, Vec<Todo>)> = aggregate_joined(
+///     &rows,
+///     |row| Ok(User { user_id: row.try_get(0)?, username: row.try_get(1)? }),
+///     |row| Ok(row.try_get::<_, Option<i32>>(2)?.map(|todo_id| Todo { todo_id, text: row.get(3) })),
+///     |row| Ok(row.try_get::<_, i32>(0)?),
+/// )?;
+/// ```
+///
+/// An error returned by one of the functions is wrapped in an [`Error::Row`] with the index of the row.
+pub fn aggregate_joined<R, P, C, K>(
+    rows: impl IntoIterator<Item = R>,
+    mut parent_fn: impl FnMut(&R) -> Result<P, Error>,
+    mut child_fn: impl FnMut(&R) -> Result<Option<C>, Error>,
+    mut key_fn: impl FnMut(&R) -> Result<K, Error>,
+) -> Result<Vec<(P, Vec<C>)>, Error>
+where
+    K: PartialEq,
+{
+    let mut parents: Vec<(P, Vec<C>)> = Vec::new();
+    let mut last_key = None;
+    for (i, row) in rows.into_iter().enumerate() {
+        let wrap = |error| RowError::wrap(error, i, None);
+        let key = key_fn(&row).map_err(wrap)?;
+        if last_key.as_ref() != Some(&key) {
+            parents.push((parent_fn(&row).map_err(wrap)?, Vec::new()));
+            last_key = Some(key);
+        }
+        if let Some(child) = child_fn(&row).map_err(wrap)? {
+            // a parent was pushed above unless the key matched the last one
+            parents.last_mut().unwrap().1.push(child);
+        }
+    }
+    Ok(parents)
+}
@@ -0,0 +1,68 @@
+use tokio_postgres::Row;
+
+use crate::{context, Error, FromRow, RowError};
+
+/// Converts rows that are read in several batches, like the fetches of a portal or a cursor.
+///
+/// Values that join several rows are kept until the first row of the next value is seen,
+/// so they can be completed by the rows of the next batch.
+pub(crate) struct Batcher<T> {
+    current: Option<T>,
+    rows: usize,
+    done: bool,
+}
+
+impl<T: FromRow> Batcher<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            current: None,
+            rows: 0,
+            done: false,
+        }
+    }
+
+    /// Whether the last row was converted, or a conversion failed.
+    pub(crate) fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Stops converting rows, after reading a batch failed.
+    pub(crate) fn stop(&mut self) {
+        self.done = true;
+        self.current = None;
+    }
+
+    /// Converts a batch of rows, `last` is whether no more rows follow them.
+    pub(crate) fn push(&mut self, rows: &[Row], last: bool) -> Result<Vec<T>, Error> {
+        let mut values = Vec::with_capacity(rows.len());
+        for row in rows {
+            self.push_row(row, &mut values)?;
+        }
+        if last {
+            self.finish(&mut values);
+        }
+        Ok(values)
+    }
+
+    /// Converts a single row, pushing the value it completes to `values`, if any.
+    pub(crate) fn push_row(&mut self, row: &Row, values: &mut Vec<T>) -> Result<(), Error> {
+        let _row_index = context::RowIndexGuard::new(self.rows);
+        self.rows += 1;
+        match T::try_from_row_joined(self.current.as_mut(), row, 0) {
+            Ok(Some(next)) if !T::JOINED => values.push(next),
+            Ok(Some(next)) => values.extend(self.current.replace(next)),
+            Ok(None) => {}
+            Err(error) => {
+                self.stop();
+                return Err(RowError::wrap(error, self.rows - 1, Some(row)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Pushes the value that was being joined to `values`, after the last row was converted.
+    pub(crate) fn finish(&mut self, values: &mut Vec<T>) {
+        self.done = true;
+        values.extend(self.current.take());
+    }
+}
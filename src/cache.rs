@@ -0,0 +1,96 @@
+use std::{any::TypeId, collections::HashMap, sync::Mutex};
+
+use tokio_postgres::{Column, Row};
+
+use crate::{Error, FromRow};
+
+/// The names and type oids of validated columns, compared to tell apart statements at the same address.
+type Fingerprint = Vec<(String, u32)>;
+
+/// Remembers which types have already been validated against which columns,
+/// so that executing the same prepared statement many times only validates its columns once.
+///
+/// Columns are identified by their address, which is shared by all the rows of a prepared statement,
+/// the names and types of the columns are compared as well, so a statement that is dropped and
+/// replaced by another one at the same address is validated again.
+#[derive(Debug, Default)]
+pub struct ValidationCache {
+    validated: Mutex<HashMap<(TypeId, usize), Fingerprint>>,
+}
+
+impl ValidationCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Like [`FromRow::assert_matches`], but skips the validation if these columns were already validated for `T`.
+    pub fn assert_matches<T: FromRow + 'static>(&self, columns: &[Column]) {
+        let key = (TypeId::of::<T>(), columns.as_ptr() as usize);
+        let mut validated = self.validated.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(fingerprint) = validated.get(&key) {
+            if fingerprint.len() == columns.len()
+                && fingerprint
+                    .iter()
+                    .zip(columns)
+                    .all(|((name, oid), column)| {
+                        name == column.name() && *oid == column.type_().oid()
+                    })
+            {
+                return;
+            }
+        }
+        T::assert_matches(columns);
+        validated.insert(
+            key,
+            columns
+                .iter()
+                .map(|column| (column.name().to_string(), column.type_().oid()))
+                .collect(),
+        );
+    }
+
+    /// Forgets all the columns validated so far.
+    pub fn clear(&self) {
+        self.validated
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Like [`FromRow::from_row`], but only validates the columns the first time they are seen.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not contain the expected column names.
+    pub fn from_row<T: FromRow + 'static>(&self, row: &Row) -> T {
+        self.try_from_row(row).expect("could not convert column")
+    }
+
+    /// Like [`FromRow::try_from_row`], but only validates the columns the first time they are seen.
+    ///
+    /// Will return an error if the row does not contain the expected column names.
+    pub fn try_from_row<T: FromRow + 'static>(&self, row: &Row) -> Result<T, Error> {
+        self.assert_matches::<T>(row.columns());
+        T::try_from_row_unchecked(row)
+    }
+
+    /// Like [`FromRow::from_slice`], but only validates the columns the first time they are seen.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not contain the expected column names.
+    pub fn from_slice<T: FromRow + 'static>(&self, rows: &[Row]) -> Vec<T> {
+        self.try_from_slice(rows).expect("could not convert column")
+    }
+
+    /// Like [`FromRow::try_from_slice`], but only validates the columns the first time they are seen.
+    ///
+    /// Will return an error if the row does not contain the expected column names.
+    pub fn try_from_slice<T: FromRow + 'static>(&self, rows: &[Row]) -> Result<Vec<T>, Error> {
+        let [first, ..] = rows else {
+            return Ok(Vec::new());
+        };
+        self.assert_matches::<T>(first.columns());
+        T::try_from_slice_unchecked(rows)
+    }
+}
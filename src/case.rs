@@ -0,0 +1,93 @@
+use std::borrow::Cow;
+
+/// The case convention of the columns of a query, see [`MatchPolicy::case`](crate::MatchPolicy::case).
+///
+/// Uses the same conventions as `#[from_row(rename_all = "..")]`, but applies them at runtime.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Case {
+    /// The names are compared as they are written.
+    #[default]
+    AsIs,
+    /// `lowercase`
+    Lower,
+    /// `UPPERCASE`
+    Upper,
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebab,
+}
+
+impl Case {
+    /// Converts a name written in any of these conventions to this one.
+    ///
+    /// Words are split at `_`, `-` and at the start of each uppercase word, so `userId`, `UserId`
+    /// and `USER_ID` all become `user_id` with [`Case::Snake`].
+    pub fn apply<'a>(self, name: &'a str) -> Cow<'a, str> {
+        let words = || words(name).map(|word| word.to_ascii_lowercase());
+        match self {
+            Case::AsIs => name.into(),
+            Case::Lower => words().collect::<String>().into(),
+            Case::Upper => words().collect::<String>().to_ascii_uppercase().into(),
+            Case::Pascal => words().map(capitalize).collect::<String>().into(),
+            Case::Camel => words()
+                .enumerate()
+                .map(|(i, word)| if i == 0 { word } else { capitalize(word) })
+                .collect::<String>()
+                .into(),
+            Case::Snake => words().collect::<Vec<_>>().join("_").into(),
+            Case::ScreamingSnake => words()
+                .collect::<Vec<_>>()
+                .join("_")
+                .to_ascii_uppercase()
+                .into(),
+            Case::Kebab => words().collect::<Vec<_>>().join("-").into(),
+            Case::ScreamingKebab => words()
+                .collect::<Vec<_>>()
+                .join("-")
+                .to_ascii_uppercase()
+                .into(),
+        }
+    }
+}
+
+/// Splits a name into its words, at separators and where a new uppercase word starts, keeping acronyms together.
+fn words(name: &str) -> impl Iterator<Item = &str> {
+    name.split(['_', '-', ' ']).flat_map(|part| {
+        let bytes = part.as_bytes();
+        let mut starts = vec![0];
+        for i in 1..bytes.len() {
+            let (prev, cur) = (bytes[i - 1], bytes[i]);
+            let next_lower = bytes.get(i + 1).is_some_and(u8::is_ascii_lowercase);
+            if cur.is_ascii_uppercase()
+                && (prev.is_ascii_lowercase()
+                    || prev.is_ascii_digit()
+                    || prev.is_ascii_uppercase() && next_lower)
+            {
+                starts.push(i);
+            }
+        }
+        starts.push(bytes.len());
+        starts
+            .windows(2)
+            .map(|w| &part[w[0]..w[1]])
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+    })
+}
+
+fn capitalize(word: String) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => word,
+    }
+}
@@ -0,0 +1,122 @@
+use std::future::Future;
+
+use tokio_postgres::{
+    types::{ToSql, Type},
+    GenericClient, Row, ToStatement,
+};
+
+use crate::{Error, FromRow};
+
+/// Extends every [`GenericClient`] with methods that convert the returned rows to a [`FromRow`] type.
+///
+/// The columns are validated against `T` once, using the first row returned.
+pub trait ClientExt: GenericClient + Sync {
+    /// Like [`GenericClient::query`], converting all the rows to `T`.
+    fn query_as<T, S>(
+        &self,
+        statement: &S,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Vec<T>, Error>> + Send
+    where
+        T: FromRow,
+        S: ?Sized + ToStatement + Sync + Send,
+    {
+        async move { T::try_from_rows(self.query(statement, params).await?) }
+    }
+
+    /// Like [`GenericClient::query_one`], converting the row to `T`.
+    fn query_one_as<T, S>(
+        &self,
+        statement: &S,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<T, Error>> + Send
+    where
+        T: FromRow,
+        S: ?Sized + ToStatement + Sync + Send,
+    {
+        async move { T::try_from_row(&self.query_one(statement, params).await?) }
+    }
+
+    /// Like [`GenericClient::query_opt`], converting the row to `T`, if any.
+    fn query_opt_as<T, S>(
+        &self,
+        statement: &S,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Option<T>, Error>> + Send
+    where
+        T: FromRow,
+        S: ?Sized + ToStatement + Sync + Send,
+    {
+        async move {
+            let row = self.query_opt(statement, params).await?;
+            row.map(T::try_from_row).transpose()
+        }
+    }
+
+    /// Like [`GenericClient::query_typed`], converting all the rows to `T`.
+    ///
+    /// Since `query_typed` skips preparing the statement, the columns can't be known ahead of time,
+    /// and are instead validated against the columns of the first row.
+    fn query_typed_as<T>(
+        &self,
+        statement: &str,
+        params: &[(&(dyn ToSql + Sync), Type)],
+    ) -> impl Future<Output = Result<Vec<T>, Error>> + Send
+    where
+        T: FromRow,
+    {
+        async move { T::try_from_rows(self.query_typed(statement, params).await?) }
+    }
+
+    /// Like [`GenericClient::query`], mapping each row with `f`, for quick projections that don't need a struct.
+    ///
+    /// ```ignore
+    /// let names: Vec<String> = client
+    ///     .query_map("SELECT first_name, last_name FROM users", &[], |row| {
+    ///         Ok(format!("{} {}", row.try_get::<_, &str>(0)?, row.try_get::<_, &str>(1)?))
+    ///     })
+    ///     .await?;
+    /// ```
+    fn query_map<U, S, F>(
+        &self,
+        statement: &S,
+        params: &[&(dyn ToSql + Sync)],
+        f: F,
+    ) -> impl Future<Output = Result<Vec<U>, Error>> + Send
+    where
+        S: ?Sized + ToStatement + Sync + Send,
+        F: FnMut(&Row) -> Result<U, Error> + Send,
+    {
+        async move { self.query(statement, params).await?.iter().map(f).collect() }
+    }
+
+    /// Like [`ClientExt::query_as`], mapping each value converted to `T` with `f`.
+    ///
+    /// ```ignore
+    /// let ids: Vec<i32> = client
+    ///     .query_map_as("SELECT * FROM users", &[], |user: User| user.user_id)
+    ///     .await?;
+    /// ```
+    fn query_map_as<T, U, S, F>(
+        &self,
+        statement: &S,
+        params: &[&(dyn ToSql + Sync)],
+        f: F,
+    ) -> impl Future<Output = Result<Vec<U>, Error>> + Send
+    where
+        T: FromRow,
+        S: ?Sized + ToStatement + Sync + Send,
+        F: FnMut(T) -> U + Send,
+    {
+        async move {
+            Ok(self
+                .query_as(statement, params)
+                .await?
+                .into_iter()
+                .map(f)
+                .collect())
+        }
+    }
+}
+
+impl<C: GenericClient + Sync> ClientExt for C {}
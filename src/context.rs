@@ -0,0 +1,117 @@
+use std::{any::Any, cell::Cell};
+
+thread_local! {
+    /// The index of the row currently being converted by one of the slice conversions.
+    static ROW_INDEX: Cell<Option<usize>> = const { Cell::new(None) };
+    /// The user context installed by [`with_context`].
+    static USER_CONTEXT: Cell<Option<*const dyn Any>> = const { Cell::new(None) };
+}
+
+/// Information about the value being converted, passed to `from_fn` and `try_from_fn`
+/// functions of fields marked with `#[from_row(context)]`.
+pub struct ConversionContext<'a> {
+    row: &'a tokio_postgres::Row,
+    column: usize,
+    row_index: Option<usize>,
+    type_name: &'static str,
+    field: &'static str,
+    user: Option<*const dyn Any>,
+}
+
+impl<'a> ConversionContext<'a> {
+    /// Used by the derive.
+    ///
+    /// # Safety
+    ///
+    /// The context must not outlive the conversion it is created for, so that the user context it reads
+    /// from [`with_context`] stays borrowed while it can be accessed.
+    #[doc(hidden)]
+    pub unsafe fn new(
+        row: &'a tokio_postgres::Row,
+        column: usize,
+        type_name: &'static str,
+        field: &'static str,
+    ) -> Self {
+        Self {
+            row,
+            column,
+            row_index: ROW_INDEX.with(Cell::get),
+            type_name,
+            field,
+            user: USER_CONTEXT.with(Cell::get),
+        }
+    }
+
+    /// The row being converted.
+    pub fn row(&self) -> &'a tokio_postgres::Row {
+        self.row
+    }
+
+    /// The index of the row being converted, when converting a slice or vector of rows.
+    pub fn row_index(&self) -> Option<usize> {
+        self.row_index
+    }
+
+    /// The index of the column being converted.
+    pub fn column_index(&self) -> usize {
+        self.column
+    }
+
+    /// The name of the column being converted.
+    pub fn column_name(&self) -> &'a str {
+        self.row.columns()[self.column].name()
+    }
+
+    /// The name of the struct being converted, as given by [`std::any::type_name`].
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The name of the field being converted.
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// The user context installed with [`with_context`], if it is of type `T`.
+    pub fn user<T: Any>(&self) -> Option<&T> {
+        // SAFETY: the pointer was read while `with_context` was running on this thread, and `new` requires
+        // the context to only live for the duration of a conversion started inside of it
+        let user = unsafe { &*self.user? };
+        user.downcast_ref()
+    }
+}
+
+/// Makes `user` available to [`ConversionContext::user`] during the conversions performed in `f`.
+///
+/// Only the conversions running on the current thread see it, the parallel conversions of the `rayon` feature don't.
+///
+/// ```ignore
+/// let currency = Currency::Eur;
+/// let prices = with_context(&currency, || Price::try_from_slice(&rows))?;
+/// ```
+pub fn with_context<R>(user: &dyn Any, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<*const dyn Any>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            USER_CONTEXT.with(|c| c.set(self.0));
+        }
+    }
+
+    let _restore = Restore(USER_CONTEXT.with(|c| c.replace(Some(user as *const dyn Any))));
+    f()
+}
+
+/// Sets the index of the row being converted until it is dropped.
+pub(crate) struct RowIndexGuard(Option<usize>);
+
+impl RowIndexGuard {
+    pub(crate) fn new(index: usize) -> Self {
+        Self(ROW_INDEX.with(|c| c.replace(Some(index))))
+    }
+}
+
+impl Drop for RowIndexGuard {
+    fn drop(&mut self) {
+        ROW_INDEX.with(|c| c.set(self.0));
+    }
+}
@@ -0,0 +1,180 @@
+//! Bulk extraction and insertion with the binary `COPY` format, enabled by the `copy` feature.
+//!
+//! Structs opt in with `#[from_row(copy_out)]`, which implements [`FromCopyRow`] next to `FromRow`,
+//! reading the columns of the `COPY` in the order of the fields:
+//!
+//! ```ignore
+//! #[derive(FromRow)]
+//! #[from_row(copy_out)]
+//! struct Todo {
+//!     todo_id: i32,
+//!     text: String,
+//! }
+//!
+//! let stream = client.copy_out("COPY todos (todo_id, text) TO STDOUT (FORMAT binary)").await?;
+//! let todos: Vec<Todo> = copy_out_as::<Todo>(stream).try_collect().await?;
+//! ```
+//!
+//! And with `#[from_row(copy_in)]`, which implements [`ToCopyRow`], writing the fields in the same order:
+//!
+//! ```ignore
+//! let sink = client.copy_in(&copy_in_statement::<Todo>("todos")).await?;
+//! copy_in_from(sink, &todos).await?;
+//! ```
+//!
+//! Joined fields and `#[from_row(context)]` are not supported, and fields written with `copy_in`
+//! can't be converted, since the conversion can't be reversed.
+
+use bytes::{Bytes, BytesMut};
+use futures_util::{pin_mut, Stream, StreamExt};
+use tokio_postgres::{
+    binary_copy::{BinaryCopyInWriter, BinaryCopyOutRow, BinaryCopyOutStream},
+    types::{to_sql_checked, IsNull, ToSql, Type},
+    CopyInSink, CopyOutStream,
+};
+
+use crate::{Error, FromRow};
+
+/// A [`FromRow`] type that can also be converted from the rows of a binary `COPY ... TO STDOUT`,
+/// implemented by `#[from_row(copy_out)]`.
+pub trait FromCopyRow: FromRow {
+    /// Try's to perform the conversion, reading the columns starting at `index` in the order of the fields.
+    fn try_from_copy_row_at(row: &BinaryCopyOutRow, index: usize) -> Result<Self, Error>;
+
+    /// Try's to perform the conversion of a row with exactly the columns of this type.
+    fn try_from_copy_row(row: &BinaryCopyOutRow) -> Result<Self, Error> {
+        Self::try_from_copy_row_at(row, 0)
+    }
+}
+
+/// `None` when all the columns of `T` are null, like the `FromRow` implementation of `Option<T>`.
+impl<T: FromCopyRow> FromCopyRow for Option<T> {
+    fn try_from_copy_row_at(row: &BinaryCopyOutRow, index: usize) -> Result<Self, Error> {
+        if T::COLUMN_COUNT != 0 && copy_columns_are_null(row, index, T::COLUMN_COUNT)? {
+            return Ok(None);
+        }
+        T::try_from_copy_row_at(row, index).map(Some)
+    }
+}
+
+fn copy_columns_are_null(
+    row: &BinaryCopyOutRow,
+    index: usize,
+    count: usize,
+) -> Result<bool, Error> {
+    for i in index..index + count {
+        if row
+            .try_get::<Option<crate::__private::AnyColumn>>(i)?
+            .is_some()
+        {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// The types of the columns of `T`, guessed from the types each column accepts.
+///
+/// The first type accepted by each column is used, which is enough when all the accepted types share the same
+/// binary format, but not for `Json<T>` read from a `jsonb` column for example, use [`copy_out_as_with_types`] then.
+pub fn copy_types<T: FromRow>() -> Vec<Type> {
+    T::report_expected_columns()
+        .iter()
+        .map(|column| {
+            column
+                .accepted_types()
+                .into_iter()
+                .next()
+                .unwrap_or(Type::UNKNOWN)
+        })
+        .collect()
+}
+
+/// Parses the binary `COPY` format of `stream` into values of `T`, with the types guessed by [`copy_types`].
+pub fn copy_out_as<T: FromCopyRow>(stream: CopyOutStream) -> impl Stream<Item = Result<T, Error>> {
+    copy_out_as_with_types(stream, &copy_types::<T>())
+}
+
+/// Parses the binary `COPY` format of `stream` into values of `T`, with the types of the columns in order.
+pub fn copy_out_as_with_types<T: FromCopyRow>(
+    stream: CopyOutStream,
+    types: &[Type],
+) -> impl Stream<Item = Result<T, Error>> {
+    BinaryCopyOutStream::new(stream, types).map(|row| T::try_from_copy_row(&row?))
+}
+
+/// A [`FromRow`] type whose values can be written with a binary `COPY ... FROM STDIN`,
+/// implemented by `#[from_row(copy_in)]`.
+pub trait ToCopyRow: FromRow {
+    /// Pushes the value of every column of this type, in order.
+    fn copy_values<'a>(&'a self, values: &mut Vec<&'a (dyn ToSql + Sync)>);
+}
+
+/// Writes `NULL` for every column of `T` when `None`.
+impl<T: ToCopyRow> ToCopyRow for Option<T> {
+    fn copy_values<'a>(&'a self, values: &mut Vec<&'a (dyn ToSql + Sync)>) {
+        match self {
+            Some(value) => value.copy_values(values),
+            None => values.extend((0..T::COLUMN_COUNT).map(|_| &Null as &(dyn ToSql + Sync))),
+        }
+    }
+}
+
+/// A `NULL` of any type.
+#[derive(Debug)]
+struct Null;
+
+impl ToSql for Null {
+    fn to_sql(
+        &self,
+        _: &Type,
+        _: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(IsNull::Yes)
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+/// The `COPY ... FROM STDIN` statement writing the columns of `T` into `table`, in the binary format.
+pub fn copy_in_statement<T: FromRow>(table: &str) -> String {
+    let columns = T::report_expected_columns()
+        .iter()
+        .map(|column| column.column_name().unwrap_or("?"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("COPY {table} ({columns}) FROM STDIN (FORMAT binary)")
+}
+
+/// Writes `values` into a sink opened with [`copy_in_statement`], with the types guessed by [`copy_types`].
+///
+/// Returns the number of rows written.
+pub async fn copy_in_from<'a, T: ToCopyRow + 'a>(
+    sink: CopyInSink<Bytes>,
+    values: impl IntoIterator<Item = &'a T>,
+) -> Result<u64, Error> {
+    copy_in_from_with_types(sink, &copy_types::<T>(), values).await
+}
+
+/// Writes `values` into a sink opened with [`copy_in_statement`], with the types of the columns in order.
+///
+/// Returns the number of rows written.
+pub async fn copy_in_from_with_types<'a, T: ToCopyRow + 'a>(
+    sink: CopyInSink<Bytes>,
+    types: &[Type],
+    values: impl IntoIterator<Item = &'a T>,
+) -> Result<u64, Error> {
+    let writer = BinaryCopyInWriter::new(sink, types);
+    pin_mut!(writer);
+    let mut row = Vec::with_capacity(T::COLUMN_COUNT);
+    for value in values {
+        row.clear();
+        value.copy_values(&mut row);
+        writer.as_mut().write(&row).await?;
+    }
+    Ok(writer.finish().await?)
+}
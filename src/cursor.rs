@@ -0,0 +1,141 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio_postgres::{types::ToSql, GenericClient};
+
+use crate::{batch::Batcher, Error, FromRow};
+
+/// Makes the names of the cursors declared by this crate unique.
+static CURSORS: AtomicUsize = AtomicUsize::new(0);
+
+/// Reads the rows of a server-side cursor in batches, converting them to `T`.
+///
+/// The cursor is declared with `DECLARE .. CURSOR FOR <sql>` and read with `FETCH n`, for servers and poolers
+/// where cursors are preferred over portals. Cursors declared by [`declare`](Self::declare) only live until the end
+/// of the transaction, so the client must be a [`Transaction`](tokio_postgres::Transaction), while the ones declared by
+/// [`declare_with_hold`](Self::declare_with_hold) outlive it and can be read outside of one.
+///
+/// Values that join several rows are only yielded once the first row of the next value is seen, so they may be
+/// completed by the rows of the next fetch, and a batch may hold fewer values than the rows that were fetched.
+pub struct CursorReader<'a, T, C: ?Sized> {
+    client: &'a C,
+    name: String,
+    batch_size: usize,
+    checked: bool,
+    batcher: Batcher<T>,
+}
+
+impl<'a, T: FromRow, C: GenericClient + ?Sized> CursorReader<'a, T, C> {
+    /// Declares a cursor for `sql` with `params`, in the transaction of `client`.
+    ///
+    /// ```ignore
+    /// let transaction = client.transaction().await?;
+    /// let mut cursor = CursorReader::<Todo, _>::declare(&transaction, "SELECT todo_id, text FROM todos", &[], 1000).await?;
+    /// while let Some(todos) = cursor.next_batch().await? {
+    ///     index(&todos).await?;
+    /// }
+    /// cursor.close().await?;
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    pub async fn declare(
+        client: &'a C,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+        batch_size: usize,
+    ) -> Result<Self, Error> {
+        Self::declare_cursor(client, sql, params, batch_size, false).await
+    }
+
+    /// Declares a cursor `WITH HOLD` for `sql` with `params`, which can be used outside of a transaction.
+    ///
+    /// The rows of the query are materialized when the transaction declaring the cursor commits, and the cursor
+    /// stays open until [`close`](Self::close) is called or the session ends, so it should always be closed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    pub async fn declare_with_hold(
+        client: &'a C,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+        batch_size: usize,
+    ) -> Result<Self, Error> {
+        Self::declare_cursor(client, sql, params, batch_size, true).await
+    }
+
+    async fn declare_cursor(
+        client: &'a C,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+        batch_size: usize,
+        with_hold: bool,
+    ) -> Result<Self, Error> {
+        assert!(batch_size > 0, "the batch size must be positive");
+        let name = format!(
+            "postgres_from_row_cursor_{}",
+            CURSORS.fetch_add(1, Ordering::Relaxed)
+        );
+        let hold = if with_hold {
+            "WITH HOLD"
+        } else {
+            "WITHOUT HOLD"
+        };
+        client
+            .execute(
+                &format!("DECLARE {name} NO SCROLL CURSOR {hold} FOR {sql}"),
+                params,
+            )
+            .await?;
+        Ok(Self {
+            client,
+            name,
+            batch_size,
+            checked: false,
+            batcher: Batcher::new(),
+        })
+    }
+
+    /// The name of the cursor.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Fetches the next batch of rows, converting them.
+    ///
+    /// Returns `None` once all the rows have been read, or after an error was returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows do not contain the expected column names, which are validated on the first fetch.
+    pub async fn next_batch(&mut self) -> Result<Option<Vec<T>>, Error> {
+        let fetch = format!("FETCH {} FROM {}", self.batch_size, self.name);
+        let mut values = Vec::new();
+        while values.is_empty() && !self.batcher.is_done() {
+            let rows = match self.client.query(&fetch, &[]).await {
+                Ok(rows) => rows,
+                Err(error) => {
+                    self.batcher.stop();
+                    return Err(error.into());
+                }
+            };
+            if let (false, [first, ..]) = (self.checked, rows.as_slice()) {
+                T::assert_matches(first.columns());
+                self.checked = true;
+            }
+            // the cursor is exhausted once it returns fewer rows than requested
+            values = self.batcher.push(&rows, rows.len() < self.batch_size)?;
+        }
+        Ok((!values.is_empty()).then_some(values))
+    }
+
+    /// Closes the cursor, releasing its resources before the end of the transaction, or of the session for
+    /// cursors declared `WITH HOLD`.
+    pub async fn close(self) -> Result<(), Error> {
+        self.client
+            .batch_execute(&format!("CLOSE {}", self.name))
+            .await?;
+        Ok(())
+    }
+}
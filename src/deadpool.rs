@@ -0,0 +1,171 @@
+//! Conversions for pooled connections of `deadpool_postgres`, enabled by the `deadpool` feature.
+//!
+//! [`ClientExt`](crate::ClientExt) already works through the `Deref` of [`Object`], these traits additionally
+//! use the statement cache of the pooled connection, and get a connection from a [`Pool`] for a single query.
+
+use std::{fmt, future::Future};
+
+use deadpool_postgres::{Object, Pool, PoolError};
+use tokio_postgres::types::ToSql;
+
+use crate::{Error, FromRow};
+
+/// Extends [`Object`] with methods that prepare the statement through its cache and convert the rows to `T`.
+///
+/// The columns are validated against `T` using the columns of the statement, even if no rows are returned.
+pub trait ObjectExt {
+    /// Like [`tokio_postgres::Client::query`], converting all the rows to `T`.
+    fn query_as<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Vec<T>, Error>> + Send;
+
+    /// Like [`tokio_postgres::Client::query_one`], converting the row to `T`.
+    fn query_one_as<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<T, Error>> + Send;
+
+    /// Converts all the rows to a single `T` that joins them, see `#[from_row(join)]`.
+    ///
+    /// Returns `None` if there are no rows, and an error if the rows don't join into a single value.
+    fn query_joined<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Option<T>, Error>> + Send;
+}
+
+impl ObjectExt for Object {
+    async fn query_as<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<T>, Error> {
+        let statement = self.prepare_cached(sql).await?;
+        let rows = self.query(&statement, params).await?;
+        T::try_from_slice_with_columns(statement.columns(), &rows)
+    }
+
+    async fn query_one_as<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<T, Error> {
+        let statement = self.prepare_cached(sql).await?;
+        T::assert_matches_statement(&statement);
+        let row = self.query_one(&statement, params).await?;
+        T::try_from_row_unchecked(&row)
+    }
+
+    async fn query_joined<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Option<T>, Error> {
+        single(self.query_as(sql, params).await?)
+    }
+}
+
+/// The error returned by [`PoolExt`], when either no connection could be taken from the pool,
+/// or the query failed.
+#[derive(Debug)]
+pub enum PoolQueryError {
+    /// No connection could be taken from the pool.
+    Pool(PoolError),
+    /// The query failed, or its rows could not be converted.
+    Query(Error),
+}
+
+impl fmt::Display for PoolQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolQueryError::Pool(error) => write!(f, "could not get a connection: {error}"),
+            PoolQueryError::Query(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for PoolQueryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PoolQueryError::Pool(error) => Some(error),
+            PoolQueryError::Query(error) => Some(error),
+        }
+    }
+}
+
+impl From<PoolError> for PoolQueryError {
+    fn from(error: PoolError) -> Self {
+        PoolQueryError::Pool(error)
+    }
+}
+
+impl From<Error> for PoolQueryError {
+    fn from(error: Error) -> Self {
+        PoolQueryError::Query(error)
+    }
+}
+
+/// Extends [`Pool`] with the methods of [`ObjectExt`], taking a connection from the pool for the query.
+pub trait PoolExt {
+    /// Like [`ObjectExt::query_as`], on a connection from the pool.
+    fn query_as<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Vec<T>, PoolQueryError>> + Send;
+
+    /// Like [`ObjectExt::query_one_as`], on a connection from the pool.
+    fn query_one_as<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<T, PoolQueryError>> + Send;
+
+    /// Like [`ObjectExt::query_joined`], on a connection from the pool.
+    fn query_joined<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> impl Future<Output = Result<Option<T>, PoolQueryError>> + Send;
+}
+
+impl PoolExt for Pool {
+    async fn query_as<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<T>, PoolQueryError> {
+        Ok(ObjectExt::query_as(&self.get().await?, sql, params).await?)
+    }
+
+    async fn query_one_as<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<T, PoolQueryError> {
+        Ok(ObjectExt::query_one_as(&self.get().await?, sql, params).await?)
+    }
+
+    async fn query_joined<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Option<T>, PoolQueryError> {
+        Ok(ObjectExt::query_joined(&self.get().await?, sql, params).await?)
+    }
+}
+
+/// Returns the only value, if any.
+fn single<T>(mut values: Vec<T>) -> Result<Option<T>, Error> {
+    if values.len() > 1 {
+        return Err(Error::conversion(format!(
+            "expected the rows to join into a single value, found {} values",
+            values.len()
+        )));
+    }
+    Ok(values.pop())
+}
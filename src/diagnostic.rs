@@ -0,0 +1,201 @@
+//! Pretty terminal diagnostics with [`miette`], enabled by the `miette` feature.
+//!
+//! [`MatchError`], [`SchemaMismatch`] and [`Error`] implement [`Diagnostic`] with a code and a help message,
+//! and [`MatchError::with_sql`] adds the SQL of the query, labeling the column that does not match:
+//!
+//! ```ignore
+//! let statement = client.prepare(sql).await?;
+//! User::check_matches(statement.columns()).map_err(|error| error.with_sql(sql))?;
+//! ```
+
+use std::fmt;
+
+pub use miette::Diagnostic;
+use miette::{LabeledSpan, SourceCode, SourceSpan};
+
+use crate::{Error, MatchError, SchemaMismatch};
+
+impl Diagnostic for MatchError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new("postgres_from_row::column_mismatch"))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let help = match (self.expected(), self.found_name()) {
+            (Some(expected), Some(_)) if self.is_type_mismatch() => format!(
+                "`{}` can be read from {}, cast the column to one of them",
+                expected.describe_type(),
+                expected.describe_accepted_types()
+            ),
+            (Some(expected), Some(found)) => match expected.column_name() {
+                Some(name) => format!(
+                    "rename the column with `AS {name}`, \
+                     or the field with `#[from_row(rename = \"{found}\")]`"
+                ),
+                None => "select the columns in the order of the fields".to_string(),
+            },
+            (Some(expected), None) => format!(
+                "select the `{}` column, or remove its field",
+                expected.column_name().unwrap_or("-")
+            ),
+            (None, Some(_)) => "remove the column from the query, or add a field for it".into(),
+            (None, None) => return None,
+        };
+        Some(Box::new(help))
+    }
+}
+
+impl Diagnostic for SchemaMismatch {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let code = match self {
+            SchemaMismatch::Query(_) => "postgres_from_row::schema_query",
+            SchemaMismatch::TableNotFound { .. } => "postgres_from_row::table_not_found",
+            SchemaMismatch::Columns { .. } => "postgres_from_row::schema_mismatch",
+        };
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let help = match self {
+            SchemaMismatch::Query(_) => return None,
+            SchemaMismatch::TableNotFound { .. } => {
+                "check the name of the table, and the `search_path` of the connection"
+            }
+            SchemaMismatch::Columns { .. } => {
+                "update the struct to the table, or run the migrations that are pending"
+            }
+        };
+        Some(Box::new(help))
+    }
+}
+
+impl Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let code = match self {
+            _ if self.is_null() => "postgres_from_row::unexpected_null",
+            Error::Postgres(_) => "postgres_from_row::decode",
+            Error::Conversion(_) => "postgres_from_row::conversion",
+            Error::Field(error) => return error.error().code(),
+            Error::Row(error) => return error.error().code(),
+            Error::Mismatch(_) => "postgres_from_row::column_mismatch",
+        };
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        if self.is_null() {
+            Some(Box::new(
+                "make the field an `Option`, or filter the rows where the column is NULL",
+            ))
+        } else if let Error::Mismatch(_) = self {
+            Some(Box::new(
+                "select the columns of the report in order, or update the type to the query",
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// A [`MatchError`] with the SQL of the query, created by [`MatchError::with_sql`].
+///
+/// Shows the SQL with a label on the column that does not match, or where a missing column was expected.
+#[derive(Debug, Clone)]
+pub struct SqlMatchError {
+    error: MatchError,
+    sql: String,
+    label: Option<LabeledSpan>,
+}
+
+impl MatchError {
+    /// Adds the SQL of the query the columns came from, so that its diagnostic points into it.
+    ///
+    /// The column is found by name in the SQL text, which is not parsed, so a column selected with `*`
+    /// or a name that appears earlier in the query may not be labeled where it is selected.
+    pub fn with_sql(self, sql: impl Into<String>) -> SqlMatchError {
+        let sql = sql.into();
+        let label = match (self.found_name(), self.found_type()) {
+            (Some(name), Some(found)) => find_word(&sql, name).map(|span| {
+                let label = match self.expected() {
+                    Some(_) if self.is_type_mismatch() => format!("this is `{found}`"),
+                    Some(expected) => {
+                        format!("expected `{}` here", expected.column_name().unwrap_or("-"))
+                    }
+                    None => "this column is not expected".to_string(),
+                };
+                LabeledSpan::new_with_span(Some(label), span)
+            }),
+            _ => self.expected().map(|expected| {
+                // the missing column should have been selected before `FROM`
+                let end = find_word(&sql, "from").map_or(sql.len(), |span| span.offset());
+                let end = sql[..end].trim_end().len();
+                LabeledSpan::new_with_span(
+                    Some(format!(
+                        "`{}` is missing",
+                        expected.column_name().unwrap_or("-")
+                    )),
+                    (end, 0),
+                )
+            }),
+        };
+        SqlMatchError {
+            error: self,
+            sql,
+            label,
+        }
+    }
+}
+
+impl SqlMatchError {
+    /// The error without the SQL.
+    pub fn error(&self) -> &MatchError {
+        &self.error
+    }
+
+    /// The SQL of the query.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+}
+
+impl fmt::Display for SqlMatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for SqlMatchError {}
+
+impl Diagnostic for SqlMatchError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.code()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.help()
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.sql)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let label = self.label.clone()?;
+        Some(Box::new(std::iter::once(label)))
+    }
+}
+
+/// Finds the first occurrence of `word` in `sql` that is not part of a longer identifier, ignoring ascii case.
+fn find_word(sql: &str, word: &str) -> Option<SourceSpan> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    // lowercasing ascii keeps the offsets of every character
+    let lower = sql.to_ascii_lowercase();
+    lower
+        .match_indices(&word.to_ascii_lowercase())
+        .find(|(i, _)| {
+            let before = sql[..*i].chars().next_back();
+            let after = sql[i + word.len()..].chars().next();
+            !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+        })
+        .map(|(i, _)| (i, word.len()).into())
+}
@@ -0,0 +1,110 @@
+use std::{any::Any, marker::PhantomData};
+
+use tokio_postgres::{Column, Row};
+
+use crate::{Error, ExpectedColumns, FromRow, MatchError, MatchQuality};
+
+/// An object safe version of [`FromRow`], so conversions can be registered and picked at runtime.
+///
+/// Obtained for any [`FromRow`] type with [`erased`], the converted values are boxed as [`Any`]
+/// and can be downcast back to the type:
+///
+/// ```ignore
+/// let mappers: HashMap<&str, Box<dyn ErasedFromRow>> = HashMap::from([
+///     ("users", erased::<User>()),
+///     ("todos", erased::<Todo>()),
+/// ]);
+/// let value = mappers["users"].erased_try_from_row(&row)?;
+/// let user = value.downcast::<User>().unwrap();
+/// ```
+pub trait ErasedFromRow: Send + Sync {
+    /// The name of the type this converts to, as given by [`std::any::type_name`].
+    fn type_name(&self) -> &'static str;
+
+    /// See [`FromRow::COLUMN_COUNT`].
+    fn column_count(&self) -> usize;
+
+    /// See [`FromRow::JOINED`].
+    fn joined(&self) -> bool;
+
+    /// See [`FromRow::report_expected_columns`].
+    fn expected_columns(&self) -> ExpectedColumns;
+
+    /// Checks that the columns match what is expected, returning the first column that does not match on error,
+    /// see [`FromRow::check_matches`].
+    fn try_assert_matches(&self, columns: &[Column]) -> Result<(), MatchError>;
+
+    /// See [`FromRow::matches`].
+    fn matches(&self, columns: &[Column]) -> MatchQuality;
+
+    /// Try's to perform the conversion, see [`FromRow::try_from_row`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not contain the expected column names.
+    fn erased_try_from_row(&self, row: &Row) -> Result<Box<dyn Any>, Error>;
+
+    /// Try's to perform the conversion on a slice of rows, see [`FromRow::try_from_slice`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not contain the expected column names.
+    fn erased_try_from_slice(&self, rows: &[Row]) -> Result<Vec<Box<dyn Any>>, Error>;
+}
+
+/// The [`ErasedFromRow`] of `T`, see [`erased`].
+pub struct ErasedMapper<T>(PhantomData<fn() -> T>);
+
+impl<T> ErasedMapper<T> {
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for ErasedMapper<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: FromRow + 'static> ErasedFromRow for ErasedMapper<T> {
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    fn column_count(&self) -> usize {
+        T::COLUMN_COUNT
+    }
+
+    fn joined(&self) -> bool {
+        T::JOINED
+    }
+
+    fn expected_columns(&self) -> ExpectedColumns {
+        T::report_expected_columns()
+    }
+
+    fn try_assert_matches(&self, columns: &[Column]) -> Result<(), MatchError> {
+        T::check_matches(columns)
+    }
+
+    fn matches(&self, columns: &[Column]) -> MatchQuality {
+        T::matches(columns)
+    }
+
+    fn erased_try_from_row(&self, row: &Row) -> Result<Box<dyn Any>, Error> {
+        Ok(Box::new(T::try_from_row(row)?))
+    }
+
+    fn erased_try_from_slice(&self, rows: &[Row]) -> Result<Vec<Box<dyn Any>>, Error> {
+        Ok(T::try_from_slice(rows)?
+            .into_iter()
+            .map(|this| Box::new(this) as Box<dyn Any>)
+            .collect())
+    }
+}
+
+/// Returns the [`ErasedFromRow`] of `T`.
+pub fn erased<T: FromRow + 'static>() -> Box<dyn ErasedFromRow> {
+    Box::new(ErasedMapper::<T>::new())
+}
@@ -0,0 +1,235 @@
+use std::fmt;
+
+use crate::MismatchReport;
+
+/// The error returned when converting a row fails.
+#[derive(Debug)]
+pub enum Error {
+    /// The row could not be read, or a column could not be decoded.
+    Postgres(tokio_postgres::Error),
+    /// A user conversion (`try_from`, `try_from_fn` or `validate`) failed.
+    Conversion(Box<dyn std::error::Error + Send + Sync>),
+    /// Reading a field of a derived struct failed, see [`FieldError`].
+    Field(Box<FieldError>),
+    /// Converting one of the rows of a slice, vector or stream of rows failed, see [`RowError`].
+    Row(Box<RowError>),
+    /// The columns don't match what the type expects, returned by the `*_checked` conversions instead of panicking,
+    /// like [`FromRow::from_row_checked`](crate::FromRow::from_row_checked).
+    Mismatch(Box<MismatchReport>),
+}
+
+/// The row that could not be converted, out of the rows being converted, and the error that caused it.
+#[derive(Debug)]
+pub struct RowError {
+    index: usize,
+    key: Option<(String, String)>,
+    source: Error,
+}
+
+impl RowError {
+    /// The zero-based index of the row, among the rows being converted.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The name of the first column of the row, shown with its value to help locating the row in the database.
+    ///
+    /// Only known when the first column is an integer or text, which are the usual types of keys.
+    pub fn key_column(&self) -> Option<&str> {
+        self.key.as_ref().map(|(column, _)| column.as_str())
+    }
+
+    /// The value of [`RowError::key_column`], `NULL` when it is null.
+    pub fn key_value(&self) -> Option<&str> {
+        self.key.as_ref().map(|(_, value)| value.as_str())
+    }
+
+    /// The error that caused the row to fail, which is never an [`Error::Row`].
+    pub fn error(&self) -> &Error {
+        &self.source
+    }
+
+    /// Records that `error` happened converting the row at `index`, unless it already was recorded.
+    pub(crate) fn wrap(error: Error, index: usize, row: Option<&tokio_postgres::Row>) -> Error {
+        match error {
+            Error::Row(error) => Error::Row(error),
+            error => Error::Row(Box::new(RowError {
+                index,
+                key: row.and_then(row_key),
+                source: error,
+            })),
+        }
+    }
+}
+
+/// The name and value of the first column of `row`, if it is an integer or text.
+fn row_key(row: &tokio_postgres::Row) -> Option<(String, String)> {
+    use tokio_postgres::types::Type;
+
+    fn get<T: for<'a> tokio_postgres::types::FromSql<'a> + ToString>(
+        row: &tokio_postgres::Row,
+    ) -> Option<String> {
+        let value = row.try_get::<_, Option<T>>(0).ok()?;
+        Some(value.map_or_else(|| "NULL".to_string(), |value| value.to_string()))
+    }
+
+    let column = row.columns().first()?;
+    let value = match *column.type_() {
+        Type::INT2 => get::<i16>(row),
+        Type::INT4 => get::<i32>(row),
+        Type::INT8 => get::<i64>(row),
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => get::<String>(row),
+        _ => None,
+    }?;
+    Some((column.name().to_string(), value))
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error converting row {}", self.index)?;
+        if let Some((column, value)) = &self.key {
+            write!(f, " (`{column}` = {value})")?;
+        }
+        write!(f, ": {}", self.source)
+    }
+}
+
+/// The field of a derived struct that could not be read, and the error that caused it.
+///
+/// For fields of flattened structs, the path starts at the outermost struct, like `customer.address.zip`.
+#[derive(Debug)]
+pub struct FieldError {
+    type_name: &'static str,
+    // innermost first, since the outer fields are added as the error is returned
+    fields: Vec<&'static str>,
+    column: Option<String>,
+    source: Error,
+}
+
+impl FieldError {
+    /// The name of the outermost struct, as given by [`std::any::type_name`].
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The fields leading to the one that failed, separated by `.`, starting at the outermost struct.
+    pub fn path(&self) -> String {
+        let mut fields = self.fields.iter().rev();
+        let mut path = fields.next().copied().unwrap_or_default().to_string();
+        for field in fields {
+            path.push('.');
+            path.push_str(field);
+        }
+        path
+    }
+
+    /// The name of the column the field was read from.
+    pub fn column(&self) -> Option<&str> {
+        self.column.as_deref()
+    }
+
+    /// The error that caused the field to fail, which is never an [`Error::Field`].
+    pub fn error(&self) -> &Error {
+        &self.source
+    }
+
+    /// Records that `error` happened reading `field` of the struct named `type_name`, from `column`.
+    ///
+    /// The errors of the fields of flattened structs are nested under `field`, keeping their column.
+    pub(crate) fn wrap(
+        error: Error,
+        type_name: &'static str,
+        field: &'static str,
+        column: Option<&str>,
+    ) -> Error {
+        match error {
+            Error::Field(mut error) => {
+                error.type_name = type_name;
+                error.fields.push(field);
+                Error::Field(error)
+            }
+            error => Error::Field(Box::new(FieldError {
+                type_name,
+                fields: vec![field],
+                column: column.map(str::to_string),
+                source: error,
+            })),
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "error reading field `{}` of `{}`",
+            self.path(),
+            self.type_name
+        )?;
+        if let Some(column) = &self.column {
+            write!(f, " from column `{column}`")?;
+        }
+        write!(f, ": {}", self.source)
+    }
+}
+
+impl Error {
+    /// Wraps the error of a user conversion.
+    ///
+    /// Errors that already are an [`Error`] or a [`tokio_postgres::Error`] are not wrapped again.
+    pub fn conversion(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        let error = error.into();
+        let error = match error.downcast::<Error>() {
+            Ok(error) => return *error,
+            Err(error) => error,
+        };
+        match error.downcast::<tokio_postgres::Error>() {
+            Ok(error) => Error::Postgres(*error),
+            Err(error) => Error::Conversion(error),
+        }
+    }
+
+    /// Whether this error was caused by reading a null column into a type that is not nullable.
+    pub fn is_null(&self) -> bool {
+        match self {
+            Error::Postgres(error) => std::error::Error::source(error)
+                .is_some_and(|x| x.downcast_ref::<tokio_postgres::types::WasNull>().is_some()),
+            Error::Conversion(error) => error.is::<tokio_postgres::types::WasNull>(),
+            Error::Field(error) => error.source.is_null(),
+            Error::Row(error) => error.source.is_null(),
+            Error::Mismatch(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Postgres(error) => error.fmt(f),
+            Error::Conversion(error) => write!(f, "error converting column: {error}"),
+            Error::Field(error) => error.fmt(f),
+            Error::Row(error) => error.fmt(f),
+            Error::Mismatch(report) => {
+                write!(f, "the columns do not match what is expected:\n{report}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Postgres(error) => Some(error),
+            Error::Conversion(error) => Some(&**error),
+            Error::Field(error) => Some(&error.source),
+            Error::Row(error) => Some(&error.source),
+            Error::Mismatch(_) => None,
+        }
+    }
+}
+
+impl From<tokio_postgres::Error> for Error {
+    fn from(error: tokio_postgres::Error) -> Self {
+        Error::Postgres(error)
+    }
+}
@@ -0,0 +1,74 @@
+use tokio_postgres::types::FromSql;
+
+use crate::{ExpectedColumn, ExpectedColumns, FromRow};
+
+/// Builds the [`ExpectedColumns`] of a hand-written [`FromRow`] implementation,
+/// so it reports its columns like a derived one:
+///
+/// ```ignore
+/// fn report_expected_columns() -> ExpectedColumns {
+///     ExpectedColumnsBuilder::new()
+///         .push_column::<i32>("todo_id")
+///         .push_nested::<User>()
+///         .mark_nullable()
+///         .build()
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ExpectedColumnsBuilder {
+    columns: Vec<ExpectedColumn>,
+    // the start of the columns added by the last push, which `mark_nullable` applies to
+    last: usize,
+}
+
+impl ExpectedColumnsBuilder {
+    /// A builder without any column.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a column named `name`, decoded as `T`.
+    pub fn push_column<T: for<'a> FromSql<'a>>(self, name: &'static str) -> Self {
+        self.push(std::iter::once(ExpectedColumn::new::<T>(Some(name))))
+    }
+
+    /// Adds a column without a name, decoded as `T`, like the columns of tuples.
+    pub fn push_unnamed<T: for<'a> FromSql<'a>>(self) -> Self {
+        self.push(std::iter::once(ExpectedColumn::new::<T>(None)))
+    }
+
+    /// Adds all the columns expected by `U`, which is read from the row like a flattened field.
+    pub fn push_nested<U: FromRow>(self) -> Self {
+        self.push(U::report_expected_columns().iter().copied())
+    }
+
+    /// Marks the columns added by the last push as nullable, like the columns of an `Option<U>`
+    /// read from the columns of a `LEFT JOIN`.
+    pub fn mark_nullable(mut self) -> Self {
+        for column in &mut self.columns[self.last..] {
+            column.set_nullable();
+        }
+        self
+    }
+
+    /// The number of columns added so far, which must be the `COLUMN_COUNT` of the implementation.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Whether no column was added yet.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// The columns added so far, in order.
+    pub fn build(self) -> ExpectedColumns {
+        self.columns.into()
+    }
+
+    fn push(mut self, columns: impl IntoIterator<Item = ExpectedColumn>) -> Self {
+        self.last = self.columns.len();
+        self.columns.extend(columns);
+        self
+    }
+}
@@ -0,0 +1,127 @@
+//! Typed `EXPLAIN (FORMAT JSON)` output, enabled by the `explain` feature.
+
+use serde::Deserialize;
+use tokio_postgres::{
+    types::{Json, ToSql},
+    GenericClient,
+};
+
+use crate::{Error, FromRow};
+
+/// The output of `EXPLAIN (FORMAT JSON)` for a single statement.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Explain {
+    /// The root node of the plan.
+    #[serde(rename = "Plan")]
+    pub plan: PlanNode,
+    /// The time spent planning, in milliseconds, only present with `ANALYZE` or `SUMMARY`.
+    #[serde(rename = "Planning Time")]
+    pub planning_time: Option<f64>,
+    /// The time spent executing, in milliseconds, only present with `ANALYZE`.
+    #[serde(rename = "Execution Time")]
+    pub execution_time: Option<f64>,
+}
+
+/// A single node of a query plan.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlanNode {
+    /// The kind of node, like `Seq Scan` or `Hash Join`.
+    #[serde(rename = "Node Type")]
+    pub node_type: String,
+    /// The table this node reads, for scans.
+    #[serde(rename = "Relation Name")]
+    pub relation_name: Option<String>,
+    /// The alias of the table this node reads, for scans.
+    #[serde(rename = "Alias")]
+    pub alias: Option<String>,
+    /// The estimated cost before the first row can be returned.
+    #[serde(rename = "Startup Cost")]
+    pub startup_cost: f64,
+    /// The estimated cost to return all rows.
+    #[serde(rename = "Total Cost")]
+    pub total_cost: f64,
+    /// The estimated number of rows returned.
+    #[serde(rename = "Plan Rows")]
+    pub plan_rows: f64,
+    /// The estimated average width of the rows returned, in bytes.
+    #[serde(rename = "Plan Width")]
+    pub plan_width: i64,
+    /// The time until the first row was returned, in milliseconds, only present with `ANALYZE`.
+    #[serde(rename = "Actual Startup Time")]
+    pub actual_startup_time: Option<f64>,
+    /// The time until all rows were returned, in milliseconds, only present with `ANALYZE`.
+    #[serde(rename = "Actual Total Time")]
+    pub actual_total_time: Option<f64>,
+    /// The number of rows returned per loop, only present with `ANALYZE`.
+    #[serde(rename = "Actual Rows")]
+    pub actual_rows: Option<f64>,
+    /// The number of times this node was executed, only present with `ANALYZE`.
+    #[serde(rename = "Actual Loops")]
+    pub actual_loops: Option<f64>,
+    /// The child nodes of this node.
+    #[serde(rename = "Plans", default)]
+    pub plans: Vec<PlanNode>,
+    /// The properties of the node that don't have a field, which depend on the kind of node.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl PlanNode {
+    /// Iterates over this node and all of its descendants, depth first.
+    pub fn iter(&self) -> impl Iterator<Item = &PlanNode> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.plans.iter().rev());
+            Some(node)
+        })
+    }
+}
+
+/// The single row returned by `EXPLAIN (FORMAT JSON)`.
+#[derive(FromRow)]
+#[from_row(crate = "crate")]
+struct ExplainRow {
+    #[from_row(rename = "QUERY PLAN")]
+    plan: Json<Vec<Explain>>,
+}
+
+/// Runs `EXPLAIN (FORMAT JSON)` on `sql`, returning the plan postgres would use.
+///
+/// The statement is not executed, use [`explain_analyze_as`] for the actual times and row counts.
+pub async fn explain_as(
+    client: &impl GenericClient,
+    sql: &str,
+    params: &[&(dyn ToSql + Sync)],
+) -> Result<Explain, Error> {
+    explain(client, "EXPLAIN (FORMAT JSON)", sql, params).await
+}
+
+/// Runs `EXPLAIN (ANALYZE, FORMAT JSON)` on `sql`, returning the plan with the actual times and row counts.
+///
+/// The statement is executed, so any changes it makes should be rolled back by the caller.
+pub async fn explain_analyze_as(
+    client: &impl GenericClient,
+    sql: &str,
+    params: &[&(dyn ToSql + Sync)],
+) -> Result<Explain, Error> {
+    explain(client, "EXPLAIN (ANALYZE, FORMAT JSON)", sql, params).await
+}
+
+async fn explain(
+    client: &impl GenericClient,
+    prefix: &str,
+    sql: &str,
+    params: &[&(dyn ToSql + Sync)],
+) -> Result<Explain, Error> {
+    let sql = format!("{prefix} {sql}");
+    let row = client.query_one(sql.as_str(), params).await?;
+    let Json(mut plans) = ExplainRow::try_from_row(&row)?.plan;
+    if plans.len() != 1 {
+        return Err(Error::conversion(format!(
+            "expected a single plan from EXPLAIN, found {}",
+            plans.len()
+        )));
+    }
+    Ok(plans.remove(0))
+}
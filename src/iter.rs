@@ -0,0 +1,75 @@
+use std::{iter::FusedIterator, marker::PhantomData};
+
+use tokio_postgres::Row;
+
+use crate::{context, Error, FromRow, RowError};
+
+/// An iterator converting a slice of rows one value at a time, created by [`FromRow::iter_from_slice`].
+///
+/// Values that join several rows are only yielded once the first row of the next value is seen,
+/// or when the rows run out.
+///
+/// After an error is returned, the iterator stops.
+pub struct SliceIter<'a, T> {
+    rows: std::iter::Enumerate<std::slice::Iter<'a, Row>>,
+    current: Option<T>,
+    checked: bool,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: FromRow> SliceIter<'a, T> {
+    pub(crate) fn new(rows: &'a [Row]) -> Self {
+        Self {
+            rows: rows.iter().enumerate(),
+            current: None,
+            checked: false,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: FromRow> Iterator for SliceIter<'_, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        for (i, row) in self.rows.by_ref() {
+            if !self.checked {
+                T::assert_matches(row.columns());
+                self.checked = true;
+            }
+            let _row_index = context::RowIndexGuard::new(i);
+            match T::try_from_row_joined(self.current.as_mut(), row, 0) {
+                Ok(Some(next)) if !T::JOINED => return Some(Ok(next)),
+                Ok(Some(next)) => {
+                    if let Some(previous) = self.current.replace(next) {
+                        return Some(Ok(previous));
+                    }
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    self.done = true;
+                    self.current = None;
+                    return Some(Err(RowError::wrap(error, i, Some(row))));
+                }
+            }
+        }
+        self.done = true;
+        self.current.take().map(Ok)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        // an error ends the iteration early, so only one value is certain
+        let rows = self.rows.len() + self.current.is_some() as usize;
+        ((rows > 0) as usize, Some(rows))
+    }
+}
+
+impl<T: FromRow> FusedIterator for SliceIter<'_, T> {}
@@ -0,0 +1,68 @@
+use std::fmt;
+
+use crate::{ExpectedColumns, FromRow};
+
+/// Displays the columns expected by a type as a table, without comparing them to anything.
+///
+/// Useful to log at startup what each registered type expects:
+///
+/// ```ignore
+/// println!("{}", ExpectedLayout::of::<User>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExpectedLayout {
+    type_name: Option<&'static str>,
+    columns: ExpectedColumns,
+}
+
+impl ExpectedLayout {
+    /// The layout expected by `T`.
+    pub fn of<T: FromRow>() -> Self {
+        Self {
+            type_name: Some(std::any::type_name::<T>()),
+            columns: T::report_expected_columns(),
+        }
+    }
+
+    /// The layout described by `columns`.
+    pub fn new(columns: ExpectedColumns) -> Self {
+        Self {
+            type_name: None,
+            columns,
+        }
+    }
+
+    /// The columns of this layout.
+    pub fn columns(&self) -> &ExpectedColumns {
+        &self.columns
+    }
+}
+
+impl fmt::Display for ExpectedLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.type_name {
+            Some(type_name) => writeln!(f, "Expected Columns of `{type_name}`:")?,
+            None => writeln!(f, "Expected Columns:")?,
+        }
+        writeln!(f, "{:-<60}", "")?;
+        writeln!(
+            f,
+            "{:>3} | {:<20} | {:<20} | Nullable",
+            "#", "Column Name", "Rust Type"
+        )?;
+        writeln!(f, "{:-<60}", "")?;
+        for (i, column) in self.columns.iter().enumerate() {
+            // nullability rarely depends on the actual type, so any type it accepts will do here
+            let nullable = column.nullable(&column.probe_type());
+            writeln!(
+                f,
+                "{:>3} | {:<20} | {:<20} | {}",
+                i,
+                column.column_name().unwrap_or("-"),
+                column.describe_type(),
+                if nullable { "Yes" } else { "No" }
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,99 @@
 #![doc = include_str!("../README.md")]
 
+mod aggregate;
+mod batch;
+mod cache;
+mod case;
+mod client;
+mod context;
+mod cursor;
+mod erased;
+mod error;
+mod expected;
+mod iter;
+mod layout;
+mod macros;
+mod map;
+mod matching;
+mod paginated;
+mod params;
+mod plan;
+mod portal;
+mod query;
+mod report;
+mod result_sets;
+mod schema;
+mod select;
+mod statement;
+mod table;
+mod text_enum;
+mod trace;
 mod tuples;
+mod type_names;
+mod typed_client;
+mod via_try_from;
 
-pub use postgres_from_row_derive::FromRow;
+#[cfg(feature = "copy")]
+pub mod copy;
+#[cfg(feature = "deadpool")]
+pub mod deadpool;
+#[cfg(feature = "miette")]
+pub mod diagnostic;
+#[cfg(feature = "explain")]
+pub mod explain;
+#[cfg(feature = "stats")]
+pub mod observer;
+#[cfg(feature = "schema-tests")]
+pub mod schema_tests;
+#[cfg(feature = "simple")]
+pub mod simple;
+#[cfg(feature = "stream")]
+pub mod stream;
+#[cfg(feature = "sync")]
+pub mod sync;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+#[cfg(feature = "usage")]
+pub mod usage;
+#[cfg(feature = "verify")]
+mod verify;
+
+#[doc(hidden)]
+pub mod __private;
+
+pub use aggregate::aggregate_joined;
+pub use cache::ValidationCache;
+pub use case::Case;
+pub use client::ClientExt;
+pub use context::{with_context, ConversionContext};
+pub use cursor::CursorReader;
+pub use erased::{erased, ErasedFromRow, ErasedMapper};
+pub use error::{Error, FieldError, RowError};
+pub use expected::ExpectedColumnsBuilder;
+pub use iter::SliceIter;
+pub use layout::ExpectedLayout;
+pub use matching::{MatchError, MatchPolicy, MatchQuality};
+pub use paginated::Paginated;
+pub use params::ToParams;
+pub use plan::{Conversion, FieldPlan};
+pub use portal::{bind_as, PortalBatches};
+pub use postgres_from_row_derive::{assert_columns, query_as, FromRow, TextEnum, ToParams};
+pub use query::Query;
+pub use report::{
+    diff_columns, report_expected_columns_mismatch, ColumnMismatch, DisplayReport, MismatchReport,
+    ReportEntry, ReportLayout, ReportOptions,
+};
+pub use result_sets::FromResultSets;
+pub use schema::{report_statement, validate_schema, SchemaIssue, SchemaMismatch};
+pub use select::Select;
+pub use statement::{prepare_as, TypedStatement};
+pub use table::{FieldMask, Table};
+pub use text_enum::TextEnum;
 pub use tokio_postgres;
+pub use tuples::{split_row, try_split_row, Flatten};
+pub use type_names::{set_type_names, type_names, TypeNames};
+pub use typed_client::TypedClient;
+pub use via_try_from::ViaTryFrom;
 
 pub type ExpectedColumns = std::borrow::Cow<'static, [ExpectedColumn]>;
 
@@ -13,15 +103,148 @@ pub struct ExpectedColumn {
     type_name: fn() -> &'static str,
     accepts: fn(&tokio_postgres::types::Type) -> bool,
     nullable: fn(&tokio_postgres::types::Type) -> bool,
+    json: bool,
+    path: Option<FieldPath>,
+}
+
+/// The fields a column is read into, starting from the outermost struct, see [`ExpectedColumn::path`].
+///
+/// Displays like `Order::customer::email`, for the `email` column of a `customer` field flattened into `Order`.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldPath {
+    owner: fn() -> &'static str,
+    fields: [&'static str; FieldPath::MAX_DEPTH],
+    depth: usize,
+    truncated: bool,
+}
+
+impl FieldPath {
+    /// How many fields a path records, the innermost fields of deeper paths are left out.
+    pub const MAX_DEPTH: usize = 8;
+
+    /// The name of the outermost struct, as given by [`std::any::type_name`].
+    pub fn owner(&self) -> &'static str {
+        (self.owner)()
+    }
+    /// The names of the fields, from the one of the outermost struct to the one the column is read into.
+    pub fn fields(&self) -> &[&'static str] {
+        &self.fields[..self.depth]
+    }
+    /// Whether the innermost fields were left out, because the path is deeper than [`FieldPath::MAX_DEPTH`].
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl std::fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // the module of the struct is left out, but not the ones of its generic arguments
+        let owner = self.owner();
+        let (path, generics) = owner.split_at(owner.find('<').unwrap_or(owner.len()));
+        let name = path.rsplit("::").next().unwrap_or(path);
+        write!(f, "{name}{}", TypeNames::Short.apply(generics))?;
+        for field in self.fields() {
+            write!(f, "::{field}")?;
+        }
+        if self.truncated {
+            write!(f, "::..")?;
+        }
+        Ok(())
+    }
 }
 
 impl ExpectedColumn {
     pub fn column_name(&self) -> Option<&'static str> {
         self.column_name
     }
+    /// The fields this column is read into, when it is read into a derived struct.
+    ///
+    /// For columns of flattened or joined fields, the path starts at the outermost struct.
+    pub fn path(&self) -> Option<&FieldPath> {
+        self.path.as_ref()
+    }
+    /// Records that this column is read into `field` of the struct named by `owner`,
+    /// nesting the path it already had, if any, under that field.
+    pub const fn in_field(mut self, owner: fn() -> &'static str, field: &'static str) -> Self {
+        let mut path = match self.path {
+            Some(path) => path,
+            None => FieldPath {
+                owner,
+                fields: [""; FieldPath::MAX_DEPTH],
+                depth: 0,
+                truncated: false,
+            },
+        };
+        if path.depth == FieldPath::MAX_DEPTH {
+            path.depth -= 1;
+            path.truncated = true;
+        }
+        let mut i = path.depth;
+        while i > 0 {
+            path.fields[i] = path.fields[i - 1];
+            i -= 1;
+        }
+        path.fields[0] = field;
+        path.depth += 1;
+        path.owner = owner;
+        self.path = Some(path);
+        self
+    }
+    /// The name of the rust type, for `Json<T>` columns this is the name of `T`.
     pub fn type_name(&self) -> &'static str {
         (self.type_name)()
     }
+    /// The built-in postgres types this column accepts.
+    ///
+    /// Only types known to `tokio_postgres` are probed, so types created with `CREATE TYPE` are never listed.
+    pub fn accepted_types(&self) -> Vec<tokio_postgres::types::Type> {
+        // oids below this are reserved for the objects built into postgres
+        const FIRST_NORMAL_OBJECT_ID: u32 = 16384;
+        (0..FIRST_NORMAL_OBJECT_ID)
+            .filter_map(tokio_postgres::types::Type::from_oid)
+            .filter(|ty| self.accepts(ty))
+            .collect()
+    }
+    /// Describes the accepted types for reports, like `int4 or int8`.
+    pub fn describe_accepted_types(&self) -> String {
+        let types = self.accepted_types();
+        match types.as_slice() {
+            [] => "no built-in type".to_string(),
+            [ty] => ty.name().to_string(),
+            [init @ .., last] if types.len() <= 8 => {
+                let init = init.iter().map(|ty| ty.name()).collect::<Vec<_>>();
+                format!("{} or {}", init.join(", "), last.name())
+            }
+            _ => format!("one of {} built-in types", types.len()),
+        }
+    }
+    /// Whether the column is decoded from `json` or `jsonb` with `Json<T>`.
+    pub fn is_json(&self) -> bool {
+        self.json
+    }
+    /// The name of the rust type as shown in reports, `Json<T>` columns are shown as `json(T)`.
+    ///
+    /// The name is shortened unless [`TypeNames::Full`] was set, see [`set_type_names`].
+    pub fn describe_type(&self) -> std::borrow::Cow<'static, str> {
+        self.describe_type_as(type_names())
+    }
+    /// Like [`ExpectedColumn::describe_type`], with the names shown as `type_names` says.
+    pub fn describe_type_as(&self, type_names: TypeNames) -> std::borrow::Cow<'static, str> {
+        let type_name = type_names.apply(self.type_name());
+        if self.json {
+            format!("json({type_name})").into()
+        } else {
+            type_name
+        }
+    }
+    /// The type used to probe the nullability of this column when the actual type is not known.
+    pub fn probe_type(&self) -> tokio_postgres::types::Type {
+        if self.json {
+            tokio_postgres::types::Type::JSONB
+        } else {
+            tokio_postgres::types::Type::UNKNOWN
+        }
+    }
     pub fn accepts(&self, ty: &tokio_postgres::types::Type) -> bool {
         (self.accepts)(ty)
     }
@@ -31,6 +254,11 @@ impl ExpectedColumn {
     pub fn set_nullable(&mut self) {
         self.nullable = |_| true;
     }
+    /// Marks this column as nullable regardless of the type it is decoded as, see `#[from_row(nullable)]`.
+    pub const fn assume_nullable(mut self) -> Self {
+        self.nullable = |_| true;
+        self
+    }
     pub const fn new<T: for<'a> tokio_postgres::types::FromSql<'a>>(
         column_name: Option<&'static str>,
     ) -> Self {
@@ -39,104 +267,25 @@ impl ExpectedColumn {
             type_name: std::any::type_name::<T>,
             accepts: T::accepts,
             nullable: |ty| T::from_sql_null(ty).is_ok(),
+            json: false,
+            path: None,
         }
     }
-}
-
-pub fn report_expected_columns_mismatch(
-    found_cols: &[tokio_postgres::Column],
-    expected_cols: &[ExpectedColumn],
-) -> String {
-    use similar::{ChangeTag, TextDiff};
-    use std::fmt::Write;
-    let mut report = String::new();
-
-    // TODO! update this code to correctly handle absent column names
-
-    // 1. Prepare the sequences for diffing (just the names)
-    let found_names: Vec<&str> = found_cols.iter().map(|c| c.name()).collect();
-    let expected_names: Vec<&str> = expected_cols
-        .iter()
-        .map(|e| e.column_name().unwrap_or("-"))
-        .collect();
-
-    let diff = TextDiff::from_slices(&expected_names, &found_names);
-
-    writeln!(report, "Column Mismatch Report:").unwrap();
-    writeln!(report, "{:-<60}", "").unwrap();
-    writeln!(
-        report,
-        "{:1} {:<20} | {:<15} | {:<15} | {}",
-        "", "Column Name", "Type Match", "Nullable", "Notes"
-    )
-    .unwrap();
-    writeln!(report, "{:-<60}", "").unwrap();
-
-    // 2. Iterate through the diff changes
-    // TextDiff tracks the indices for us so we can pull the full objects
-    for change in diff.iter_all_changes() {
-        let tag = change.tag();
-
-        match tag {
-            ChangeTag::Equal => {
-                // Name matches! Now check if the actual type 'accepts' the expected type
-                let f_idx = change.new_index().unwrap();
-                let e_idx = change.old_index().unwrap();
-
-                let f_col = &found_cols[f_idx];
-                let e_col = &expected_cols[e_idx];
-
-                let type_matches = e_col.accepts(f_col.type_());
-                let is_nullable = e_col.nullable(f_col.type_());
-
-                let status = if type_matches { "OK" } else { "MISMATCH" };
-
-                writeln!(
-                    report,
-                    "  {:<20} | {:<15} | {:<15} | {}",
-                    f_col.name(),
-                    status,
-                    if is_nullable { "Yes" } else { "No" },
-                    if type_matches {
-                        ""
-                    } else {
-                        "Type rejected the database column"
-                    }
-                )
-                .unwrap();
-            }
-            ChangeTag::Delete => {
-                // Present in 'Expected' (Old) but missing in 'Found' (New)
-                let e_idx = change.old_index().unwrap();
-                let e_col = &expected_cols[e_idx];
-
-                writeln!(
-                    report,
-                    "- {:<20} | {:<15} | {:<15} | MISSING FROM DATABASE",
-                    e_col.column_name().unwrap_or("-"),
-                    e_col.type_name(),
-                    "---"
-                )
-                .unwrap();
-            }
-            ChangeTag::Insert => {
-                // Present in 'Found' (New) but not in 'Expected' (Old)
-                let f_idx = change.new_index().unwrap();
-                let f_col = &found_cols[f_idx];
-
-                writeln!(
-                    report,
-                    "+ {:<20} | {:<15} | {:<15} | UNEXPECTED EXTRA COLUMN",
-                    f_col.name(),
-                    f_col.type_().name(),
-                    "---"
-                )
-                .unwrap();
-            }
+    /// Like [`ExpectedColumn::new`], for a column decoded with `J`, which is `Json<T>` or `Option<Json<T>>`.
+    ///
+    /// Reports show the name of `T`, instead of the full name of the wrapper.
+    pub const fn new_json<J: for<'a> tokio_postgres::types::FromSql<'a>, T: ?Sized>(
+        column_name: Option<&'static str>,
+    ) -> Self {
+        Self {
+            column_name,
+            type_name: std::any::type_name::<T>,
+            accepts: J::accepts,
+            nullable: |ty| J::from_sql_null(ty).is_ok(),
+            json: true,
+            path: None,
         }
     }
-
-    report
 }
 
 /// A trait that allows mapping rows from [tokio-postgres](<https://docs.rs/tokio-postgres>), to other types.
@@ -144,6 +293,23 @@ pub trait FromRow: Sized {
     /// The number of columns this type will attempt to consume
     const COLUMN_COUNT: usize;
 
+    /// The names of the columns this type expects, if they are known at compile time.
+    ///
+    /// Columns without a name (such as the ones of tuples) are `None`.
+    ///
+    /// This is used by [`query_as!`] to check queries at compile time.
+    const COLUMN_NAMES: Option<&'static [Option<&'static str>]> = None;
+
+    /// The columns this type expects, if they can be known at compile time.
+    ///
+    /// When known, [`FromRow::report_expected_columns`] returns them without building them at runtime.
+    const EXPECTED_COLUMNS: Option<&'static [ExpectedColumn]> = None;
+
+    /// Whether this type may join several consecutive rows into a single value, see `#[from_row(join)]`.
+    ///
+    /// Types that don't are converted one row at a time, which allows converting rows in parallel.
+    const JOINED: bool = true;
+
     /// Try's to perform the conversion.
     ///
     /// Will return an error if the row does not contain the expected column names.
@@ -155,14 +321,15 @@ pub trait FromRow: Sized {
         last: Option<&mut Self>,
         row: &tokio_postgres::Row,
         index: usize,
-    ) -> Result<Option<Self>, tokio_postgres::Error>;
+    ) -> Result<Option<Self>, Error>;
 
     fn report_expected_columns() -> ExpectedColumns;
     fn try_assert_matches(columns: &[tokio_postgres::Column]) -> Result<(), ()>;
 
     /// Verifies that the column names and count match what is expected, panics on error
     ///
-    /// The panic message is a detailed description of what is missing
+    /// The panic message is a detailed description of what is missing, the same report can be
+    /// inspected without panicking with [`report_expected_columns_mismatch`]
     ///
     /// Do not override this implementation, instead implement report_expected_columns and try_assert_matches
     ///
@@ -171,35 +338,272 @@ pub trait FromRow: Sized {
     /// This makes it possible to make queries where multiple columns have the same name, and still use the result with a flattened FromRow struct that matches the different column names
     fn assert_matches(columns: &[tokio_postgres::Column]) {
         if Self::try_assert_matches(columns).is_err() {
-            std::panic::panic_any(report_expected_columns_mismatch(
-                columns,
-                &Self::report_expected_columns(),
-            ))
+            let expected = Self::report_expected_columns();
+            let report = report_expected_columns_mismatch(columns, &expected);
+            trace::mismatch::<Self>(&report);
+            panic!("{report}")
+        }
+    }
+
+    /// Checks that the columns match what is expected like [`FromRow::try_assert_matches`],
+    /// returning the first column that does not match on error.
+    ///
+    /// ```ignore
+    /// if let Err(error) = User::check_matches(statement.columns()) {
+    ///     log::warn!("`users` changed: {error}");
+    /// }
+    /// ```
+    ///
+    /// The columns are only compared again when they don't match.
+    fn check_matches(columns: &[tokio_postgres::Column]) -> Result<(), MatchError> {
+        if Self::try_assert_matches(columns).is_ok() {
+            return Ok(());
+        }
+        let expected = Self::report_expected_columns();
+        Err(MatchError::find(&expected, columns))
+    }
+
+    /// Verifies that the columns match what is expected, as strictly as `policy` requires, panics on error
+    ///
+    /// With the default policy, this is the same as [`FromRow::assert_matches`].
+    ///
+    /// The report of the panic compares the columns with the expected names as they are written in the type.
+    fn assert_matches_with(columns: &[tokio_postgres::Column], policy: &MatchPolicy) {
+        if Self::try_assert_matches_with(columns, policy).is_err() {
+            let expected = Self::report_expected_columns();
+            let report = report_expected_columns_mismatch(columns, &expected);
+            trace::mismatch::<Self>(&report);
+            panic!("{report}")
+        }
+    }
+
+    /// Checks that the columns match what is expected, as strictly as `policy` requires,
+    /// returning the first column that does not match on error.
+    ///
+    /// Use [`FromRow::assert_matches_with`] to get a detailed description of what is missing.
+    fn try_assert_matches_with(
+        columns: &[tokio_postgres::Column],
+        policy: &MatchPolicy,
+    ) -> Result<(), MatchError> {
+        if *policy == MatchPolicy::STRICT {
+            return Self::check_matches(columns);
+        }
+        let expected = Self::report_expected_columns();
+        matching::check_with_policy(&expected, columns, policy)
+    }
+
+    /// Verifies that the expected columns appear in order within `columns`, which may have other columns
+    /// before, between and after them, panics on error
+    ///
+    /// Allows reusing a type for wider queries, like `SELECT *`, see [`FromRow::try_from_row_subset`].
+    fn assert_matches_subset(columns: &[tokio_postgres::Column]) {
+        Self::assert_matches_with(columns, &MatchPolicy::SUBSET)
+    }
+
+    /// Checks that the expected columns appear in order within `columns`, which may have other columns
+    /// before, between and after them, returning the first expected column that was not found on error.
+    ///
+    /// Use [`FromRow::assert_matches_subset`] to get a detailed description of what is missing.
+    fn try_assert_matches_subset(columns: &[tokio_postgres::Column]) -> Result<(), MatchError> {
+        Self::try_assert_matches_with(columns, &MatchPolicy::SUBSET)
+    }
+
+    /// Determines how well `columns` match what is expected, without panicking.
+    ///
+    /// Useful to pick which of several candidate types to decode the result of a dynamic query into.
+    fn matches(columns: &[tokio_postgres::Column]) -> MatchQuality {
+        if Self::try_assert_matches(columns).is_ok() {
+            MatchQuality::Exact
+        } else if columns.len() > Self::COLUMN_COUNT
+            && Self::try_assert_matches(&columns[..Self::COLUMN_COUNT]).is_ok()
+        {
+            MatchQuality::Prefix
+        } else if matching::matches_by_name_subset(&Self::report_expected_columns(), columns) {
+            MatchQuality::ByNameSubset
+        } else {
+            MatchQuality::NoMatch
         }
     }
 
+    /// Describes which column each field would be read from, and how, without reading any row.
+    ///
+    /// Useful for debugging queries whose columns are not in the order the type expects.
+    ///
+    /// The default implementation describes each of the expected columns as a direct conversion.
+    fn dry_run(columns: &[tokio_postgres::Column]) -> Vec<FieldPlan> {
+        Self::report_expected_columns()
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                FieldPlan::column(None, columns, i, column.column_name(), Conversion::Direct)
+            })
+            .collect()
+    }
+
+    /// The number of rows this value was built from, not counting rows that were null.
+    ///
+    /// This is `1` unless the type joins several rows into one value, see `#[from_row(join)]`.
+    ///
+    /// With the `verify` feature, the values converted from a slice of rows are checked to account for every row.
+    fn row_span(&self) -> usize {
+        1
+    }
+
+    /// Verifies that the columns of a prepared statement match what is expected, panics on error
+    ///
+    /// This allows the check to happen once when the statement is prepared,
+    /// instead of every time the statement is executed.
+    fn assert_matches_statement(statement: &tokio_postgres::Statement) {
+        Self::assert_matches(statement.columns())
+    }
+
+    /// Checks that the columns of a prepared statement match what is expected,
+    /// returning the first column that does not match on error.
+    ///
+    /// Use [`FromRow::assert_matches_statement`] to get a detailed description of what is missing.
+    fn try_assert_matches_statement(
+        statement: &tokio_postgres::Statement,
+    ) -> Result<(), MatchError> {
+        Self::check_matches(statement.columns())
+    }
+
     /// Perform the conversion.
     ///
     /// # Panics
     ///
     /// Panics if the row does not contain the expected column names.
     fn from_row(row: impl AsRow) -> Self {
-        let row = row.as_row();
-        Self::assert_matches(row.columns());
+        // `try_from_row` verifies the columns, panicking with the report when they don't match
         Self::try_from_row(row).expect("could not convert column")
     }
 
     /// Try's to perform the conversion.
     ///
     /// Will return an error if the row does not contain the expected column names.
-    fn try_from_row(row: impl AsRow) -> Result<Self, tokio_postgres::Error> {
+    fn try_from_row(row: impl AsRow) -> Result<Self, Error> {
         let row = row.as_row();
-        Self::assert_matches(row.columns());
-        Self::try_from_row_joined(None, row, 0).map(|x| {
-            x.expect(
-                "when try_from_row_joined is called with last = None it should never return None",
-            )
-        })
+        let span = trace::ConversionSpan::enter::<Self>("try_from_row", 1);
+        let result = panic_on_mismatch(Self::from_row_checked(row));
+        span.finish(&result, |_| 1);
+        result
+    }
+
+    /// Try's to perform the conversion starting at the column `offset` instead of the first one,
+    /// returning the value and the offset of the first column after the ones it read.
+    ///
+    /// Allows decoding several types from a single row by hand:
+    ///
+    /// ```ignore
+    /// let (todo, next) = Todo::try_from_row_at(&row, 0)?;
+    /// let (user, _) = User::try_from_row_at(&row, next)?;
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the columns starting at `offset` do not contain the expected column names.
+    fn try_from_row_at(row: impl AsRow, offset: usize) -> Result<(Self, usize), Error> {
+        panic_on_mismatch(Self::from_row_at_checked(row, offset))
+    }
+
+    /// Try's to perform the conversion by looking up each column by its name instead of its index.
+    ///
+    /// The row only needs to contain the columns this type uses, in any order.
+    ///
+    /// Types that don't know the names of their columns (like tuples) perform the conversion by index.
+    fn try_from_row_by_name(row: impl AsRow) -> Result<Self, Error> {
+        Self::try_from_row(row)
+    }
+
+    /// Perform the conversion of a row with more columns than this type expects, like the ones of `SELECT *`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the expected columns don't appear in order within the columns of the row.
+    fn from_row_subset(row: impl AsRow) -> Self {
+        Self::try_from_row_subset(row).expect("could not convert column")
+    }
+
+    /// Try's to perform the conversion of a row with more columns than this type expects, like the ones of `SELECT *`.
+    ///
+    /// The expected columns must appear in order within the columns of the row, see [`FromRow::assert_matches_subset`],
+    /// and are then read by name, with [`FromRow::try_from_row_by_name`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the expected columns don't appear in order within the columns of the row.
+    fn try_from_row_subset(row: impl AsRow) -> Result<Self, Error> {
+        Self::try_from_row_with_policy(row, &MatchPolicy::SUBSET)
+    }
+
+    /// Try's to perform the conversion of a row whose columns are named differently than this type expects.
+    ///
+    /// `aliases` maps the name of a column of the row to the name this type expects for it, and is applied
+    /// before the names are verified, so one struct can be reused by queries whose aliases differ slightly:
+    ///
+    /// ```ignore
+    /// let aliases = HashMap::from([("author_id", "user_id")]);
+    /// let todo = Todo::try_from_row_with_aliases(&row, &aliases)?;
+    /// ```
+    ///
+    /// The columns are still read by index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not contain the expected column names, once aliased.
+    fn try_from_row_with_aliases(
+        row: impl AsRow,
+        aliases: &std::collections::HashMap<&str, &str>,
+    ) -> Result<Self, Error> {
+        let row = row.as_row();
+        let expected = Self::report_expected_columns();
+        if !matching::matches_with_aliases(&expected, row.columns(), aliases) {
+            let aliased: Vec<_> = row
+                .columns()
+                .iter()
+                .map(|c| {
+                    (
+                        aliases.get(c.name()).copied().unwrap_or(c.name()),
+                        c.type_(),
+                    )
+                })
+                .collect();
+            let report = report::report_named_columns_mismatch(&aliased, &expected);
+            trace::mismatch::<Self>(&report);
+            panic!("{report}")
+        }
+        Self::try_from_row_unchecked(row)
+    }
+
+    /// Try's to perform the conversion, verifying the columns with [`FromRow::assert_matches_with`].
+    ///
+    /// ```ignore
+    /// let policy = MatchPolicy { case: Case::Snake, ..MatchPolicy::default() };
+    /// let user = CamelUser::try_from_row_with_policy(&row, &policy)?;
+    /// ```
+    ///
+    /// The columns are read by index when the policy requires them to be in order without extra columns,
+    /// and by name with [`FromRow::try_from_row_by_name`] otherwise, in which case the names of the columns
+    /// must be the expected ones, regardless of [`MatchPolicy::case`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not contain the expected column names, as strictly as `policy` requires.
+    fn try_from_row_with_policy(row: impl AsRow, policy: &MatchPolicy) -> Result<Self, Error> {
+        panic_on_mismatch(Self::from_row_with_policy_checked(row, policy))
+    }
+
+    /// Try's to perform the conversion on a slice of rows, verifying the columns with [`FromRow::assert_matches_with`].
+    ///
+    /// The rows are read like [`FromRow::try_from_row_with_policy`], and are only joined when they are read by index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not contain the expected column names, as strictly as `policy` requires.
+    fn try_from_slice_with_policy(
+        rows: &[tokio_postgres::Row],
+        policy: &MatchPolicy,
+    ) -> Result<Vec<Self>, Error> {
+        panic_on_mismatch(Self::from_slice_with_policy_checked(rows, policy))
     }
 
     /// Perform the conversion on a slice of rows.
@@ -208,54 +612,335 @@ pub trait FromRow: Sized {
     ///
     /// Panics if the row does not contain the expected column names.
     fn from_slice(rows: &[tokio_postgres::Row]) -> Vec<Self> {
+        Self::try_from_slice(rows).expect("could not convert column")
+    }
+
+    /// Try's to perform the conversion on a slice of rows.
+    ///
+    /// Will return an error if the row does not contain the expected column names.
+    fn try_from_slice(rows: &[tokio_postgres::Row]) -> Result<Vec<Self>, Error> {
+        let span = trace::ConversionSpan::enter::<Self>("try_from_slice", rows.len());
+        let result = panic_on_mismatch(Self::from_slice_checked(rows));
+        span.finish(&result, Vec::len);
+        result
+    }
+
+    /// Perform the conversion on a slice of rows in parallel, using rayon.
+    ///
+    /// Types that join rows (see [`FromRow::JOINED`]) are converted sequentially instead.
+    /// The user context of [`with_context`] is not available to the conversions running on other threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not contain the expected column names.
+    #[cfg(feature = "rayon")]
+    fn from_slice_par(rows: &[tokio_postgres::Row]) -> Vec<Self>
+    where
+        Self: Send,
+    {
+        Self::try_from_slice_par(rows).expect("could not convert column")
+    }
+
+    /// Try's to perform the conversion on a slice of rows in parallel, using rayon.
+    ///
+    /// Types that join rows (see [`FromRow::JOINED`]) are converted sequentially instead.
+    /// The user context of [`with_context`] is not available to the conversions running on other threads.
+    ///
+    /// Will return an error if the row does not contain the expected column names.
+    #[cfg(feature = "rayon")]
+    fn try_from_slice_par(rows: &[tokio_postgres::Row]) -> Result<Vec<Self>, Error>
+    where
+        Self: Send,
+    {
+        use rayon::prelude::*;
+
+        if Self::JOINED {
+            return Self::try_from_slice(rows);
+        }
         let [first, ..] = rows else {
-            return Vec::new();
+            return Ok(Vec::new());
         };
         Self::assert_matches(first.columns());
+        rows.par_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let _row_index = context::RowIndexGuard::new(i);
+                Self::try_from_row_unchecked(row)
+                    .map_err(|error| RowError::wrap(error, i, Some(row)))
+            })
+            .collect()
+    }
+
+    /// Lazily converts a slice of rows, joining them like [`FromRow::try_from_slice`].
+    ///
+    /// Allows processing the values as they are converted, or stopping early, without collecting them all first.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the first value is requested if the row does not contain the expected column names.
+    fn iter_from_slice(rows: &[tokio_postgres::Row]) -> SliceIter<'_, Self> {
+        SliceIter::new(rows)
+    }
+
+    /// Try's to perform the conversion without verifying the columns of the row.
+    ///
+    /// Meant for hot loops where the caller controls the sql and already validated the columns once,
+    /// for example with [`FromRow::assert_matches_statement`].
+    ///
+    /// Columns are read by index, so if they don't match, the values may end up in the wrong fields.
+    fn try_from_row_unchecked(row: impl AsRow) -> Result<Self, Error> {
+        Self::try_from_row_joined(None, row.as_row(), 0).map(|x| {
+            x.expect(
+                "when try_from_row_joined is called with last = None it should never return None",
+            )
+        })
+    }
+
+    /// Perform the conversion on a slice of rows without verifying their columns.
+    ///
+    /// See [`FromRow::try_from_row_unchecked`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a column could not be converted.
+    fn from_slice_unchecked(rows: &[tokio_postgres::Row]) -> Vec<Self> {
+        #[cfg(feature = "verify")]
+        let verification = verify::Verification::start(rows.len());
         let mut vec = Vec::with_capacity(rows.len());
-        for row in rows {
-            if let Some(this) =
-                Self::try_from_row_joined(vec.last_mut(), row, 0).expect("could not convert column")
+        for (i, row) in rows.iter().enumerate() {
+            let _row_index = context::RowIndexGuard::new(i);
+            if let Some(this) = Self::try_from_row_joined(vec.last_mut(), row, 0)
+                .map_err(|error| RowError::wrap(error, i, Some(row)))
+                .expect("could not convert column")
             {
                 vec.push(this);
             }
         }
+        #[cfg(feature = "verify")]
+        verification.finish(&vec);
         vec
     }
 
-    /// Try's to perform the conversion on a slice of rows.
+    /// Try's to perform the conversion on a slice of rows without verifying their columns.
     ///
-    /// Will return an error if the row does not contain the expected column names.
-    fn try_from_slice(rows: &[tokio_postgres::Row]) -> Result<Vec<Self>, tokio_postgres::Error> {
-        let [first, ..] = rows else {
-            return Ok(Vec::new());
-        };
-        Self::assert_matches(first.columns());
+    /// See [`FromRow::try_from_row_unchecked`].
+    fn try_from_slice_unchecked(rows: &[tokio_postgres::Row]) -> Result<Vec<Self>, Error> {
+        #[cfg(feature = "verify")]
+        let verification = verify::Verification::start(rows.len());
         let mut vec = Vec::with_capacity(rows.len());
-        for row in rows {
-            if let Some(this) = Self::try_from_row_joined(vec.last_mut(), row, 0)? {
+        for (i, row) in rows.iter().enumerate() {
+            let _row_index = context::RowIndexGuard::new(i);
+            if let Some(this) = Self::try_from_row_joined(vec.last_mut(), row, 0)
+                .map_err(|error| RowError::wrap(error, i, Some(row)))?
+            {
                 vec.push(this);
             }
         }
+        #[cfg(feature = "verify")]
+        verification.finish(&vec);
         Ok(vec)
     }
 
-    /// Perform the conversion on a slice of rows.
+    /// Perform the conversion on a slice of rows, validating against `columns` even if there are no rows.
+    ///
+    /// The rows are expected to have been produced by a statement with these columns,
+    /// such as the ones from [`Statement::columns`](tokio_postgres::Statement::columns).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `columns` does not contain the expected column names.
+    fn from_slice_with_columns(
+        columns: &[tokio_postgres::Column],
+        rows: &[tokio_postgres::Row],
+    ) -> Vec<Self> {
+        Self::try_from_slice_with_columns(columns, rows).expect("could not convert column")
+    }
+
+    /// Try's to perform the conversion on a slice of rows, validating against `columns` even if there are no rows.
+    ///
+    /// The rows are expected to have been produced by a statement with these columns,
+    /// such as the ones from [`Statement::columns`](tokio_postgres::Statement::columns).
+    ///
+    /// Will return an error if the row does not contain the expected column names.
+    fn try_from_slice_with_columns(
+        columns: &[tokio_postgres::Column],
+        rows: &[tokio_postgres::Row],
+    ) -> Result<Vec<Self>, Error> {
+        panic_on_mismatch(Self::from_slice_with_columns_checked(columns, rows))
+    }
+
+    /// Perform the conversion on a vector of rows, consuming it.
+    ///
+    /// Each row is dropped as soon as it has been converted,
+    /// so the rows and the converted values are never all alive at the same time.
     ///
     /// # Panics
     ///
     /// Panics if the row does not contain the expected column names.
     fn from_rows(rows: Vec<tokio_postgres::Row>) -> Vec<Self> {
-        Self::from_slice(&rows)
-        // vec_map::VecMapEx::map(rows, Self::from_row)
+        Self::try_from_rows(rows).expect("could not convert column")
     }
 
-    /// Try's to perform the conversion on a slice of rows.
+    /// Try's to perform the conversion on a vector of rows, consuming it.
+    ///
+    /// Each row is dropped as soon as it has been converted,
+    /// so the rows and the converted values are never all alive at the same time.
     ///
     /// Will return an error if the row does not contain the expected column names.
-    fn try_from_rows(rows: Vec<tokio_postgres::Row>) -> Result<Vec<Self>, tokio_postgres::Error> {
-        Self::try_from_slice(&rows)
-        // vec_map::VecMapEx::try_map(rows, Self::try_from_row)
+    fn try_from_rows(rows: Vec<tokio_postgres::Row>) -> Result<Vec<Self>, Error> {
+        let span = trace::ConversionSpan::enter::<Self>("try_from_rows", rows.len());
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+        let result = panic_on_mismatch(Self::from_rows_checked(rows));
+        span.finish(&result, Vec::len);
+        result
+    }
+
+    /// Verifies that the column names and count match what is expected, like [`FromRow::assert_matches`],
+    /// returning the report as an [`Error::Mismatch`] instead of panicking.
+    fn assert_matches_checked(columns: &[tokio_postgres::Column]) -> Result<(), Error> {
+        if Self::try_assert_matches(columns).is_ok() {
+            return Ok(());
+        }
+        let expected = Self::report_expected_columns();
+        let report = report_expected_columns_mismatch(columns, &expected);
+        trace::mismatch::<Self>(&report);
+        Err(Error::Mismatch(Box::new(report)))
+    }
+
+    /// Verifies that the columns match what is expected, as strictly as `policy` requires,
+    /// like [`FromRow::assert_matches_with`], returning the report as an [`Error::Mismatch`] instead of panicking.
+    fn assert_matches_with_checked(
+        columns: &[tokio_postgres::Column],
+        policy: &MatchPolicy,
+    ) -> Result<(), Error> {
+        if Self::try_assert_matches_with(columns, policy).is_ok() {
+            return Ok(());
+        }
+        let expected = Self::report_expected_columns();
+        let report = report_expected_columns_mismatch(columns, &expected);
+        trace::mismatch::<Self>(&report);
+        Err(Error::Mismatch(Box::new(report)))
+    }
+
+    /// Try's to perform the conversion like [`FromRow::try_from_row`], without panicking.
+    ///
+    /// Returns an [`Error::Mismatch`] with the report if the row does not contain the expected column names,
+    /// for servers that must not panic on a query that changed:
+    ///
+    /// ```ignore
+    /// match User::from_row_checked(&row) {
+    ///     Ok(user) => Ok(user),
+    ///     Err(postgres_from_row::Error::Mismatch(report)) => Err(ServerError::Internal(report.to_string())),
+    ///     Err(error) => Err(error.into()),
+    /// }
+    /// ```
+    fn from_row_checked(row: impl AsRow) -> Result<Self, Error> {
+        let row = row.as_row();
+        Self::assert_matches_checked(row.columns())?;
+        Self::try_from_row_unchecked(row)
+    }
+
+    /// Try's to perform the conversion starting at the column `offset`, like [`FromRow::try_from_row_at`],
+    /// returning an [`Error::Mismatch`] instead of panicking.
+    fn from_row_at_checked(row: impl AsRow, offset: usize) -> Result<(Self, usize), Error> {
+        let row = row.as_row();
+        let columns = row.columns();
+        let end = (offset + Self::COLUMN_COUNT).min(columns.len());
+        Self::assert_matches_checked(&columns[offset.min(end)..end])?;
+        let this = Self::try_from_row_joined(None, row, offset)?.expect(
+            "when try_from_row_joined is called with last = None it should never return None",
+        );
+        Ok((this, offset + Self::COLUMN_COUNT))
+    }
+
+    /// Try's to perform the conversion like [`FromRow::try_from_row_with_policy`],
+    /// returning an [`Error::Mismatch`] instead of panicking.
+    fn from_row_with_policy_checked(row: impl AsRow, policy: &MatchPolicy) -> Result<Self, Error> {
+        let row = row.as_row();
+        Self::assert_matches_with_checked(row.columns(), policy)?;
+        if policy.reads_by_index() {
+            Self::try_from_row_unchecked(row)
+        } else {
+            Self::try_from_row_by_name(row)
+        }
+    }
+
+    /// Try's to perform the conversion on a slice of rows like [`FromRow::try_from_slice`],
+    /// returning an [`Error::Mismatch`] instead of panicking.
+    fn from_slice_checked(rows: &[tokio_postgres::Row]) -> Result<Vec<Self>, Error> {
+        let [first, ..] = rows else {
+            return Ok(Vec::new());
+        };
+        Self::assert_matches_checked(first.columns())?;
+        Self::try_from_slice_unchecked(rows)
+    }
+
+    /// Try's to perform the conversion on a slice of rows like [`FromRow::try_from_slice_with_policy`],
+    /// returning an [`Error::Mismatch`] instead of panicking.
+    fn from_slice_with_policy_checked(
+        rows: &[tokio_postgres::Row],
+        policy: &MatchPolicy,
+    ) -> Result<Vec<Self>, Error> {
+        let [first, ..] = rows else {
+            return Ok(Vec::new());
+        };
+        Self::assert_matches_with_checked(first.columns(), policy)?;
+        if policy.reads_by_index() {
+            Self::try_from_slice_unchecked(rows)
+        } else {
+            rows.iter()
+                .enumerate()
+                .map(|(i, row)| {
+                    let _row_index = context::RowIndexGuard::new(i);
+                    Self::try_from_row_by_name(row)
+                        .map_err(|error| RowError::wrap(error, i, Some(row)))
+                })
+                .collect()
+        }
+    }
+
+    /// Try's to perform the conversion on a slice of rows, validating against `columns` even if there are no rows,
+    /// like [`FromRow::try_from_slice_with_columns`], returning an [`Error::Mismatch`] instead of panicking.
+    fn from_slice_with_columns_checked(
+        columns: &[tokio_postgres::Column],
+        rows: &[tokio_postgres::Row],
+    ) -> Result<Vec<Self>, Error> {
+        Self::assert_matches_checked(columns)?;
+        Self::try_from_slice_unchecked(rows)
+    }
+
+    /// Try's to perform the conversion on a vector of rows, consuming it, like [`FromRow::try_from_rows`],
+    /// returning an [`Error::Mismatch`] instead of panicking.
+    fn from_rows_checked(rows: Vec<tokio_postgres::Row>) -> Result<Vec<Self>, Error> {
+        let Some(first) = rows.first() else {
+            return Ok(Vec::new());
+        };
+        Self::assert_matches_checked(first.columns())?;
+        #[cfg(feature = "verify")]
+        let verification = verify::Verification::start(rows.len());
+        let mut vec = Vec::with_capacity(rows.len());
+        for (i, row) in rows.into_iter().enumerate() {
+            let _row_index = context::RowIndexGuard::new(i);
+            if let Some(this) = Self::try_from_row_joined(vec.last_mut(), &row, 0)
+                .map_err(|error| RowError::wrap(error, i, Some(&row)))?
+            {
+                vec.push(this);
+            }
+        }
+        #[cfg(feature = "verify")]
+        verification.finish(&vec);
+        Ok(vec)
+    }
+}
+
+/// Panics with the report of an [`Error::Mismatch`], for the conversions that panic when the columns don't match,
+/// which are built on the `*_checked` ones.
+fn panic_on_mismatch<T>(result: Result<T, Error>) -> Result<T, Error> {
+    match result {
+        Err(Error::Mismatch(report)) => panic!("{report}"),
+        result => result,
     }
 }
 
@@ -274,24 +959,46 @@ impl<T: AsRow> AsRow for &T {
     }
 }
 
+/// `None` when all the columns of `T` are null, as they are for a `LEFT JOIN` without a match.
+///
+/// When only some of the columns are null, `T` is decoded from them, so its own `Option` fields can be `None`
+/// and its other fields fail with the null error.
 impl<T: FromRow> FromRow for Option<T> {
     const COLUMN_COUNT: usize = T::COLUMN_COUNT;
+    const COLUMN_NAMES: Option<&'static [Option<&'static str>]> = T::COLUMN_NAMES;
+    const JOINED: bool = T::JOINED;
     fn try_from_row_joined(
         mut last: Option<&mut Self>,
         row: &tokio_postgres::Row,
         index: usize,
-    ) -> Result<Option<Self>, tokio_postgres::Error> {
-        let this: Self = match T::try_from_row_joined(
-            last.as_deref_mut().and_then(|l| l.as_mut()),
-            row,
-            index,
-        ) {
-            Ok(None) => return Ok(None),
-            Ok(Some(row)) => Some(row),
-            Err(e) if is_was_null(&e) => None,
-            Err(error) => return Err(error),
-        };
-        Ok(Some(this))
+    ) -> Result<Option<Self>, Error> {
+        if T::COLUMN_COUNT != 0 && __private::columns_are_null(row, index, T::COLUMN_COUNT)? {
+            __private::null_row();
+            return Ok(Some(None));
+        }
+        let this =
+            T::try_from_row_joined(last.as_deref_mut().and_then(|l| l.as_mut()), row, index)?;
+        Ok(this.map(Some))
+    }
+    /// Besides being `None` when the columns are null, this is also `None` when none of the columns of `T`
+    /// are present in the row, so the same type can be used for queries that don't select `T` at all.
+    fn try_from_row_by_name(row: impl AsRow) -> Result<Self, Error> {
+        let row = row.as_row();
+        let expected = T::report_expected_columns();
+        let absent = !expected.is_empty()
+            && expected.iter().all(|column| {
+                column
+                    .column_name()
+                    .is_some_and(|name| row.columns().iter().all(|c| c.name() != name))
+            });
+        let named = expected.iter().any(|column| column.column_name().is_some());
+        if absent || named && __private::named_columns_are_null(row, &expected)? {
+            return Ok(None);
+        }
+        T::try_from_row_by_name(row).map(Some)
+    }
+    fn row_span(&self) -> usize {
+        self.as_ref().map_or(0, T::row_span)
     }
     fn report_expected_columns() -> ExpectedColumns {
         let mut columns = T::report_expected_columns().into_owned();
@@ -300,38 +1007,69 @@ impl<T: FromRow> FromRow for Option<T> {
         }
         columns.into()
     }
+    fn dry_run(columns: &[tokio_postgres::Column]) -> Vec<FieldPlan> {
+        T::dry_run(columns)
+    }
     fn try_assert_matches(columns: &[tokio_postgres::Column]) -> Result<(), ()> {
         T::try_assert_matches(columns)
     }
 }
 
+/// Empty when all the columns of `T` are null, as they are for a `LEFT JOIN` without a match,
+/// and the rows that follow are joined into the last element of the `Vec`, or pushed as new elements.
+///
+/// When only some of the columns are null, `T` is decoded from them, like the `FromRow` implementation of `Option<T>`.
+/// If `T` has no known column count, the first null error decoding it is taken as the whole row being null instead.
 impl<T: FromRow> FromRow for Vec<T> {
     const COLUMN_COUNT: usize = T::COLUMN_COUNT;
+    const COLUMN_NAMES: Option<&'static [Option<&'static str>]> = T::COLUMN_NAMES;
+    const JOINED: bool = true;
     fn assert_matches(column: &[tokio_postgres::Column]) {
         T::assert_matches(column);
     }
     fn try_from_row_joined(
-        last: Option<&mut Self>,
+        mut last: Option<&mut Self>,
         row: &tokio_postgres::Row,
         index: usize,
-    ) -> Result<Option<Self>, tokio_postgres::Error> {
-        let Some(vec) = last else {
-            match T::try_from_row_joined(None, row, index) {
-                Ok(option) => return Ok(Some(vec![option.expect("when try_from_row_joined is called with last = None it should never return None")])),
-                Err(e) if is_was_null(&e) => return Ok(Some(Vec::new())),
-                Err(e) => return Err(e),
+    ) -> Result<Option<Self>, Error> {
+        if T::COLUMN_COUNT != 0 && __private::columns_are_null(row, index, T::COLUMN_COUNT)? {
+            __private::null_row();
+            return Ok(last.is_none().then(Vec::new));
+        }
+        let last_item = last.as_deref_mut().and_then(|vec| vec.last_mut());
+        let item = match T::try_from_row_joined(last_item, row, index) {
+            Err(e) if T::COLUMN_COUNT == 0 && e.is_null() => {
+                __private::null_row();
+                return Ok(last.is_none().then(Vec::new));
             }
+            result => result?,
         };
-        if let Some(item) = T::try_from_row_joined(vec.last_mut(), row, index).or_else(|e| {
-            if is_was_null(&e) {
+        match last {
+            Some(vec) => {
+                vec.extend(item);
                 Ok(None)
-            } else {
-                Err(e)
             }
-        })? {
-            vec.push(item);
+            None => Ok(Some(vec![item.expect(
+                "when try_from_row_joined is called with last = None it should never return None",
+            )])),
         }
-        Ok(None)
+    }
+    /// Empty when the named columns of `T` are all null.
+    fn try_from_row_by_name(row: impl AsRow) -> Result<Self, Error> {
+        let row = row.as_row();
+        let expected = T::report_expected_columns();
+        let named = expected.iter().any(|column| column.column_name().is_some());
+        if named && __private::named_columns_are_null(row, &expected)? {
+            return Ok(Vec::new());
+        }
+        match T::try_from_row_by_name(row) {
+            Ok(this) => Ok(vec![this]),
+            Err(e) if !named && e.is_null() => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+    fn row_span(&self) -> usize {
+        self.iter().map(T::row_span).sum()
     }
     fn report_expected_columns() -> ExpectedColumns {
         let mut columns = T::report_expected_columns().into_owned();
@@ -340,12 +1078,57 @@ impl<T: FromRow> FromRow for Vec<T> {
         }
         columns.into()
     }
+    fn dry_run(columns: &[tokio_postgres::Column]) -> Vec<FieldPlan> {
+        T::dry_run(columns)
+    }
     fn try_assert_matches(columns: &[tokio_postgres::Column]) -> Result<(), ()> {
         T::try_assert_matches(columns)
     }
 }
 
-fn is_was_null(e: &tokio_postgres::Error) -> bool {
-    std::error::Error::source(&e)
-        .is_some_and(|x| x.downcast_ref::<tokio_postgres::types::WasNull>().is_some())
+/// Captures the errors of decoding `T` instead of failing the whole row,
+/// so a corrupt nested entity can be reported to the caller without losing its siblings.
+///
+/// A null `T` is not captured, so that `Option<Result<T, E>>` and `Vec<Result<T, E>>` still work as expected.
+///
+/// When joining, the rows following an `Err` are treated as the start of a new entity.
+impl<T: FromRow, E: From<Error>> FromRow for Result<T, E> {
+    const COLUMN_COUNT: usize = T::COLUMN_COUNT;
+    const COLUMN_NAMES: Option<&'static [Option<&'static str>]> = T::COLUMN_NAMES;
+    const EXPECTED_COLUMNS: Option<&'static [ExpectedColumn]> = T::EXPECTED_COLUMNS;
+    const JOINED: bool = T::JOINED;
+    fn try_from_row_joined(
+        last: Option<&mut Self>,
+        row: &tokio_postgres::Row,
+        index: usize,
+    ) -> Result<Option<Self>, Error> {
+        let last = match last {
+            Some(Ok(last)) => Some(last),
+            Some(Err(_)) | None => None,
+        };
+        match T::try_from_row_joined(last, row, index) {
+            Ok(this) => Ok(this.map(Ok)),
+            Err(e) if e.is_null() => Err(e),
+            Err(e) => Ok(Some(Err(e.into()))),
+        }
+    }
+    fn try_from_row_by_name(row: impl AsRow) -> Result<Self, Error> {
+        match T::try_from_row_by_name(row) {
+            Ok(this) => Ok(Ok(this)),
+            Err(e) if e.is_null() => Err(e),
+            Err(e) => Ok(Err(e.into())),
+        }
+    }
+    fn row_span(&self) -> usize {
+        self.as_ref().map_or(1, T::row_span)
+    }
+    fn report_expected_columns() -> ExpectedColumns {
+        T::report_expected_columns()
+    }
+    fn dry_run(columns: &[tokio_postgres::Column]) -> Vec<FieldPlan> {
+        T::dry_run(columns)
+    }
+    fn try_assert_matches(columns: &[tokio_postgres::Column]) -> Result<(), ()> {
+        T::try_assert_matches(columns)
+    }
 }
@@ -0,0 +1,86 @@
+//! Declarative macros for the types that can't use the derive.
+
+/// Implements [`FromRow`](crate::FromRow) for structs whose fields are each read from a column named after them,
+/// for the types that can't use the derive, like the ones of builds where proc macros are unavailable:
+///
+/// ```ignore
+/// pub struct User {
+///     pub user_id: i32,
+///     pub username: Option<String>,
+/// }
+///
+/// postgres_from_row::impl_from_row!(User { user_id: i32, username: Option<String> });
+/// ```
+///
+/// Like the derive, the columns are read by index and verified by name, errors are reported with the field they
+/// happened in, and the columns are known at compile time, so the type can be flattened into derived structs and
+/// checked by [`query_as!`](crate::query_as). Several structs can be given at once.
+#[macro_export]
+macro_rules! impl_from_row {
+    ($($ty:path { $($field:ident: $field_ty:ty),* $(,)? })*) => {
+        $(
+            impl $crate::FromRow for $ty {
+                const COLUMN_COUNT: usize = <[&str]>::len(&[$(::std::stringify!($field)),*]);
+                const COLUMN_NAMES: ::std::option::Option<&'static [::std::option::Option<&'static str>]> =
+                    ::std::option::Option::Some(&[$(::std::option::Option::Some(::std::stringify!($field))),*]);
+                const EXPECTED_COLUMNS: ::std::option::Option<&'static [$crate::ExpectedColumn]> =
+                    ::std::option::Option::Some(&[$(
+                        $crate::ExpectedColumn::new::<$field_ty>(::std::option::Option::Some(::std::stringify!($field)))
+                            .in_field(::std::any::type_name::<$ty>, ::std::stringify!($field))
+                    ),*]);
+                const JOINED: bool = false;
+
+                #[allow(unused_mut, unused_assignments)]
+                fn try_from_row_joined(
+                    _: ::std::option::Option<&mut Self>,
+                    row: &$crate::tokio_postgres::Row,
+                    mut index: usize,
+                ) -> ::std::result::Result<::std::option::Option<Self>, $crate::Error> {
+                    $(
+                        let column = index;
+                        index += 1;
+                        let $field = $crate::tokio_postgres::Row::try_get::<_, $field_ty>(row, column).map_err(|error| {
+                            $crate::__private::field_error(
+                                error.into(),
+                                ::std::any::type_name::<Self>(),
+                                ::std::stringify!($field),
+                                ::std::option::Option::Some(row.columns()[column].name()),
+                            )
+                        })?;
+                    )*
+                    ::std::result::Result::Ok(::std::option::Option::Some(Self { $($field),* }))
+                }
+
+                fn try_from_row_by_name(row: impl $crate::AsRow) -> ::std::result::Result<Self, $crate::Error> {
+                    let row = $crate::AsRow::as_row(&row);
+                    $(
+                        let $field = $crate::tokio_postgres::Row::try_get::<_, $field_ty>(row, ::std::stringify!($field)).map_err(|error| {
+                            $crate::__private::field_error(
+                                error.into(),
+                                ::std::any::type_name::<Self>(),
+                                ::std::stringify!($field),
+                                ::std::option::Option::Some(::std::stringify!($field)),
+                            )
+                        })?;
+                    )*
+                    ::std::result::Result::Ok(Self { $($field),* })
+                }
+
+                fn report_expected_columns() -> $crate::ExpectedColumns {
+                    match <Self as $crate::FromRow>::EXPECTED_COLUMNS {
+                        ::std::option::Option::Some(columns) => $crate::ExpectedColumns::Borrowed(columns),
+                        ::std::option::Option::None => ::std::unreachable!(),
+                    }
+                }
+
+                fn try_assert_matches(columns: &[$crate::tokio_postgres::Column]) -> ::std::result::Result<(), ()> {
+                    if $crate::__private::columns_match(&<Self as $crate::FromRow>::report_expected_columns(), columns) {
+                        ::std::result::Result::Ok(())
+                    } else {
+                        ::std::result::Result::Err(())
+                    }
+                }
+            }
+        )*
+    };
+}
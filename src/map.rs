@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+use tokio_postgres::{Column, Row};
+
+#[cfg(feature = "verify")]
+use crate::verify;
+use crate::{AsRow, Conversion, Error, ExpectedColumns, FieldPlan, FromRow};
+
+/// Groups rows by a key, reading the columns of `K` followed by the columns of `V`.
+///
+/// Rows with the same key don't need to be consecutive, the value of a key that was already seen
+/// is joined with the new row, so `V` is usually a collection like `Vec<T>`. When `V` does not join rows,
+/// the value of the last row with that key is kept.
+///
+/// The keys are kept in order, for report-style queries that must render sorted output.
+///
+/// A row whose key is null is skipped.
+impl<K: FromRow + Ord, V: FromRow> FromRow for BTreeMap<K, V> {
+    const COLUMN_COUNT: usize = K::COLUMN_COUNT + V::COLUMN_COUNT;
+    const JOINED: bool = true;
+    fn try_from_row_joined(
+        last: Option<&mut Self>,
+        row: &Row,
+        index: usize,
+    ) -> Result<Option<Self>, Error> {
+        let key = match K::try_from_row_joined(None, row, index) {
+            Ok(key) => key.expect(
+                "when try_from_row_joined is called with last = None it should never return None",
+            ),
+            Err(e) if e.is_null() => {
+                #[cfg(feature = "verify")]
+                verify::null_row();
+                return Ok(last.is_none().then(BTreeMap::new));
+            }
+            Err(e) => return Err(e),
+        };
+        let index = index + K::COLUMN_COUNT;
+        let Some(map) = last else {
+            let value = V::try_from_row_joined(None, row, index)?.expect(
+                "when try_from_row_joined is called with last = None it should never return None",
+            );
+            return Ok(Some(BTreeMap::from([(key, value)])));
+        };
+        if let Some(value) = V::try_from_row_joined(map.get_mut(&key), row, index)? {
+            map.insert(key, value);
+        }
+        Ok(None)
+    }
+    fn try_from_row_by_name(row: impl AsRow) -> Result<Self, Error> {
+        let row = row.as_row();
+        Ok(BTreeMap::from([(
+            K::try_from_row_by_name(row)?,
+            V::try_from_row_by_name(row)?,
+        )]))
+    }
+    fn row_span(&self) -> usize {
+        self.values().map(V::row_span).sum()
+    }
+    fn report_expected_columns() -> ExpectedColumns {
+        let mut columns = K::report_expected_columns().into_owned();
+        columns.extend_from_slice(&V::report_expected_columns());
+        columns.into()
+    }
+    fn dry_run(columns: &[Column]) -> Vec<FieldPlan> {
+        vec![
+            FieldPlan::nested(
+                None,
+                0,
+                K::COLUMN_COUNT,
+                Conversion::Flatten,
+                K::dry_run(columns),
+            ),
+            FieldPlan::nested(
+                None,
+                K::COLUMN_COUNT,
+                V::COLUMN_COUNT,
+                Conversion::Join,
+                V::dry_run(columns.get(K::COLUMN_COUNT..).unwrap_or(&[])),
+            ),
+        ]
+    }
+    fn try_assert_matches(columns: &[Column]) -> Result<(), ()> {
+        if columns.len() != Self::COLUMN_COUNT {
+            return Err(());
+        }
+        let (key, value) = columns.split_at(K::COLUMN_COUNT);
+        K::try_assert_matches(key)?;
+        V::try_assert_matches(value)
+    }
+}
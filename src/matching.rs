@@ -0,0 +1,284 @@
+use std::{collections::HashMap, fmt};
+
+use tokio_postgres::{types::Type, Column};
+
+use crate::{Case, ExpectedColumn};
+
+/// How well a set of columns matches the columns expected by a type, see [`FromRow::matches`](crate::FromRow::matches).
+///
+/// Ordered from worst to best, so the best candidate can be picked with [`Iterator::max`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MatchQuality {
+    /// The type can't be decoded from these columns.
+    NoMatch,
+    /// Every column the type expects is present by name with an accepted type, in any order,
+    /// so the type can be decoded with [`FromRow::try_from_row_by_name`](crate::FromRow::try_from_row_by_name).
+    ByNameSubset,
+    /// The columns start with exactly the columns the type expects, followed by extra columns.
+    Prefix,
+    /// The columns are exactly the ones the type expects.
+    Exact,
+}
+
+/// How strictly the columns of a query are compared with the columns expected by a type,
+/// see [`FromRow::assert_matches_with`](crate::FromRow::assert_matches_with).
+///
+/// The default is the policy of [`FromRow::assert_matches`](crate::FromRow::assert_matches),
+/// which expects exactly the columns of the type, in order, with accepted types.
+///
+/// ```ignore
+/// let policy = MatchPolicy { case: Case::Snake, ..MatchPolicy::default() };
+/// let user = CamelUser::try_from_row_with_policy(&row, &policy)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MatchPolicy {
+    /// Whether the query may have columns that are not expected, before, between or after the expected ones.
+    pub extra_columns: bool,
+    /// Whether the expected columns must be in the order they are expected, otherwise they are found by name,
+    /// and columns without an expected name match the column at their index.
+    pub order: bool,
+    /// The case convention of the columns, the expected names are converted to it before they are compared.
+    ///
+    /// Allows validating a struct renamed with `rename_all = "camelCase"` against `snake_case` columns,
+    /// without renaming every field.
+    pub case: Case,
+    /// Whether the types of the columns must be accepted by the types of the fields.
+    ///
+    /// Without it, a column of the wrong type is only reported when it fails to be decoded.
+    pub type_check: bool,
+}
+
+impl MatchPolicy {
+    /// The policy of [`FromRow::assert_matches`](crate::FromRow::assert_matches),
+    /// which expects exactly the columns of the type, in order, with accepted types.
+    pub const STRICT: Self = Self {
+        extra_columns: false,
+        order: true,
+        case: Case::AsIs,
+        type_check: true,
+    };
+
+    /// Like [`MatchPolicy::STRICT`], allowing columns that are not expected,
+    /// see [`FromRow::assert_matches_subset`](crate::FromRow::assert_matches_subset).
+    pub const SUBSET: Self = Self {
+        extra_columns: true,
+        ..Self::STRICT
+    };
+
+    /// Whether the columns allowed by this policy can be read by index.
+    pub(crate) fn reads_by_index(&self) -> bool {
+        self.order && !self.extra_columns
+    }
+}
+
+impl Default for MatchPolicy {
+    fn default() -> Self {
+        Self::STRICT
+    }
+}
+
+/// The name and type of a column, so that columns other than the ones of a `Row` can be matched too.
+pub(crate) trait NamedColumn {
+    fn name(&self) -> &str;
+    fn type_(&self) -> &Type;
+}
+
+impl NamedColumn for Column {
+    fn name(&self) -> &str {
+        Column::name(self)
+    }
+
+    fn type_(&self) -> &Type {
+        Column::type_(self)
+    }
+}
+
+/// The first column that does not match what a type expects, returned by [`FromRow::check_matches`](crate::FromRow::check_matches).
+///
+/// The columns are compared by index, like [`FromRow::assert_matches`](crate::FromRow::assert_matches),
+/// so a missing column is reported where the next column was found.
+#[derive(Debug, Clone)]
+pub struct MatchError {
+    index: usize,
+    expected: Option<Box<ExpectedColumn>>,
+    found: Option<(String, Type)>,
+}
+
+impl MatchError {
+    /// Compares the columns by index, returning the first one that does not match.
+    pub(crate) fn find(expected: &[ExpectedColumn], columns: &[Column]) -> Self {
+        let len = expected.len().max(columns.len());
+        // a type may reject columns its expected columns accept, then no column is blamed
+        let index = (0..len)
+            .find(|&i| match (expected.get(i), columns.get(i)) {
+                (Some(expected), Some(column)) => {
+                    expected
+                        .column_name()
+                        .is_some_and(|name| name != column.name())
+                        || !expected.accepts(column.type_())
+                }
+                _ => true,
+            })
+            .unwrap_or(len);
+        Self::at(index, expected.get(index), columns.get(index))
+    }
+
+    /// The column at `index` does not match, the expected column may come from another index.
+    pub(crate) fn at(
+        index: usize,
+        expected: Option<&ExpectedColumn>,
+        found: Option<&impl NamedColumn>,
+    ) -> Self {
+        Self {
+            index,
+            expected: expected.copied().map(Box::new),
+            found: found.map(|column| (column.name().to_string(), column.type_().clone())),
+        }
+    }
+
+    /// The index of the column that does not match.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The column expected at [`MatchError::index`], `None` when there are more columns than expected.
+    pub fn expected(&self) -> Option<&ExpectedColumn> {
+        self.expected.as_deref()
+    }
+
+    /// The name of the column found at [`MatchError::index`], `None` when the expected column is missing.
+    pub fn found_name(&self) -> Option<&str> {
+        self.found.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    /// The type of the column found at [`MatchError::index`], `None` when the expected column is missing.
+    pub fn found_type(&self) -> Option<&Type> {
+        self.found.as_ref().map(|(_, ty)| ty)
+    }
+
+    /// Whether the column was found with the expected name, but its type is not accepted.
+    pub fn is_type_mismatch(&self) -> bool {
+        match (&self.expected, &self.found) {
+            (Some(expected), Some((name, _))) => expected.column_name().is_none_or(|n| n == name),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for MatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let index = self.index;
+        match (&self.expected, &self.found) {
+            (Some(expected), Some((name, found))) if self.is_type_mismatch() => write!(
+                f,
+                "column {index} `{name}` has type `{found}`, expected {}",
+                expected.describe_accepted_types()
+            ),
+            (Some(expected), Some((name, found))) => write!(
+                f,
+                "column {index} is `{name}` of type `{found}`, expected `{}` of type `{}`",
+                expected.column_name().unwrap_or("-"),
+                expected.describe_type()
+            ),
+            (Some(expected), None) => write!(
+                f,
+                "column {index} is missing, expected `{}` of type `{}`",
+                expected.column_name().unwrap_or("-"),
+                expected.describe_type()
+            ),
+            (None, Some((name, found))) => {
+                write!(
+                    f,
+                    "column {index} `{name}` of type `{found}` is not expected"
+                )
+            }
+            (None, None) => write!(f, "the columns do not match"),
+        }
+    }
+}
+
+impl std::error::Error for MatchError {}
+
+/// Checks if the columns match the expected ones, as allowed by `policy`, returning the first one that does not.
+pub(crate) fn check_with_policy<C: NamedColumn>(
+    expected: &[ExpectedColumn],
+    columns: &[C],
+    policy: &MatchPolicy,
+) -> Result<(), MatchError> {
+    let matches = |expected: &ExpectedColumn, column: &C| {
+        expected
+            .column_name()
+            .is_none_or(|name| policy.case.apply(name) == column.name())
+            && (!policy.type_check || expected.accepts(column.type_()))
+    };
+    if !policy.extra_columns && expected.len() != columns.len() {
+        let index = expected
+            .iter()
+            .zip(columns)
+            .position(|(expected, column)| !matches(expected, column))
+            .unwrap_or(expected.len().min(columns.len()));
+        return Err(MatchError::at(
+            index,
+            expected.get(index),
+            columns.get(index),
+        ));
+    }
+    if policy.order {
+        let mut next = 0;
+        for expected in expected {
+            match columns[next..]
+                .iter()
+                .position(|column| matches(expected, column))
+            {
+                Some(offset) => next += offset + 1,
+                None => return Err(MatchError::at(next, Some(expected), columns.get(next))),
+            }
+        }
+        Ok(())
+    } else {
+        for (i, expected) in expected.iter().enumerate() {
+            let index = match expected.column_name() {
+                Some(name) => {
+                    let name = policy.case.apply(name);
+                    columns.iter().position(|column| column.name() == name)
+                }
+                None => (i < columns.len()).then_some(i),
+            };
+            match index {
+                Some(index) if matches(expected, &columns[index]) => {}
+                Some(index) => {
+                    return Err(MatchError::at(index, Some(expected), Some(&columns[index])))
+                }
+                None => return Err(MatchError::at(i, Some(expected), None::<&C>)),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks if every expected column is present by name with an accepted type.
+pub(crate) fn matches_by_name_subset(expected: &[ExpectedColumn], columns: &[Column]) -> bool {
+    expected.iter().all(|expected| {
+        expected.column_name().is_some_and(|name| {
+            columns
+                .iter()
+                .any(|column| column.name() == name && expected.accepts(column.type_()))
+        })
+    })
+}
+
+/// Checks if the columns are exactly the expected ones, once their names are replaced by their `aliases`.
+pub(crate) fn matches_with_aliases(
+    expected: &[ExpectedColumn],
+    columns: &[Column],
+    aliases: &HashMap<&str, &str>,
+) -> bool {
+    expected.len() == columns.len()
+        && expected.iter().zip(columns).all(|(expected, column)| {
+            let name = aliases.get(column.name()).copied().unwrap_or(column.name());
+            expected
+                .column_name()
+                .is_none_or(|expected| expected == name)
+                && expected.accepts(column.type_())
+        })
+}
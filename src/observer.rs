@@ -0,0 +1,127 @@
+//! Hooks for observing conversions, enabled by the `stats` feature.
+
+use std::{
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+/// Describes a single field that was decoded from a row.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldDecode {
+    /// The name of the type the field belongs to, as given by [`std::any::type_name`].
+    pub type_name: &'static str,
+    /// The name of the field.
+    pub field: &'static str,
+    /// The index of the first column the field was decoded from.
+    pub column: usize,
+    /// How long it took to decode the field, including any conversions.
+    pub elapsed: Duration,
+}
+
+/// Receives events about conversions, install one with [`set_observer`].
+pub trait Observer: Send + Sync {
+    /// Called after a field of a derived struct has been decoded successfully.
+    fn field_decoded(&self, event: &FieldDecode);
+}
+
+static OBSERVER: OnceLock<&'static dyn Observer> = OnceLock::new();
+
+/// Installs the global observer, can only be done once.
+///
+/// Returns the observer back if one was already installed.
+pub fn set_observer(observer: &'static dyn Observer) -> Result<(), &'static dyn Observer> {
+    OBSERVER.set(observer)
+}
+
+/// Returns the global observer, if one was installed.
+pub fn observer() -> Option<&'static dyn Observer> {
+    OBSERVER.get().copied()
+}
+
+/// The accumulated statistics of a single field.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldStats {
+    /// The name of the type the field belongs to, as given by [`std::any::type_name`].
+    pub type_name: &'static str,
+    /// The name of the field.
+    pub field: &'static str,
+    /// How many times the field was decoded.
+    pub count: u64,
+    /// The total time spent decoding the field.
+    pub total: Duration,
+}
+
+impl FieldStats {
+    /// The average time spent decoding the field.
+    pub fn average(&self) -> Duration {
+        match u32::try_from(self.count) {
+            Ok(0) => Duration::ZERO,
+            Ok(count) => self.total / count,
+            Err(_) => Duration::from_secs_f64(self.total.as_secs_f64() / self.count as f64),
+        }
+    }
+}
+
+/// An [`Observer`] that accumulates decode counts and durations per field.
+///
+/// ```ignore
+/// static STATS: DecodeStats = DecodeStats::new();
+///
+/// set_observer(&STATS).unwrap();
+/// // ... convert rows ...
+/// for field in STATS.snapshot() {
+///     println!("{}::{} {:?}", field.type_name, field.field, field.total);
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct DecodeStats {
+    fields: Mutex<Vec<FieldStats>>,
+}
+
+impl DecodeStats {
+    pub const fn new() -> Self {
+        Self {
+            fields: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the statistics collected so far, sorted by total time spent, slowest first.
+    pub fn snapshot(&self) -> Vec<FieldStats> {
+        let mut fields = self
+            .fields
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        fields.sort_by_key(|f| std::cmp::Reverse(f.total));
+        fields
+    }
+
+    /// Clears the statistics collected so far.
+    pub fn reset(&self) {
+        self.fields
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+impl Observer for DecodeStats {
+    fn field_decoded(&self, event: &FieldDecode) {
+        let mut fields = self.fields.lock().unwrap_or_else(|e| e.into_inner());
+        match fields
+            .iter_mut()
+            .find(|f| f.field == event.field && f.type_name == event.type_name)
+        {
+            Some(stats) => {
+                stats.count += 1;
+                stats.total += event.elapsed;
+            }
+            None => fields.push(FieldStats {
+                type_name: event.type_name,
+                field: event.field,
+                count: 1,
+                total: event.elapsed,
+            }),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use tokio_postgres::{types::FromSql, Column, Row};
+
+use crate::{AsRow, Conversion, Error, ExpectedColumn, ExpectedColumns, FieldPlan, FromRow};
+
+/// The name of the column holding the total number of rows, selected after the columns of `T`.
+pub(crate) const TOTAL_COUNT: &str = "total_count";
+
+/// A page of items, together with the number of rows the query would return without `LIMIT` and `OFFSET`.
+///
+/// Reads the columns of `T` followed by a `total_count` column, as selected by:
+///
+/// ```sql
+/// SELECT todo_id, text, count(*) OVER () AS total_count FROM todos ORDER BY todo_id LIMIT $1 OFFSET $2
+/// ```
+///
+/// All the rows are joined into a single value, use [`Paginated::try_from_page`] to always get one,
+/// even if the page is empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paginated<T> {
+    /// The items of this page.
+    pub items: Vec<T>,
+    /// The number of rows of all the pages.
+    pub total_count: i64,
+}
+
+impl<T: FromRow> Paginated<T> {
+    /// Converts the rows of a page.
+    ///
+    /// When there are no rows, the total can't be known and is `0`, which is only accurate for the first page.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not contain the expected column names.
+    pub fn from_page(rows: &[Row]) -> Self {
+        Self::try_from_page(rows).expect("could not convert column")
+    }
+
+    /// Try's to convert the rows of a page.
+    ///
+    /// When there are no rows, the total can't be known and is `0`, which is only accurate for the first page.
+    pub fn try_from_page(rows: &[Row]) -> Result<Self, Error> {
+        Ok(Self::try_from_slice(rows)?.pop().unwrap_or(Self {
+            items: Vec::new(),
+            total_count: 0,
+        }))
+    }
+}
+
+impl<T: FromRow> FromRow for Paginated<T> {
+    const COLUMN_COUNT: usize = T::COLUMN_COUNT + 1;
+    const JOINED: bool = true;
+    fn try_from_row_joined(
+        last: Option<&mut Self>,
+        row: &Row,
+        index: usize,
+    ) -> Result<Option<Self>, Error> {
+        let Some(page) = last else {
+            let item = T::try_from_row_joined(None, row, index)?.expect(
+                "when try_from_row_joined is called with last = None it should never return None",
+            );
+            return Ok(Some(Self {
+                items: vec![item],
+                total_count: row.try_get(index + T::COLUMN_COUNT)?,
+            }));
+        };
+        if let Some(item) = T::try_from_row_joined(page.items.last_mut(), row, index)? {
+            page.items.push(item);
+        }
+        Ok(None)
+    }
+    fn try_from_row_by_name(row: impl AsRow) -> Result<Self, Error> {
+        let row = row.as_row();
+        Ok(Self {
+            items: vec![T::try_from_row_by_name(row)?],
+            total_count: row.try_get(TOTAL_COUNT)?,
+        })
+    }
+    fn row_span(&self) -> usize {
+        self.items.iter().map(T::row_span).sum()
+    }
+    fn report_expected_columns() -> ExpectedColumns {
+        let mut columns = T::report_expected_columns().into_owned();
+        columns.push(ExpectedColumn::new::<i64>(Some(TOTAL_COUNT)));
+        columns.into()
+    }
+    fn dry_run(columns: &[Column]) -> Vec<FieldPlan> {
+        let mut plans = T::dry_run(columns);
+        plans.push(FieldPlan::column(
+            Some(TOTAL_COUNT),
+            columns,
+            T::COLUMN_COUNT,
+            Some(TOTAL_COUNT),
+            Conversion::Direct,
+        ));
+        plans
+    }
+    fn try_assert_matches(columns: &[Column]) -> Result<(), ()> {
+        let [items @ .., total_count] = columns else {
+            return Err(());
+        };
+        if total_count.name() != TOTAL_COUNT || !<i64 as FromSql>::accepts(total_count.type_()) {
+            return Err(());
+        }
+        T::try_assert_matches(items)
+    }
+}
@@ -0,0 +1,25 @@
+use tokio_postgres::types::ToSql;
+
+/// A type whose fields can be passed as the parameters of a statement, implemented by `#[derive(ToParams)]`.
+///
+/// The derive also adds an inherent `params` method, returning the parameters as an array in field order:
+///
+/// ```ignore
+/// #[derive(ToParams)]
+/// struct NewUser {
+///     username: String,
+///     #[to_params(skip)]
+///     password: String,
+///     #[to_params(rename = "password_hash")]
+///     hash: String,
+/// }
+///
+/// client.execute("INSERT INTO users (username, password_hash) VALUES ($1, $2)", &user.params()).await?;
+/// ```
+pub trait ToParams {
+    /// The names of the parameters, in order, which are the names of the fields unless renamed.
+    const PARAM_NAMES: &'static [&'static str];
+
+    /// The parameters, in the order of [`ToParams::PARAM_NAMES`].
+    fn to_params(&self) -> Vec<&(dyn ToSql + Sync)>;
+}
@@ -0,0 +1,104 @@
+use std::ops::Range;
+
+use tokio_postgres::Column;
+
+/// How the value of a field is obtained from its columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Conversion {
+    /// The column is decoded directly into the field with `FromSql`.
+    Direct,
+    /// The column is decoded and then converted with `From`, see `#[from_row(from = "..")]`.
+    From,
+    /// The column is decoded and then converted with `TryFrom`, see `#[from_row(try_from = "..")]`.
+    TryFrom,
+    /// The column is decoded and then passed to a function, see `#[from_row(from_fn = "..")]`.
+    FromFn,
+    /// The column is decoded and then passed to a fallible function, see `#[from_row(try_from_fn = "..")]`.
+    TryFromFn,
+    /// The column is decoded as text and then matched to a variant of a [`TextEnum`](crate::TextEnum),
+    /// see `#[from_row(enum_text)]`.
+    EnumText,
+    /// The columns are decoded by a nested `FromRow` type, see `#[from_row(flatten)]`.
+    Flatten,
+    /// The columns are decoded by a nested `FromRow` type, merging consecutive rows, see `#[from_row(join)]`.
+    Join,
+}
+
+/// Which columns a field would be read from, as returned by [`FromRow::dry_run`](crate::FromRow::dry_run).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPlan {
+    /// The name of the field, `None` for types without named fields, like tuples.
+    pub field: Option<&'static str>,
+    /// The indexes of the columns this field would read.
+    pub columns: Range<usize>,
+    /// The name of the column the field expects, `None` for nested types or when it is not known.
+    pub expected_name: Option<&'static str>,
+    /// The name of the column that is actually at that index, `None` if there are not enough columns.
+    pub found_name: Option<String>,
+    /// How the value is obtained from the columns.
+    pub conversion: Conversion,
+    /// The plans of the fields of the nested type, when using `Flatten` or `Join`.
+    ///
+    /// Their column indexes are relative to the whole row, not to the nested type.
+    pub children: Vec<FieldPlan>,
+}
+
+impl FieldPlan {
+    /// The plan of a field that reads the single column at `index`.
+    pub fn column(
+        field: Option<&'static str>,
+        columns: &[Column],
+        index: usize,
+        expected_name: Option<&'static str>,
+        conversion: Conversion,
+    ) -> Self {
+        Self {
+            field,
+            columns: index..index + 1,
+            expected_name,
+            found_name: columns.get(index).map(|c| c.name().to_owned()),
+            conversion,
+            children: Vec::new(),
+        }
+    }
+
+    /// The plan of a field that reads `count` columns starting at `index` with a nested type.
+    ///
+    /// `children` are the plans of the nested type against the columns starting at `index`.
+    pub fn nested(
+        field: Option<&'static str>,
+        index: usize,
+        count: usize,
+        conversion: Conversion,
+        children: Vec<FieldPlan>,
+    ) -> Self {
+        Self {
+            field,
+            columns: index..index + count,
+            expected_name: None,
+            found_name: None,
+            conversion,
+            children: children.into_iter().map(|c| c.offset(index)).collect(),
+        }
+    }
+
+    /// Whether the column found at this index has the expected name, including the children.
+    pub fn is_aligned(&self) -> bool {
+        let this = match (self.expected_name, &self.found_name) {
+            (Some(expected), Some(found)) => expected == found,
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        this && self.children.iter().all(FieldPlan::is_aligned)
+    }
+
+    fn offset(mut self, offset: usize) -> Self {
+        self.columns = self.columns.start + offset..self.columns.end + offset;
+        self.children = self
+            .children
+            .into_iter()
+            .map(|c| c.offset(offset))
+            .collect();
+        self
+    }
+}
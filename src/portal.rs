@@ -0,0 +1,74 @@
+use tokio_postgres::{types::ToSql, Portal, Transaction};
+
+use crate::{batch::Batcher, Error, FromRow};
+
+/// Reads the rows of a portal in batches, converting them to `T`, created by [`bind_as`].
+///
+/// Each batch fetches up to `batch_size` rows, so huge result sets can be consumed incrementally inside a transaction.
+///
+/// Values that join several rows are only yielded once the first row of the next value is seen, so they may be
+/// completed by the rows of the next fetch, and a batch may hold fewer values than the rows that were fetched.
+pub struct PortalBatches<'a, T> {
+    transaction: &'a Transaction<'a>,
+    portal: Portal,
+    batch_size: i32,
+    batcher: Batcher<T>,
+}
+
+/// Binds `sql` with `params` to a portal in `transaction`, after validating the columns of the statement against `T`.
+///
+/// ```ignore
+/// let transaction = client.transaction().await?;
+/// let mut batches = bind_as::<Todo>(&transaction, "SELECT todo_id, text FROM todos", &[], 1000).await?;
+/// while let Some(todos) = batches.next_batch().await? {
+///     index(&todos).await?;
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the statement does not return the expected column names, or `batch_size` is not positive.
+pub async fn bind_as<'a, T: FromRow>(
+    transaction: &'a Transaction<'a>,
+    sql: &str,
+    params: &[&(dyn ToSql + Sync)],
+    batch_size: i32,
+) -> Result<PortalBatches<'a, T>, Error> {
+    assert!(batch_size > 0, "the batch size must be positive");
+    let statement = transaction.prepare(sql).await?;
+    T::assert_matches_statement(&statement);
+    let portal = transaction.bind(&statement, params).await?;
+    Ok(PortalBatches {
+        transaction,
+        portal,
+        batch_size,
+        batcher: Batcher::new(),
+    })
+}
+
+impl<T: FromRow> PortalBatches<'_, T> {
+    /// Fetches the next batch of rows, converting them.
+    ///
+    /// Returns `None` once all the rows have been read, or after an error was returned.
+    pub async fn next_batch(&mut self) -> Result<Option<Vec<T>>, Error> {
+        let mut values = Vec::new();
+        while values.is_empty() && !self.batcher.is_done() {
+            let rows = match self
+                .transaction
+                .query_portal(&self.portal, self.batch_size)
+                .await
+            {
+                Ok(rows) => rows,
+                Err(error) => {
+                    self.batcher.stop();
+                    return Err(error.into());
+                }
+            };
+            // the portal is exhausted once it returns fewer rows than requested
+            values = self
+                .batcher
+                .push(&rows, rows.len() < self.batch_size as usize)?;
+        }
+        Ok((!values.is_empty()).then_some(values))
+    }
+}
@@ -0,0 +1,50 @@
+use std::marker::PhantomData;
+
+use tokio_postgres::{types::ToSql, GenericClient};
+
+use crate::{Error, FromRow};
+
+/// A query whose result is converted to `T`, usually created with [`query_as!`](crate::query_as).
+pub struct Query<'a, T> {
+    sql: &'a str,
+    params: Vec<&'a (dyn ToSql + Sync)>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: FromRow> Query<'a, T> {
+    /// Creates a query without checking the sql against `T`.
+    pub fn new(sql: &'a str, params: Vec<&'a (dyn ToSql + Sync)>) -> Self {
+        Self {
+            sql,
+            params,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The sql of this query.
+    pub fn sql(&self) -> &'a str {
+        self.sql
+    }
+
+    /// Executes the query, converting all the rows.
+    pub async fn fetch_all(&self, client: &impl GenericClient) -> Result<Vec<T>, Error> {
+        let rows = client.query(self.sql, &self.params).await?;
+        T::try_from_rows(rows)
+    }
+
+    /// Executes the query, converting the only row it returns.
+    ///
+    /// Returns an error if the query does not return exactly one row.
+    pub async fn fetch_one(&self, client: &impl GenericClient) -> Result<T, Error> {
+        let row = client.query_one(self.sql, &self.params).await?;
+        T::try_from_row(&row)
+    }
+
+    /// Executes the query, converting the row it returns, if any.
+    ///
+    /// Returns an error if the query returns more than one row.
+    pub async fn fetch_optional(&self, client: &impl GenericClient) -> Result<Option<T>, Error> {
+        let row = client.query_opt(self.sql, &self.params).await?;
+        row.map(T::try_from_row).transpose()
+    }
+}
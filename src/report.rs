@@ -0,0 +1,535 @@
+use std::{borrow::Cow, fmt, io::IsTerminal};
+
+use tokio_postgres::{types::Type, Column};
+
+use crate::{type_names, ExpectedColumn, FieldPath, TypeNames};
+
+/// A single line of a [`MismatchReport`].
+#[derive(Debug, Clone)]
+pub enum ReportEntry {
+    /// The column was found with the expected name and an accepted type.
+    Match {
+        expected: ExpectedColumn,
+        name: String,
+        found: Type,
+    },
+    /// The column is expected, but no column with its name was found.
+    Missing { expected: ExpectedColumn },
+    /// A column was found that is not expected.
+    Extra { name: String, found: Type },
+    /// The column was found with the expected name, but its type is not accepted.
+    TypeMismatch {
+        expected: ExpectedColumn,
+        name: String,
+        found: Type,
+    },
+    /// The column was found with an accepted type, but it is nullable and the rust type can't hold `NULL`.
+    ///
+    /// The columns of a statement don't say whether they are nullable, so this is only reported
+    /// once that is known, see [`MismatchReport::flag_nullable`].
+    NullabilityMismatch {
+        expected: ExpectedColumn,
+        name: String,
+        found: Type,
+    },
+}
+
+impl ReportEntry {
+    /// Whether this entry is a problem, which is every entry except [`ReportEntry::Match`].
+    pub fn is_mismatch(&self) -> bool {
+        !matches!(self, ReportEntry::Match { .. })
+    }
+}
+
+/// The differences between the columns found and the columns expected by a type,
+/// returned by [`report_expected_columns_mismatch`].
+///
+/// Displays as a table with a line for each column.
+#[derive(Debug, Clone, Default)]
+pub struct MismatchReport {
+    entries: Vec<ReportEntry>,
+}
+
+impl MismatchReport {
+    /// Every line of the report, in the order of the columns.
+    pub fn entries(&self) -> &[ReportEntry] {
+        &self.entries
+    }
+
+    /// The lines of the report that are a problem.
+    pub fn mismatches(&self) -> impl Iterator<Item = &ReportEntry> {
+        self.entries.iter().filter(|entry| entry.is_mismatch())
+    }
+
+    /// Whether every column was found with the expected name and an accepted type.
+    pub fn is_match(&self) -> bool {
+        self.mismatches().next().is_none()
+    }
+
+    /// Turns the matching columns that `is_nullable` says are nullable into a
+    /// [`ReportEntry::NullabilityMismatch`], when their rust type can't hold `NULL`.
+    pub fn flag_nullable(&mut self, mut is_nullable: impl FnMut(&str) -> bool) {
+        for entry in &mut self.entries {
+            if let ReportEntry::Match {
+                expected,
+                name,
+                found,
+            } = entry
+            {
+                if is_nullable(name) && !expected.nullable(found) {
+                    *entry = ReportEntry::NullabilityMismatch {
+                        expected: *expected,
+                        name: std::mem::take(name),
+                        found: found.clone(),
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// How a [`MismatchReport`] is laid out, see [`ReportOptions::layout`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ReportLayout {
+    /// A table with a header and aligned columns, meant to be read by people.
+    #[default]
+    Table,
+    /// A line of tab separated fields for each column, without a header or color, meant to be parsed or snapshotted.
+    ///
+    /// The fields are the status (`ok`, `type_mismatch`, `nullable`, `missing` or `extra`), the column name,
+    /// the rust type, the postgres type, its oid, whether the rust type can hold `NULL`,
+    /// and the [`FieldPath`] of the column, with `-` for the fields that don't apply.
+    Plain,
+}
+
+/// Controls how a [`MismatchReport`] is formatted, see [`MismatchReport::display`].
+///
+/// ```ignore
+/// let options = ReportOptions { layout: ReportLayout::Plain, ..ReportOptions::default() };
+/// insta::assert_snapshot!(report.display(&options).to_string());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReportOptions {
+    /// The layout of the report.
+    pub layout: ReportLayout,
+    /// The minimum width of the column names in the table, longer names are not truncated.
+    pub name_width: usize,
+    /// The minimum width of the rust and postgres types in the table, longer types are not truncated.
+    pub type_width: usize,
+    /// Whether to show if the rust type of each column can hold `NULL`.
+    pub show_nullable: bool,
+    /// Whether the table may be colored, with the `color` feature, when stderr is a terminal and `NO_COLOR` is not set.
+    pub color: bool,
+    /// How the names of rust types are shown, the default is the one set with [`set_type_names`](crate::set_type_names).
+    pub type_names: TypeNames,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            layout: ReportLayout::Table,
+            name_width: 20,
+            type_width: 20,
+            show_nullable: true,
+            color: true,
+            type_names: type_names(),
+        }
+    }
+}
+
+/// A [`MismatchReport`] formatted with [`ReportOptions`], returned by [`MismatchReport::display`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayReport<'a> {
+    report: &'a MismatchReport,
+    options: &'a ReportOptions,
+}
+
+impl MismatchReport {
+    /// Formats the report with `options`, instead of the defaults used by its [`Display`](fmt::Display) implementation.
+    pub fn display<'a>(&'a self, options: &'a ReportOptions) -> DisplayReport<'a> {
+        DisplayReport {
+            report: self,
+            options,
+        }
+    }
+}
+
+impl fmt::Display for MismatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(&ReportOptions::default()).fmt(f)
+    }
+}
+
+/// The fields of a line of the report.
+struct Line<'a> {
+    marker: &'static str,
+    status: &'static str,
+    name: &'a str,
+    rust_type: Option<Cow<'static, str>>,
+    found: Option<&'a Type>,
+    type_match: &'static str,
+    nullable: Option<bool>,
+    notes: Cow<'static, str>,
+    path: Option<&'a FieldPath>,
+}
+
+impl<'a> Line<'a> {
+    fn new(entry: &'a ReportEntry, type_names: TypeNames) -> Self {
+        match entry {
+            ReportEntry::Match {
+                expected,
+                name,
+                found,
+            } => Line {
+                marker: " ",
+                status: "ok",
+                name,
+                rust_type: Some(expected.describe_type_as(type_names)),
+                found: Some(found),
+                type_match: "OK",
+                nullable: Some(expected.nullable(found)),
+                notes: "".into(),
+                path: expected.path(),
+            },
+            ReportEntry::TypeMismatch {
+                expected,
+                name,
+                found,
+            } => Line {
+                marker: " ",
+                status: "type_mismatch",
+                name,
+                rust_type: Some(expected.describe_type_as(type_names)),
+                found: Some(found),
+                type_match: "MISMATCH",
+                nullable: Some(expected.nullable(found)),
+                notes: format!("expected {}", expected.describe_accepted_types()).into(),
+                path: expected.path(),
+            },
+            ReportEntry::NullabilityMismatch {
+                expected,
+                name,
+                found,
+            } => Line {
+                marker: " ",
+                status: "nullable",
+                name,
+                rust_type: Some(expected.describe_type_as(type_names)),
+                found: Some(found),
+                type_match: "OK",
+                nullable: Some(false),
+                notes: "NULLABLE COLUMN, TYPE CAN'T HOLD NULL".into(),
+                path: expected.path(),
+            },
+            ReportEntry::Missing { expected } => Line {
+                marker: "-",
+                status: "missing",
+                name: expected.column_name().unwrap_or("-"),
+                rust_type: Some(expected.describe_type_as(type_names)),
+                found: None,
+                type_match: "---",
+                nullable: None,
+                notes: "MISSING FROM DATABASE".into(),
+                path: expected.path(),
+            },
+            ReportEntry::Extra { name, found } => Line {
+                marker: "+",
+                status: "extra",
+                name,
+                rust_type: None,
+                found: Some(found),
+                type_match: "---",
+                nullable: None,
+                notes: "UNEXPECTED EXTRA COLUMN".into(),
+                path: None,
+            },
+        }
+    }
+}
+
+impl fmt::Display for DisplayReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = self.options;
+        let lines = self
+            .report
+            .entries
+            .iter()
+            .map(|entry| (entry, Line::new(entry, options.type_names)));
+
+        if options.layout == ReportLayout::Plain {
+            for (_, line) in lines {
+                write!(
+                    f,
+                    "{}\t{}\t{}\t{}\t{}",
+                    line.status,
+                    line.name,
+                    line.rust_type.as_deref().unwrap_or("-"),
+                    line.found.map_or("-", |found| found.name()),
+                    line.found
+                        .map_or("-".to_string(), |found| found.oid().to_string()),
+                )?;
+                if options.show_nullable {
+                    write!(f, "\t{}", line.nullable.map_or("-", yes_no))?;
+                }
+                match line.path {
+                    Some(path) => writeln!(f, "\t{path}")?,
+                    None => writeln!(f, "\t-")?,
+                }
+            }
+            return Ok(());
+        }
+
+        let (name_width, type_width) = (options.name_width, options.type_width);
+        let nullable_width = if options.show_nullable { 11 } else { 0 };
+        let rule = 2 + name_width + 2 * (type_width + 3) + 13 + nullable_width + 8;
+        writeln!(f, "Column Mismatch Report:")?;
+        writeln!(f, "{:-<rule$}", "")?;
+        write!(
+            f,
+            "  {:<name_width$} | {:<type_width$} | {:<type_width$} | {:<10} | ",
+            "Column Name", "Rust Type", "Postgres Type", "Type Match"
+        )?;
+        if options.show_nullable {
+            write!(f, "{:<8} | ", "Nullable")?;
+        }
+        writeln!(f, "Notes")?;
+        writeln!(f, "{:-<rule$}", "")?;
+
+        let color = options.color && use_color();
+        for (entry, line) in lines {
+            if color {
+                write!(f, "{}", entry_color(entry))?;
+            }
+            write!(
+                f,
+                "{} {:<name_width$} | {:<type_width$} | {:<type_width$} | {:<10} | ",
+                line.marker,
+                line.name,
+                line.rust_type.as_deref().unwrap_or("---"),
+                line.found.map_or("---".to_string(), describe_found),
+                line.type_match,
+            )?;
+            if options.show_nullable {
+                write!(f, "{:<8} | ", line.nullable.map_or("---", yes_no))?;
+            }
+            write!(f, "{}", line.notes)?;
+            // the path only tells something the column name doesn't for the columns of nested structs
+            if let Some(path) = line.path.filter(|path| path.fields().len() > 1) {
+                if !line.notes.is_empty() {
+                    write!(f, " ")?;
+                }
+                write!(f, "(from {path})")?;
+            }
+            if color {
+                write!(f, "\x1b[0m")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Describes a postgres type for reports with its name and oid, like `int4 (23)`.
+fn describe_found(found: &Type) -> String {
+    format!("{} ({})", found.name(), found.oid())
+}
+
+/// Whether to color the report, with the `color` feature, when stderr is a terminal and `NO_COLOR` is not set.
+///
+/// Stderr is checked since that is where the report is printed when [`FromRow::assert_matches`](crate::FromRow::assert_matches) panics.
+fn use_color() -> bool {
+    cfg!(feature = "color")
+        && std::env::var_os("NO_COLOR").is_none()
+        && std::io::stderr().is_terminal()
+}
+
+/// The ANSI color of the line of an entry: green when it matches, red when it is missing or has the wrong type,
+/// and yellow for extra columns and nullability mismatches.
+fn entry_color(entry: &ReportEntry) -> &'static str {
+    match entry {
+        ReportEntry::Match { .. } => "\x1b[32m",
+        ReportEntry::Missing { .. } | ReportEntry::TypeMismatch { .. } => "\x1b[31m",
+        ReportEntry::Extra { .. } | ReportEntry::NullabilityMismatch { .. } => "\x1b[33m",
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "Yes"
+    } else {
+        "No"
+    }
+}
+
+/// A difference between the columns found and the columns expected by a type, returned by [`diff_columns`].
+#[derive(Debug, Clone)]
+pub enum ColumnMismatch {
+    /// The column is expected, but no column with its name was found.
+    Missing { expected: ExpectedColumn },
+    /// A column was found that is not expected.
+    Extra { name: String, found: Type },
+    /// The column was found with the expected name, but its type is not accepted.
+    TypeMismatch {
+        expected: ExpectedColumn,
+        found: Type,
+    },
+    /// The column was found with an accepted type where it is expected, but with another name.
+    Renamed {
+        /// The name the type expects.
+        from: &'static str,
+        /// The name that was found.
+        to: String,
+        expected: ExpectedColumn,
+        found: Type,
+    },
+}
+
+/// Compares the columns found with the columns expected by a type, like [`report_expected_columns_mismatch`],
+/// returning only the differences, so they can be inspected without parsing a report.
+///
+/// A column that is missing where a column of an accepted type was found instead is reported as
+/// [`ColumnMismatch::Renamed`], instead of a missing and an extra column.
+///
+/// ```ignore
+/// for mismatch in diff_columns(statement.columns(), &User::report_expected_columns()) {
+///     if let ColumnMismatch::Renamed { from, to, .. } = mismatch {
+///         metrics::counter!("renamed_columns", "from" => from, "to" => to).increment(1);
+///     }
+/// }
+/// ```
+pub fn diff_columns(found: &[Column], expected: &[ExpectedColumn]) -> Vec<ColumnMismatch> {
+    diff_entries(report_expected_columns_mismatch(found, expected).entries)
+}
+
+/// Reduces the entries of a report to their differences, see [`diff_columns`].
+pub(crate) fn diff_entries(entries: Vec<ReportEntry>) -> Vec<ColumnMismatch> {
+    let mut mismatches = Vec::new();
+    let mut entries = entries.into_iter().peekable();
+    while let Some(entry) = entries.next() {
+        match entry {
+            ReportEntry::Match { .. } | ReportEntry::NullabilityMismatch { .. } => {}
+            ReportEntry::TypeMismatch {
+                expected, found, ..
+            } => mismatches.push(ColumnMismatch::TypeMismatch { expected, found }),
+            ReportEntry::Missing { .. } | ReportEntry::Extra { .. } => {
+                // the columns that replace each other are in the same run of missing and extra columns
+                let mut run = vec![entry];
+                while let Some(next) = entries.next_if(|next| {
+                    matches!(
+                        next,
+                        ReportEntry::Missing { .. } | ReportEntry::Extra { .. }
+                    )
+                }) {
+                    run.push(next);
+                }
+                let mut extra = run
+                    .iter()
+                    .filter_map(|entry| match entry {
+                        ReportEntry::Extra { name, found } => Some((name.clone(), found.clone())),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                for entry in &run {
+                    let ReportEntry::Missing { expected } = *entry else {
+                        continue;
+                    };
+                    let renamed = expected.column_name().and_then(|from| {
+                        let i = extra
+                            .iter()
+                            .position(|(_, found)| expected.accepts(found))?;
+                        Some((from, i))
+                    });
+                    match renamed {
+                        Some((from, i)) => {
+                            let (to, found) = extra.remove(i);
+                            mismatches.push(ColumnMismatch::Renamed {
+                                from,
+                                to,
+                                expected,
+                                found,
+                            });
+                        }
+                        None => mismatches.push(ColumnMismatch::Missing { expected }),
+                    }
+                }
+                mismatches.extend(
+                    extra
+                        .into_iter()
+                        .map(|(name, found)| ColumnMismatch::Extra { name, found }),
+                );
+            }
+        }
+    }
+    mismatches
+}
+
+/// Compares the columns found with the columns expected by a type.
+pub fn report_expected_columns_mismatch(
+    found_cols: &[Column],
+    expected_cols: &[ExpectedColumn],
+) -> MismatchReport {
+    let found: Vec<(&str, &Type)> = found_cols.iter().map(|c| (c.name(), c.type_())).collect();
+    report_named_columns_mismatch(&found, expected_cols)
+}
+
+/// Compares the names and types of the columns found with the columns expected by a type,
+/// for columns whose names are not the ones of the row, like aliased columns.
+pub(crate) fn report_named_columns_mismatch(
+    found_cols: &[(&str, &Type)],
+    expected_cols: &[ExpectedColumn],
+) -> MismatchReport {
+    use similar::{ChangeTag, TextDiff};
+
+    // TODO! update this code to correctly handle absent column names
+
+    // 1. Prepare the sequences for diffing (just the names)
+    let found_names: Vec<&str> = found_cols.iter().map(|&(name, _)| name).collect();
+    let expected_names: Vec<&str> = expected_cols
+        .iter()
+        .map(|e| e.column_name().unwrap_or("-"))
+        .collect();
+
+    let diff = TextDiff::from_slices(&expected_names, &found_names);
+
+    // 2. Iterate through the diff changes
+    // TextDiff tracks the indices for us so we can pull the full objects
+    let entries = diff
+        .iter_all_changes()
+        .map(|change| match change.tag() {
+            ChangeTag::Equal => {
+                // Name matches! Now check if the actual type 'accepts' the expected type
+                let (name, found) = found_cols[change.new_index().unwrap()];
+                let e_col = expected_cols[change.old_index().unwrap()];
+
+                if e_col.accepts(found) {
+                    ReportEntry::Match {
+                        expected: e_col,
+                        name: name.to_string(),
+                        found: found.clone(),
+                    }
+                } else {
+                    ReportEntry::TypeMismatch {
+                        expected: e_col,
+                        name: name.to_string(),
+                        found: found.clone(),
+                    }
+                }
+            }
+            // Present in 'Expected' (Old) but missing in 'Found' (New)
+            ChangeTag::Delete => ReportEntry::Missing {
+                expected: expected_cols[change.old_index().unwrap()],
+            },
+            // Present in 'Found' (New) but not in 'Expected' (Old)
+            ChangeTag::Insert => {
+                let (name, found) = found_cols[change.new_index().unwrap()];
+                ReportEntry::Extra {
+                    name: name.to_string(),
+                    found: found.clone(),
+                }
+            }
+        })
+        .collect();
+
+    MismatchReport { entries }
+}
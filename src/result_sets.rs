@@ -0,0 +1,74 @@
+use tokio_postgres::Row;
+
+use crate::{Error, FromRow};
+
+/// Converts several result sets at once, like the ones of pipelined queries, into a tuple `(Vec<A>, Vec<B>, ...)`.
+///
+/// Each result set is converted with [`FromRow::try_from_slice`] of its element, so the columns of every set
+/// are validated independently.
+///
+/// ```ignore
+/// let (users, todos) = tokio::try_join!(
+///     client.query("SELECT user_id, username FROM users", &[]),
+///     client.query("SELECT todo_id, text FROM todos", &[]),
+/// )?;
+/// let (users, todos) = <(Vec<User>, Vec<Todo>)>::from_result_sets(&[users, todos]);
+/// ```
+pub trait FromResultSets: Sized {
+    /// The number of result sets this type is converted from.
+    const RESULT_SETS: usize;
+
+    /// Perform the conversion of every result set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of result sets is not [`FromResultSets::RESULT_SETS`],
+    /// or the rows of a set do not contain the expected column names.
+    fn from_result_sets(sets: &[Vec<Row>]) -> Self {
+        Self::try_from_result_sets(sets).expect("could not convert column")
+    }
+
+    /// Try's to perform the conversion of every result set.
+    ///
+    /// Fails if the number of result sets is not [`FromResultSets::RESULT_SETS`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows of a set do not contain the expected column names.
+    fn try_from_result_sets(sets: &[Vec<Row>]) -> Result<Self, Error>;
+}
+
+macro_rules! impl_from_result_sets_for_tuple {
+    ($($T:ident),*) => {
+        impl<$($T: FromRow),*> FromResultSets for ($(Vec<$T>,)*) {
+            const RESULT_SETS: usize = 0 $( + count_ident!($T))*;
+            fn try_from_result_sets(sets: &[Vec<Row>]) -> Result<Self, Error> {
+                #[allow(non_snake_case)]
+                let [$($T,)*] = sets else {
+                    return Err(Error::conversion(format!(
+                        "expected {} result sets, found {}",
+                        Self::RESULT_SETS,
+                        sets.len()
+                    )));
+                };
+                Ok(($($T::try_from_slice($T)?,)*))
+            }
+        }
+    };
+}
+
+macro_rules! count_ident {
+    ($i:ident) => {
+        1
+    };
+}
+
+macro_rules! generate_from_result_sets {
+    () => {};
+    ($head:ident $(, $tail:ident)*) => {
+        impl_from_result_sets_for_tuple!($head $(, $tail)*);
+        generate_from_result_sets!($($tail),*);
+    };
+}
+
+generate_from_result_sets!(T11, T10, T9, T8, T7, T6, T5, T4, T3, T2, T1, T0);
@@ -0,0 +1,234 @@
+use std::{collections::HashSet, fmt};
+
+use tokio_postgres::{types::Type, GenericClient, Statement};
+
+use crate::{report_expected_columns_mismatch, type_names, FromRow, MismatchReport};
+
+/// A difference between the columns expected by a type and the columns of a table.
+#[derive(Debug, Clone)]
+pub enum SchemaIssue {
+    /// The table has no column with this name.
+    MissingColumn { column: &'static str },
+    /// The table has the column, but the rust type does not accept its type.
+    TypeMismatch {
+        column: &'static str,
+        rust_type: &'static str,
+        found: Type,
+    },
+    /// The column is nullable, but the rust type can't hold `NULL`.
+    Nullable {
+        column: &'static str,
+        rust_type: &'static str,
+    },
+}
+
+impl fmt::Display for SchemaIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaIssue::MissingColumn { column } => write!(f, "column `{column}` does not exist"),
+            SchemaIssue::TypeMismatch {
+                column,
+                rust_type,
+                found,
+            } => write!(
+                f,
+                "column `{column}` has type `{found}` which can't be read into `{}`",
+                type_names().apply(rust_type)
+            ),
+            SchemaIssue::Nullable { column, rust_type } => write!(
+                f,
+                "column `{column}` is nullable but `{}` can't hold NULL",
+                type_names().apply(rust_type)
+            ),
+        }
+    }
+}
+
+/// The error returned by [`validate_schema`].
+#[derive(Debug)]
+pub enum SchemaMismatch {
+    /// Querying the schema failed.
+    Query(tokio_postgres::Error),
+    /// The table does not exist, or has no visible columns.
+    TableNotFound { table: String },
+    /// The table exists, but its columns don't match what the type expects.
+    Columns {
+        type_name: &'static str,
+        table: String,
+        issues: Vec<SchemaIssue>,
+    },
+}
+
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaMismatch::Query(error) => write!(f, "could not query the schema: {error}"),
+            SchemaMismatch::TableNotFound { table } => write!(f, "table `{table}` does not exist"),
+            SchemaMismatch::Columns {
+                type_name,
+                table,
+                issues,
+            } => {
+                write!(f, "`{type_name}` does not match table `{table}`:")?;
+                for issue in issues {
+                    write!(f, "\n  {issue}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchemaMismatch {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SchemaMismatch::Query(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<tokio_postgres::Error> for SchemaMismatch {
+    fn from(error: tokio_postgres::Error) -> Self {
+        SchemaMismatch::Query(error)
+    }
+}
+
+/// Verifies that the named columns expected by `T` exist in `table`, have types `T` accepts,
+/// and are only nullable where `T` can hold `NULL`.
+///
+/// The table may be qualified with a schema (`"public.users"`), otherwise the current schema is used.
+///
+/// This is meant to be called at startup, so schema drift is detected before any query is executed.
+pub async fn validate_schema<T: FromRow>(
+    client: &impl GenericClient,
+    table: &str,
+) -> Result<(), SchemaMismatch> {
+    let (schema, name) = match table.split_once('.') {
+        Some((schema, name)) => (Some(schema), name),
+        None => (None, table),
+    };
+
+    let rows = client
+        .query(
+            "SELECT c.column_name::text, \
+                    (quote_ident(c.udt_schema) || '.' || quote_ident(c.udt_name))::regtype::oid, \
+                    c.is_nullable = 'YES' \
+             FROM information_schema.columns c \
+             WHERE c.table_schema::text = coalesce($1::text, current_schema()::text) \
+               AND c.table_name::text = $2::text \
+             ORDER BY c.ordinal_position",
+            &[&schema, &name],
+        )
+        .await?;
+
+    if rows.is_empty() {
+        return Err(SchemaMismatch::TableNotFound {
+            table: table.to_string(),
+        });
+    }
+
+    let columns = rows
+        .iter()
+        .map(|row| {
+            Ok((
+                row.try_get::<_, String>(0)?,
+                row.try_get::<_, u32>(1)?,
+                row.try_get::<_, bool>(2)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, tokio_postgres::Error>>()?;
+
+    let mut issues = Vec::new();
+    for expected in T::report_expected_columns().iter() {
+        let Some(column) = expected.column_name() else {
+            continue;
+        };
+        let Some((_, oid, nullable)) = columns.iter().find(|(name, _, _)| name == column) else {
+            issues.push(SchemaIssue::MissingColumn { column });
+            continue;
+        };
+        // types that are not built in can't be checked without more round trips
+        let found = Type::from_oid(*oid);
+        if let Some(found) = &found {
+            if !expected.accepts(found) {
+                issues.push(SchemaIssue::TypeMismatch {
+                    column,
+                    rust_type: expected.type_name(),
+                    found: found.clone(),
+                });
+                continue;
+            }
+        }
+        if *nullable && !expected.nullable(&found.unwrap_or_else(|| expected.probe_type())) {
+            issues.push(SchemaIssue::Nullable {
+                column,
+                rust_type: expected.type_name(),
+            });
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(SchemaMismatch::Columns {
+            type_name: std::any::type_name::<T>(),
+            table: table.to_string(),
+            issues,
+        })
+    }
+}
+
+/// Compares the columns of `statement` with the columns expected by `T`, like [`FromRow::assert_matches_statement`],
+/// also flagging the columns read from a table column without `NOT NULL` into a type that can't hold `NULL`.
+///
+/// Which columns are nullable is looked up in `pg_attribute`, for the columns of the statement that are read
+/// directly from a table. Computed columns are not flagged, and neither are table columns made nullable
+/// by an outer join, since the catalog can't tell.
+///
+/// ```ignore
+/// let statement = client.prepare("SELECT user_id, username FROM users").await?;
+/// let report = report_statement::<User>(&client, &statement).await?;
+/// assert!(report.is_match(), "{report}");
+/// ```
+pub async fn report_statement<T: FromRow>(
+    client: &impl GenericClient,
+    statement: &Statement,
+) -> Result<MismatchReport, tokio_postgres::Error> {
+    let columns = statement.columns();
+    let mut report = report_expected_columns_mismatch(columns, &T::report_expected_columns());
+
+    let (tables, attributes): (Vec<u32>, Vec<i16>) = columns
+        .iter()
+        .filter_map(|column| Some((column.table_oid()?, column.column_id()?)))
+        .unzip();
+    if tables.is_empty() {
+        return Ok(report);
+    }
+    let rows = client
+        .query(
+            "SELECT a.attrelid, a.attnum \
+             FROM pg_attribute a \
+             JOIN unnest($1::oid[], $2::int2[]) AS c (attrelid, attnum) USING (attrelid, attnum) \
+             WHERE NOT a.attnotnull",
+            &[&tables, &attributes],
+        )
+        .await?;
+    let nullable = rows
+        .iter()
+        .map(|row| Ok((row.try_get::<_, u32>(0)?, row.try_get::<_, i16>(1)?)))
+        .collect::<Result<HashSet<_>, tokio_postgres::Error>>()?;
+    let nullable_names = columns
+        .iter()
+        .filter(|column| {
+            column
+                .table_oid()
+                .zip(column.column_id())
+                .is_some_and(|key| nullable.contains(&key))
+        })
+        .map(|column| column.name())
+        .collect::<HashSet<_>>();
+
+    report.flag_nullable(|name| nullable_names.contains(name));
+    Ok(report)
+}
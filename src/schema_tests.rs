@@ -0,0 +1,78 @@
+//! Tests asserting that structs match the tables of a live database, enabled by the `schema-tests` feature.
+//!
+//! [`schema_tests!`](crate::schema_tests) generates a `#[tokio::test]` for each struct, which connects to the database
+//! in the `TEST_DATABASE_URL` environment variable and validates the struct against its table with
+//! [`validate_schema`](crate::validate_schema), so schema drift fails the tests instead of the first query:
+//!
+//! ```ignore
+//! postgres_from_row::schema_tests! {
+//!     // the table of `#[from_row(table = "..")]`
+//!     users_match_schema: User,
+//!     todos_match_schema: Todo => "public.todos",
+//! }
+//! ```
+//!
+//! The tests need `tokio` with the `macros` and `rt` features as a dev-dependency, and connect without TLS.
+//! When `TEST_DATABASE_URL` is not set, they pass without checking anything, so they can stay enabled
+//! on machines without a database.
+
+use tokio_postgres::GenericClient;
+
+use crate::{report_expected_columns_mismatch, validate_schema, FromRow};
+
+/// The environment variable holding the connection string of the database the tests connect to.
+pub const DATABASE_URL: &str = "TEST_DATABASE_URL";
+
+/// Reads [`DATABASE_URL`], warning that the schema is not checked when it is not set.
+pub fn database_url() -> Option<String> {
+    let url = std::env::var(DATABASE_URL).ok();
+    if url.is_none() {
+        eprintln!("`{DATABASE_URL}` is not set, skipping the schema test");
+    }
+    url
+}
+
+/// Validates `T` against `table` with [`validate_schema`], panicking with the issues found on error.
+///
+/// The panic message also shows the mismatch report of `T` against all the columns of the table, in order.
+pub async fn assert_table<T: FromRow>(client: &impl GenericClient, table: &str) {
+    let Err(mismatch) = validate_schema::<T>(client, table).await else {
+        return;
+    };
+    let mut message = mismatch.to_string();
+    if let Ok(statement) = client.prepare(&format!("SELECT * FROM {table}")).await {
+        let expected = T::report_expected_columns();
+        let report = report_expected_columns_mismatch(statement.columns(), &expected);
+        message = format!("{message}\n\n{report}");
+    }
+    panic!("{message}")
+}
+
+/// Generates a `#[tokio::test]` asserting that each struct matches its table, see the [`schema_tests`](mod@crate::schema_tests) module.
+///
+/// Each test is written as `name: Type`, reading the table from [`Table::TABLE`](crate::Table::TABLE),
+/// or as `name: Type => "table"`.
+#[macro_export]
+macro_rules! schema_tests {
+    (@table $ty:ty) => {
+        <$ty as $crate::Table>::TABLE
+    };
+    (@table $ty:ty, $table:expr) => {
+        $table
+    };
+    ($($name:ident: $ty:ty $(=> $table:expr)?),* $(,)?) => {
+        $(
+            #[tokio::test]
+            async fn $name() {
+                let Some(url) = $crate::schema_tests::database_url() else {
+                    return;
+                };
+                let (client, connection) = $crate::tokio_postgres::connect(&url, $crate::tokio_postgres::NoTls)
+                    .await
+                    .expect("could not connect to the test database");
+                tokio::spawn(connection);
+                $crate::schema_tests::assert_table::<$ty>(&client, $crate::schema_tests!(@table $ty $(, $table)?)).await;
+            }
+        )*
+    };
+}
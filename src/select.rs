@@ -0,0 +1,127 @@
+use std::{fmt, marker::PhantomData};
+
+use crate::{table::column_list, Table};
+
+/// Builds a `SELECT` of the columns of `T`, in the order [`FromRow::assert_matches`](crate::FromRow::assert_matches)
+/// expects them, created with [`Table::select`].
+///
+/// The columns of the table are qualified with its name, and the columns of flattened or joined fields are
+/// selected by name, so the tables they come from must be joined with [`Select::join_raw`].
+///
+/// ```ignore
+/// let sql = User::select().where_raw("user_id = $1").to_sql();
+/// // SELECT users.user_id, users.username FROM users WHERE user_id = $1
+/// ```
+pub struct Select<T> {
+    joins: Vec<String>,
+    conditions: Vec<String>,
+    order_by: Option<String>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+    target: PhantomData<fn() -> T>,
+}
+
+impl<T: Table> Select<T> {
+    /// Selects all the rows of the table of `T`.
+    pub fn new() -> Self {
+        Self {
+            joins: Vec::new(),
+            conditions: Vec::new(),
+            order_by: None,
+            limit: None,
+            offset: None,
+            target: PhantomData,
+        }
+    }
+
+    /// Adds a join, like `JOIN users USING (user_id)`.
+    pub fn join_raw(mut self, join: impl Into<String>) -> Self {
+        self.joins.push(join.into());
+        self
+    }
+
+    /// Adds a condition, combined with the previous ones with `AND`.
+    ///
+    /// When there are several conditions, each one is wrapped in parentheses.
+    pub fn where_raw(mut self, condition: impl Into<String>) -> Self {
+        self.conditions.push(condition.into());
+        self
+    }
+
+    /// Sets the `ORDER BY` clause, like `created_at DESC`.
+    pub fn order_by_raw(mut self, order_by: impl Into<String>) -> Self {
+        self.order_by = Some(order_by.into());
+        self
+    }
+
+    /// Sets the `LIMIT` clause.
+    pub fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets the `OFFSET` clause.
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Returns the sql of this query.
+    pub fn to_sql(&self) -> String {
+        self.to_string()
+    }
+
+    /// The select list, qualifying the columns of the table and quoting the others.
+    fn columns(&self) -> Vec<String> {
+        column_list::<T>(true)
+    }
+}
+
+// implemented by hand, since deriving would require `T: Clone`
+impl<T> Clone for Select<T> {
+    fn clone(&self) -> Self {
+        Self {
+            joins: self.joins.clone(),
+            conditions: self.conditions.clone(),
+            order_by: self.order_by.clone(),
+            limit: self.limit,
+            offset: self.offset,
+            target: PhantomData,
+        }
+    }
+}
+
+impl<T: Table> Default for Select<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Table> fmt::Display for Select<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SELECT {} FROM {}", self.columns().join(", "), T::TABLE)?;
+        for join in &self.joins {
+            write!(f, " {join}")?;
+        }
+        match &self.conditions[..] {
+            [] => {}
+            [condition] => write!(f, " WHERE {condition}")?,
+            conditions => {
+                for (i, condition) in conditions.iter().enumerate() {
+                    let keyword = if i == 0 { "WHERE" } else { "AND" };
+                    write!(f, " {keyword} ({condition})")?;
+                }
+            }
+        }
+        if let Some(order_by) = &self.order_by {
+            write!(f, " ORDER BY {order_by}")?;
+        }
+        if let Some(limit) = self.limit {
+            write!(f, " LIMIT {limit}")?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, " OFFSET {offset}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+//! Conversions of the text-format rows returned by `simple_query`, enabled by the `simple` feature.
+//!
+//! Structs opt in with `#[from_row(simple_query)]`, which implements [`FromSimpleRow`] next to `FromRow`,
+//! reading each column by name and parsing it with [`FromText`]:
+//!
+//! ```ignore
+//! #[derive(FromRow)]
+//! #[from_row(simple_query)]
+//! struct User {
+//!     user_id: i32,
+//!     username: String,
+//! }
+//!
+//! let messages = client.simple_query("SELECT user_id, username FROM users").await?;
+//! let users = User::try_from_simple_messages(&messages)?;
+//! ```
+//!
+//! Joined fields and `#[from_row(context)]` are not supported.
+
+use tokio_postgres::{SimpleQueryMessage, SimpleQueryRow};
+
+use crate::{Error, FromRow};
+
+/// A type that can be parsed from the text format postgres uses for a column, `None` being `NULL`.
+pub trait FromText: Sized {
+    fn from_text(text: Option<&str>) -> Result<Self, Error>;
+}
+
+/// Returns the error of reading a `NULL` into a type that can't hold it.
+fn unexpected_null<T>() -> Error {
+    Error::conversion(format!(
+        "unexpected NULL for `{}`",
+        std::any::type_name::<T>()
+    ))
+}
+
+macro_rules! impl_from_text_with_parse {
+    ($($T:ty),*) => {
+        $(
+            impl FromText for $T {
+                fn from_text(text: Option<&str>) -> Result<Self, Error> {
+                    text.ok_or_else(unexpected_null::<Self>)?
+                        .parse()
+                        .map_err(Error::conversion)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_text_with_parse!(i16, i32, i64, u32, f32, f64);
+
+impl FromText for String {
+    fn from_text(text: Option<&str>) -> Result<Self, Error> {
+        text.map(str::to_string).ok_or_else(unexpected_null::<Self>)
+    }
+}
+
+impl FromText for bool {
+    fn from_text(text: Option<&str>) -> Result<Self, Error> {
+        match text.ok_or_else(unexpected_null::<Self>)? {
+            "t" => Ok(true),
+            "f" => Ok(false),
+            text => Err(Error::conversion(format!("invalid bool `{text}`"))),
+        }
+    }
+}
+
+/// Parses the hex format of `bytea`, like `\x0a1b`.
+impl FromText for Vec<u8> {
+    fn from_text(text: Option<&str>) -> Result<Self, Error> {
+        let text = text.ok_or_else(unexpected_null::<Self>)?;
+        let invalid = || Error::conversion(format!("invalid bytea `{text}`"));
+        let hex = text.strip_prefix("\\x").ok_or_else(invalid)?;
+        if hex.len() % 2 != 0 {
+            return Err(invalid());
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                u8::from_str_radix(hex.get(i..i + 2).ok_or_else(invalid)?, 16)
+                    .map_err(|_| invalid())
+            })
+            .collect()
+    }
+}
+
+impl<T: FromText> FromText for Option<T> {
+    fn from_text(text: Option<&str>) -> Result<Self, Error> {
+        text.map(|text| T::from_text(Some(text))).transpose()
+    }
+}
+
+/// Reads the column with this name, used by the code generated for `#[from_row(simple_query)]`.
+pub fn get<T: FromText>(row: &SimpleQueryRow, column: &str) -> Result<T, Error> {
+    T::from_text(row.try_get(column)?)
+        .map_err(|error| Error::conversion(format!("error converting column `{column}`: {error}")))
+}
+
+/// A [`FromRow`] type that can also be converted from the text-format rows of `simple_query`,
+/// implemented by `#[from_row(simple_query)]`.
+pub trait FromSimpleRow: FromRow {
+    /// Try's to perform the conversion, reading the columns by name.
+    fn try_from_simple_row(row: &SimpleQueryRow) -> Result<Self, Error>;
+
+    /// Perform the conversion, reading the columns by name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a column is missing or could not be parsed.
+    fn from_simple_row(row: &SimpleQueryRow) -> Self {
+        Self::try_from_simple_row(row).expect("could not convert column")
+    }
+
+    /// Try's to convert every row of the messages returned by `simple_query`, ignoring the other messages.
+    fn try_from_simple_messages(messages: &[SimpleQueryMessage]) -> Result<Vec<Self>, Error> {
+        messages
+            .iter()
+            .filter_map(|message| match message {
+                SimpleQueryMessage::Row(row) => Some(Self::try_from_simple_row(row)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// `None` when all the columns of `T` are null, like the `FromRow` implementation of `Option<T>`.
+impl<T: FromSimpleRow> FromSimpleRow for Option<T> {
+    fn try_from_simple_row(row: &SimpleQueryRow) -> Result<Self, Error> {
+        let mut null = false;
+        for name in T::report_expected_columns()
+            .iter()
+            .filter_map(|c| c.column_name())
+        {
+            if row.try_get(name)?.is_some() {
+                return T::try_from_simple_row(row).map(Some);
+            }
+            null = true;
+        }
+        if null {
+            return Ok(None);
+        }
+        T::try_from_simple_row(row).map(Some)
+    }
+}
@@ -0,0 +1,86 @@
+use std::marker::PhantomData;
+
+use tokio_postgres::{types::ToSql, GenericClient, Statement};
+
+use crate::{Error, FromRow};
+
+/// A prepared statement whose columns were validated against `T`, created by [`prepare_as`].
+///
+/// Since the columns are only validated once, when the statement is prepared, executing it
+/// converts the rows without validating them again.
+pub struct TypedStatement<'a, T, C: ?Sized> {
+    client: &'a C,
+    statement: Statement,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Prepares `sql` on `client` and validates the columns of the statement against `T`.
+///
+/// ```ignore
+/// let by_author = prepare_as::<Todo, _>(&client, "SELECT todo_id, text FROM todos WHERE author_id = $1").await?;
+/// let todos = by_author.query(&[&author_id]).await?;
+/// ```
+///
+/// # Panics
+///
+/// Panics if the statement does not return the expected column names.
+pub async fn prepare_as<'a, T, C>(
+    client: &'a C,
+    sql: &str,
+) -> Result<TypedStatement<'a, T, C>, Error>
+where
+    T: FromRow,
+    C: GenericClient + ?Sized,
+{
+    let statement = client.prepare(sql).await?;
+    T::assert_matches_statement(&statement);
+    Ok(TypedStatement {
+        client,
+        statement,
+        _marker: PhantomData,
+    })
+}
+
+impl<'a, T: FromRow, C: GenericClient + ?Sized> TypedStatement<'a, T, C> {
+    /// The prepared statement.
+    pub fn statement(&self) -> &Statement {
+        &self.statement
+    }
+
+    /// The client the statement was prepared on.
+    pub fn client(&self) -> &'a C {
+        self.client
+    }
+
+    /// Executes the statement, converting all the rows.
+    pub async fn query(&self, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<T>, Error> {
+        let rows = self.client.query(&self.statement, params).await?;
+        T::try_from_slice_unchecked(&rows)
+    }
+
+    /// Executes the statement, converting the only row it returns.
+    ///
+    /// Returns an error if the statement does not return exactly one row.
+    pub async fn query_one(&self, params: &[&(dyn ToSql + Sync)]) -> Result<T, Error> {
+        let row = self.client.query_one(&self.statement, params).await?;
+        T::try_from_row_unchecked(&row)
+    }
+
+    /// Executes the statement, converting the row it returns, if any.
+    ///
+    /// Returns an error if the statement returns more than one row.
+    pub async fn query_opt(&self, params: &[&(dyn ToSql + Sync)]) -> Result<Option<T>, Error> {
+        let row = self.client.query_opt(&self.statement, params).await?;
+        row.map(T::try_from_row_unchecked).transpose()
+    }
+}
+
+impl<T, C: ?Sized> Clone for TypedStatement<'_, T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client,
+            statement: self.statement.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Conversions of the rows of a [`RowStream`](tokio_postgres::RowStream) in chunks, enabled by the `stream` feature.
+//!
+//! ```ignore
+//! let rows = client.query_raw("SELECT todo_id, text FROM todos", params).await?;
+//! let mut chunks = std::pin::pin!(chunks_as::<Todo, _>(rows, 500));
+//! while let Some(todos) = chunks.try_next().await? {
+//!     index(&todos).await?;
+//! }
+//! ```
+
+use std::pin::Pin;
+
+use futures_util::{Stream, StreamExt};
+use tokio_postgres::Row;
+
+use crate::{batch::Batcher, Error, FromRow};
+
+/// Converts the rows of `rows` to `T`, in chunks of `size` values, for consumers that process values in batches.
+///
+/// Values that join several rows are never split across chunks, a value is only added to a chunk once
+/// the first row of the next value is seen, or when the rows run out. Every chunk has `size` values,
+/// except the last one, which may have fewer.
+///
+/// After an error is returned, the stream ends.
+///
+/// # Panics
+///
+/// Panics if `size` is zero, or when the first chunk is requested if the rows do not contain the expected column names.
+pub fn chunks_as<T, S>(rows: S, size: usize) -> impl Stream<Item = Result<Vec<T>, Error>>
+where
+    T: FromRow,
+    S: Stream<Item = Result<Row, tokio_postgres::Error>>,
+{
+    assert!(size > 0, "the chunk size must be positive");
+    let chunks = Chunks {
+        rows: Box::pin(rows),
+        checked: false,
+        batcher: Batcher::new(),
+    };
+    futures_util::stream::unfold(chunks, move |mut chunks| async move {
+        let chunk = chunks.next_chunk(size).await?;
+        Some((chunk, chunks))
+    })
+}
+
+struct Chunks<S, T> {
+    rows: Pin<Box<S>>,
+    checked: bool,
+    batcher: Batcher<T>,
+}
+
+impl<S, T> Chunks<S, T>
+where
+    T: FromRow,
+    S: Stream<Item = Result<Row, tokio_postgres::Error>>,
+{
+    async fn next_chunk(&mut self, size: usize) -> Option<Result<Vec<T>, Error>> {
+        let mut chunk = Vec::with_capacity(size);
+        while chunk.len() < size && !self.batcher.is_done() {
+            let row = match self.rows.next().await {
+                Some(Ok(row)) => row,
+                Some(Err(error)) => {
+                    self.batcher.stop();
+                    return Some(Err(error.into()));
+                }
+                None => {
+                    self.batcher.finish(&mut chunk);
+                    break;
+                }
+            };
+            if !self.checked {
+                T::assert_matches(row.columns());
+                self.checked = true;
+            }
+            if let Err(error) = self.batcher.push_row(&row, &mut chunk) {
+                return Some(Err(error));
+            }
+        }
+        (!chunk.is_empty()).then_some(Ok(chunk))
+    }
+}
@@ -0,0 +1,121 @@
+//! Lazy conversions for the synchronous `postgres` crate, enabled by the `sync` feature.
+//!
+//! The rows of `postgres::Client::query_raw` are a [`FallibleIterator`], [`RowIterExt::rows_as`]
+//! converts them one value at a time, joining them like [`FromRow::try_from_slice`]:
+//!
+//! ```ignore
+//! use postgres_from_row::sync::{FallibleIterator, RowIterExt};
+//!
+//! let mut todos = client.query_raw("SELECT todo_id, text FROM todos", params)?.rows_as::<Todo>();
+//! while let Some(todo) = todos.next()? {
+//!     println!("{}", todo.text);
+//! }
+//! ```
+
+pub use fallible_iterator::FallibleIterator;
+use tokio_postgres::Row;
+
+use crate::{context, Error, FromRow, RowError};
+
+/// Converts the rows of a [`FallibleIterator`], such as `postgres::RowIter`, one value at a time,
+/// created by [`RowIterExt::rows_as`].
+///
+/// Values that join several rows are only yielded once the first row of the next value is seen,
+/// or when the rows run out.
+///
+/// After an error is returned, the iterator stops.
+pub struct FromRowIter<I, T> {
+    rows: I,
+    current: Option<T>,
+    index: usize,
+    checked: bool,
+    done: bool,
+}
+
+impl<I, T> FromRowIter<I, T> {
+    pub fn new(rows: I) -> Self {
+        Self {
+            rows,
+            current: None,
+            index: 0,
+            checked: false,
+            done: false,
+        }
+    }
+
+    /// Returns the iterator of the rows, dropping the value that was being joined, if any.
+    pub fn into_inner(self) -> I {
+        self.rows
+    }
+}
+
+impl<I, T> FallibleIterator for FromRowIter<I, T>
+where
+    I: FallibleIterator<Item = Row, Error = tokio_postgres::Error>,
+    T: FromRow,
+{
+    type Item = T;
+    type Error = Error;
+
+    /// # Panics
+    ///
+    /// Panics when the first value is requested if the row does not contain the expected column names.
+    fn next(&mut self) -> Result<Option<T>, Error> {
+        if self.done {
+            return Ok(None);
+        }
+        loop {
+            let row = match self.rows.next() {
+                Ok(Some(row)) => row,
+                Ok(None) => break,
+                Err(error) => {
+                    self.done = true;
+                    self.current = None;
+                    return Err(error.into());
+                }
+            };
+            if !self.checked {
+                T::assert_matches(row.columns());
+                self.checked = true;
+            }
+            let _row_index = context::RowIndexGuard::new(self.index);
+            self.index += 1;
+            match T::try_from_row_joined(self.current.as_mut(), &row, 0) {
+                Ok(Some(next)) if !T::JOINED => return Ok(Some(next)),
+                Ok(Some(next)) => {
+                    if let Some(previous) = self.current.replace(next) {
+                        return Ok(Some(previous));
+                    }
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    self.done = true;
+                    self.current = None;
+                    return Err(RowError::wrap(error, self.index - 1, Some(&row)));
+                }
+            }
+        }
+        self.done = true;
+        Ok(self.current.take())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        // an error ends the iteration early, so only one value is certain
+        let (rows, max) = self.rows.size_hint();
+        let current = self.current.is_some() as usize;
+        ((rows + current > 0) as usize, max.map(|max| max + current))
+    }
+}
+
+/// Adds [`RowIterExt::rows_as`] to iterators of rows, such as `postgres::RowIter`.
+pub trait RowIterExt: FallibleIterator<Item = Row, Error = tokio_postgres::Error> + Sized {
+    /// Converts the rows to `T` as they are read.
+    fn rows_as<T: FromRow>(self) -> FromRowIter<Self, T> {
+        FromRowIter::new(self)
+    }
+}
+
+impl<I: FallibleIterator<Item = Row, Error = tokio_postgres::Error>> RowIterExt for I {}
@@ -0,0 +1,174 @@
+use std::collections::BTreeSet;
+
+use crate::{FromRow, Select};
+
+/// A [`FromRow`] type read from a single table, implemented by `#[from_row(table = "..")]`.
+///
+/// The statements only write the columns of the struct's own fields,
+/// flattened and joined fields are read from other tables and are left out.
+pub trait Table: FromRow {
+    /// The name of the table, as written in the attribute.
+    const TABLE: &'static str;
+
+    /// The columns of the table read by this type, in field order, quoted when needed.
+    const TABLE_COLUMNS: &'static [&'static str];
+
+    /// The columns of the fields marked with `#[from_row(primary_key)]`, in field order, quoted when needed.
+    const PRIMARY_KEY: &'static [&'static str];
+
+    /// Inserts a row, with one parameter per column in the order of [`Table::TABLE_COLUMNS`]:
+    ///
+    /// ```sql
+    /// INSERT INTO users (user_id, username) VALUES ($1, $2)
+    /// ```
+    fn insert_sql() -> &'static str;
+
+    /// Like [`Table::insert_sql`], returning every column this type reads, in order, so the row can be converted.
+    ///
+    /// The columns of flattened and joined fields are returned by name, so the table must have them too:
+    ///
+    /// ```sql
+    /// INSERT INTO users (user_id, username) VALUES ($1, $2) RETURNING user_id, username
+    /// ```
+    fn insert_returning_sql() -> String {
+        let columns = column_list::<Self>(false);
+        let returning = if columns.is_empty() {
+            "*".to_string()
+        } else {
+            columns.join(", ")
+        };
+        format!("{} RETURNING {returning}", Self::insert_sql())
+    }
+
+    /// Starts building a `SELECT` of the columns of this type, see [`Select`].
+    fn select() -> Select<Self> {
+        Select::new()
+    }
+
+    /// Updates the `changed` columns of the row with the primary key, returning `None` if nothing changed.
+    ///
+    /// The parameters are the changed columns in the order of [`Table::TABLE_COLUMNS`],
+    /// followed by the columns of the primary key:
+    ///
+    /// ```sql
+    /// UPDATE users SET username = $1 WHERE user_id = $2
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if this type has no primary key, or `changed` contains a column that is not in [`Table::TABLE_COLUMNS`].
+    fn update_sql(changed: &FieldMask) -> Option<String> {
+        assert!(
+            !Self::PRIMARY_KEY.is_empty(),
+            "`{}` has no `#[from_row(primary_key)]` field",
+            std::any::type_name::<Self>()
+        );
+        if let Some(unknown) = changed
+            .columns
+            .iter()
+            .find(|c| !Self::TABLE_COLUMNS.iter().any(|t| unquote(t) == **c))
+        {
+            panic!("`{unknown}` is not a column of `{}`", Self::TABLE);
+        }
+        let set = Self::TABLE_COLUMNS
+            .iter()
+            .filter(|c| changed.contains(&unquote(c)) && !Self::PRIMARY_KEY.contains(c))
+            .collect::<Vec<_>>();
+        if set.is_empty() {
+            return None;
+        }
+        let assignments = set
+            .iter()
+            .enumerate()
+            .map(|(i, column)| format!("{column} = ${}", i + 1))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let predicate = Self::PRIMARY_KEY
+            .iter()
+            .enumerate()
+            .map(|(i, column)| format!("{column} = ${}", set.len() + i + 1))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        Some(format!(
+            "UPDATE {} SET {assignments} WHERE {predicate}",
+            Self::TABLE
+        ))
+    }
+}
+
+/// A set of columns that changed, given to [`Table::update_sql`].
+///
+/// The columns are named like in the database, not like the fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldMask {
+    columns: BTreeSet<String>,
+}
+
+impl FieldMask {
+    /// An empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a column to the set.
+    pub fn with(mut self, column: impl Into<String>) -> Self {
+        self.insert(column);
+        self
+    }
+
+    /// Adds a column to the set.
+    pub fn insert(&mut self, column: impl Into<String>) {
+        self.columns.insert(column.into());
+    }
+
+    /// Whether the column is in the set.
+    pub fn contains(&self, column: &str) -> bool {
+        self.columns.contains(column)
+    }
+
+    /// Whether no column is in the set.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for FieldMask {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Self {
+            columns: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// The columns expected by `T`, in order, written like in [`Table::TABLE_COLUMNS`] for the columns of the table,
+/// and always quoted for the columns of flattened and joined fields.
+///
+/// With `qualify`, the columns of the table are qualified with its name.
+pub(crate) fn column_list<T: Table>(qualify: bool) -> Vec<String> {
+    let mut own = T::TABLE_COLUMNS.iter().peekable();
+    T::report_expected_columns()
+        .iter()
+        .map(|column| {
+            let name = column.column_name().unwrap_or("?column?");
+            match own.peek() {
+                Some(&&quoted) if unquote(quoted) == name => {
+                    own.next();
+                    if qualify {
+                        format!("{}.{quoted}", T::TABLE)
+                    } else {
+                        quoted.to_string()
+                    }
+                }
+                _ => format!("\"{}\"", name.replace('"', "\"\"")),
+            }
+        })
+        .collect()
+}
+
+/// Removes the quotes of an identifier of [`Table::TABLE_COLUMNS`].
+pub(crate) fn unquote(quoted: &str) -> String {
+    match quoted.strip_prefix('"').and_then(|q| q.strip_suffix('"')) {
+        Some(inner) => inner.replace("\"\"", "\""),
+        None => quoted.to_string(),
+    }
+}
@@ -0,0 +1,653 @@
+//! Rows built by hand, to test conversions without a database, enabled by the `test-util` feature.
+//!
+//! `tokio_postgres::Row` can't be constructed outside of `tokio_postgres`, so structs opt in with
+//! `#[from_row(mock)]`, which implements [`FromMockRow`] next to `FromRow`, performing the same conversion
+//! (including `from_fn` converters and joins) on a [`MockRow`] instead:
+//!
+//! ```ignore
+//! #[derive(FromRow)]
+//! #[cfg_attr(test, from_row(mock))]
+//! struct Todo {
+//!     todo_id: i32,
+//!     text: String,
+//! }
+//!
+//! let row = MockRow::new().column("todo_id", 1).column("text", "wash the dishes");
+//! let todo = Todo::try_from_mock_row(&row)?;
+//! ```
+//!
+//! `#[from_row(context)]` is not supported, since `ConversionContext` refers to a real row.
+
+use std::{collections::BTreeMap, ops::Range};
+
+use bytes::BytesMut;
+use tokio_postgres::types::{FromSql, FromSqlOwned, IsNull, ToSql, Type};
+
+use crate::{
+    context::RowIndexGuard, matching, report, ColumnMismatch, Error, FromRow, MatchError,
+    MatchPolicy, MismatchReport, Paginated, RowError, ViaTryFrom,
+};
+
+/// A row built column by column, with the values encoded in the binary format like the ones sent by postgres.
+#[derive(Debug, Clone, Default)]
+pub struct MockRow {
+    columns: Vec<MockColumn>,
+}
+
+#[derive(Debug, Clone)]
+struct MockColumn {
+    name: String,
+    ty: Type,
+    value: Option<Vec<u8>>,
+}
+
+impl matching::NamedColumn for MockColumn {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn type_(&self) -> &Type {
+        &self.ty
+    }
+}
+
+/// The first built-in type `T` can be written as, the type of a mock column when it is not given.
+fn guess_type<T: ToSql>() -> Type {
+    // oids below this are reserved for the objects built into postgres
+    const FIRST_NORMAL_OBJECT_ID: u32 = 16384;
+    (0..FIRST_NORMAL_OBJECT_ID)
+        .filter_map(Type::from_oid)
+        .find(|ty| T::accepts(ty))
+        .unwrap_or(Type::UNKNOWN)
+}
+
+impl MockRow {
+    /// A row without columns.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a column with this value, its type is the first built-in type the value can be written as.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value can't be encoded.
+    pub fn column<T: ToSql>(self, name: impl Into<String>, value: T) -> Self {
+        self.column_with_type(name, guess_type::<T>(), value)
+    }
+
+    /// Adds a column of type `ty` with this value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value can't be encoded as `ty`.
+    pub fn column_with_type<T: ToSql>(
+        mut self,
+        name: impl Into<String>,
+        ty: Type,
+        value: T,
+    ) -> Self {
+        let mut buf = BytesMut::new();
+        let value = match value.to_sql_checked(&ty, &mut buf) {
+            Ok(IsNull::No) => Some(buf.to_vec()),
+            Ok(IsNull::Yes) => None,
+            Err(error) => panic!("could not encode the mock column as `{ty}`: {error}"),
+        };
+        self.columns.push(MockColumn {
+            name: name.into(),
+            ty,
+            value,
+        });
+        self
+    }
+
+    /// Adds a `NULL` column, its type is the first built-in type `T` can be written as.
+    pub fn null<T: ToSql>(mut self, name: impl Into<String>) -> Self {
+        self.columns.push(MockColumn {
+            name: name.into(),
+            ty: guess_type::<T>(),
+            value: None,
+        });
+        self
+    }
+
+    /// The number of columns of this row.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Whether this row has no columns.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// The name of the column at `index`.
+    pub fn column_name(&self, index: usize) -> Option<&str> {
+        self.columns.get(index).map(|column| column.name.as_str())
+    }
+
+    /// The type of the column at `index`.
+    pub fn column_type(&self, index: usize) -> Option<&Type> {
+        self.columns.get(index).map(|column| &column.ty)
+    }
+
+    /// Decodes the column at `index`, like `Row::try_get`.
+    pub fn try_get<'a, T: FromSql<'a>>(&'a self, index: usize) -> Result<T, Error> {
+        let column = self.columns.get(index).ok_or_else(|| {
+            Error::conversion(format!(
+                "invalid column {index}, the mock row has {} columns",
+                self.columns.len()
+            ))
+        })?;
+        if !T::accepts(&column.ty) {
+            return Err(Error::conversion(format!(
+                "error converting column `{}`: `{}` can't be decoded from `{}`",
+                column.name,
+                std::any::type_name::<T>(),
+                column.ty
+            )));
+        }
+        match &column.value {
+            Some(raw) => T::from_sql(&column.ty, raw),
+            None => T::from_sql_null(&column.ty),
+        }
+        .map_err(Error::conversion)
+    }
+
+    /// Decodes the first column named `name`, like `Row::try_get` with a name.
+    pub fn try_get_by_name<'a, T: FromSql<'a>>(&'a self, name: &str) -> Result<T, Error> {
+        self.try_get(self.index_of(name)?)
+    }
+
+    /// The index of the first column named `name`.
+    fn index_of(&self, name: &str) -> Result<usize, Error> {
+        self.columns
+            .iter()
+            .position(|column| column.name == name)
+            .ok_or_else(|| {
+                Error::conversion(format!(
+                    "invalid column `{name}`, the mock row has no such column"
+                ))
+            })
+    }
+
+    /// Decodes the column at `index`, like `Row::get`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column does not exist or can't be decoded as `T`.
+    pub fn get<'a, T: FromSql<'a>>(&'a self, index: usize) -> T {
+        self.try_get(index).expect("could not convert column")
+    }
+
+    /// Verifies that the names and count of the columns match what `T` expects, panics on error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the columns don't match, listing the columns that were expected and found.
+    pub fn assert_matches<T: FromRow>(&self) {
+        self.assert_matches_range::<T>(0..self.columns.len());
+    }
+
+    /// Checks that the columns match what `T` expects, as strictly as `policy` requires,
+    /// like [`FromRow::try_assert_matches_with`].
+    pub fn check_matches_with<T: FromRow>(&self, policy: &MatchPolicy) -> Result<(), MatchError> {
+        let expected = T::report_expected_columns();
+        matching::check_with_policy(&expected, &self.columns, policy)
+    }
+
+    /// Compares the columns with the columns expected by `T`, like [`report_expected_columns_mismatch`](crate::report_expected_columns_mismatch).
+    pub fn report_mismatch<T: FromRow>(&self) -> MismatchReport {
+        let found: Vec<_> = self
+            .columns
+            .iter()
+            .map(|column| (column.name.as_str(), &column.ty))
+            .collect();
+        report::report_named_columns_mismatch(&found, &T::report_expected_columns())
+    }
+
+    /// Returns only the differences between the columns and the columns expected by `T`,
+    /// like [`diff_columns`](crate::diff_columns).
+    pub fn diff_columns<T: FromRow>(&self) -> Vec<ColumnMismatch> {
+        report::diff_entries(self.report_mismatch::<T>().entries().to_vec())
+    }
+
+    /// Like [`MockRow::assert_matches`], for the columns in `range`.
+    fn assert_matches_range<T: FromRow>(&self, range: Range<usize>) {
+        let columns = &self.columns[range];
+        let expected = T::report_expected_columns();
+        let matches = expected.len() == columns.len()
+            && expected.iter().zip(columns).all(|(expected, found)| {
+                expected.column_name().is_none_or(|name| name == found.name)
+                    && expected.accepts(&found.ty)
+            });
+        if !matches {
+            let expected = expected
+                .iter()
+                .map(|column| {
+                    format!(
+                        "{} {}",
+                        column.column_name().unwrap_or("?column?"),
+                        column.describe_type()
+                    )
+                })
+                .collect::<Vec<_>>();
+            let found = columns
+                .iter()
+                .map(|column| format!("{} {}", column.name, column.ty))
+                .collect::<Vec<_>>();
+            panic!(
+                "the mock row does not match `{}`\nexpected: {}\nfound:    {}",
+                std::any::type_name::<T>(),
+                expected.join(", "),
+                found.join(", ")
+            );
+        }
+    }
+}
+
+/// Checks whether the `count` columns starting at `index` are all null, used by `#[from_row(join, left)]`.
+#[doc(hidden)]
+pub fn columns_are_null(row: &MockRow, index: usize, count: usize) -> Result<bool, Error> {
+    for i in index..index + count {
+        let column = row.columns.get(i).ok_or_else(|| {
+            Error::conversion(format!(
+                "invalid column {i}, the mock row has {} columns",
+                row.columns.len()
+            ))
+        })?;
+        if column.value.is_some() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Whether the `count` columns of `T` starting at `index` are `None` by `policy`, used by `#[from_row(flatten, none_when = "..")]`.
+#[doc(hidden)]
+pub fn group_is_none<T>(
+    row: &MockRow,
+    index: usize,
+    count: usize,
+    policy: crate::__private::NullPolicy,
+) -> Result<bool, Error> {
+    let nulls = (index..index + count)
+        .map(|i| columns_are_null(row, i, 1))
+        .collect::<Result<Vec<_>, Error>>()?;
+    policy.is_none(&nulls, std::any::type_name::<T>())
+}
+
+/// Checks whether the named columns of `expected` are all null, used by `#[from_row(join, left)]` when converting by name.
+#[doc(hidden)]
+pub fn named_columns_are_null(
+    row: &MockRow,
+    expected: &[crate::ExpectedColumn],
+) -> Result<bool, Error> {
+    for name in expected.iter().filter_map(|column| column.column_name()) {
+        if !columns_are_null(row, row.index_of(name)?, 1)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Whether the named columns of `T` are `None` by `policy`, used by `#[from_row(flatten, none_when = "..")]` when converting by name.
+#[doc(hidden)]
+pub fn named_group_is_none<T>(
+    row: &MockRow,
+    expected: &[crate::ExpectedColumn],
+    policy: crate::__private::NullPolicy,
+) -> Result<bool, Error> {
+    let nulls = expected
+        .iter()
+        .filter_map(|column| column.column_name())
+        .map(|name| columns_are_null(row, row.index_of(name)?, 1))
+        .collect::<Result<Vec<_>, Error>>()?;
+    policy.is_none(&nulls, std::any::type_name::<T>())
+}
+
+/// A [`FromRow`] type that can also be converted from a [`MockRow`], implemented by `#[from_row(mock)]`.
+pub trait FromMockRow: FromRow {
+    /// Like [`FromRow::try_from_row_joined`], reading from a mock row.
+    fn try_from_mock_row_joined(
+        last: Option<&mut Self>,
+        row: &MockRow,
+        index: usize,
+    ) -> Result<Option<Self>, Error>;
+
+    /// Try's to perform the conversion.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not contain the expected columns, see [`MockRow::assert_matches`].
+    fn try_from_mock_row(row: &MockRow) -> Result<Self, Error> {
+        row.assert_matches::<Self>();
+        Self::try_from_mock_row_joined(None, row, 0).map(|x| {
+            x.expect(
+                "when try_from_row_joined is called with last = None it should never return None",
+            )
+        })
+    }
+
+    /// Try's to perform the conversion like [`FromMockRow::try_from_mock_row`], returning an [`Error::Mismatch`]
+    /// with the report of [`MockRow::report_mismatch`] instead of panicking, like [`FromRow::from_row_checked`].
+    fn from_mock_row_checked(row: &MockRow) -> Result<Self, Error> {
+        if row
+            .check_matches_with::<Self>(&MatchPolicy::STRICT)
+            .is_err()
+        {
+            return Err(Error::Mismatch(Box::new(row.report_mismatch::<Self>())));
+        }
+        Self::try_from_mock_row(row)
+    }
+
+    /// Try's to perform the conversion by looking up each column by its name, like [`FromRow::try_from_row_by_name`].
+    ///
+    /// Types that don't know the names of their columns (like tuples) perform the conversion by index.
+    fn try_from_mock_row_by_name(row: &MockRow) -> Result<Self, Error> {
+        Self::try_from_mock_row(row)
+    }
+
+    /// Try's to perform the conversion, verifying the columns with [`MockRow::check_matches_with`],
+    /// like [`FromRow::try_from_row_with_policy`].
+    ///
+    /// The columns are read by index when the policy requires them to be in order without extra columns,
+    /// and by name with [`FromMockRow::try_from_mock_row_by_name`] otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row does not contain the expected columns, as strictly as `policy` requires.
+    fn try_from_mock_row_with_policy(row: &MockRow, policy: &MatchPolicy) -> Result<Self, Error> {
+        if let Err(error) = row.check_matches_with::<Self>(policy) {
+            panic!(
+                "the mock row does not match `{}`: {error}",
+                std::any::type_name::<Self>()
+            );
+        }
+        if policy.reads_by_index() {
+            Self::try_from_mock_row_joined(None, row, 0).map(|x| {
+                x.expect(
+                    "when try_from_row_joined is called with last = None it should never return None",
+                )
+            })
+        } else {
+            Self::try_from_mock_row_by_name(row)
+        }
+    }
+
+    /// Try's to perform the conversion of a row with more columns than this type expects,
+    /// like [`FromRow::try_from_row_subset`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the expected columns don't appear in order within the columns of the row.
+    fn try_from_mock_row_subset(row: &MockRow) -> Result<Self, Error> {
+        Self::try_from_mock_row_with_policy(row, &MatchPolicy::SUBSET)
+    }
+
+    /// Try's to perform the conversion starting at the column `offset`, like [`FromRow::try_from_row_at`],
+    /// returning the value and the offset of the first column after the ones it read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the columns starting at `offset` do not contain the expected columns.
+    fn try_from_mock_row_at(row: &MockRow, offset: usize) -> Result<(Self, usize), Error> {
+        let end = (offset + Self::COLUMN_COUNT).min(row.len());
+        row.assert_matches_range::<Self>(offset.min(end)..end);
+        let this = Self::try_from_mock_row_joined(None, row, offset)?.expect(
+            "when try_from_row_joined is called with last = None it should never return None",
+        );
+        Ok((this, offset + Self::COLUMN_COUNT))
+    }
+
+    /// Try's to perform the conversion on a slice of rows, joining them like [`FromRow::try_from_slice`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the first row does not contain the expected columns, see [`MockRow::assert_matches`].
+    fn try_from_mock_rows(rows: &[MockRow]) -> Result<Vec<Self>, Error> {
+        if let [first, ..] = rows {
+            first.assert_matches::<Self>();
+        }
+        let mut vec = Vec::with_capacity(rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            let _row_index = RowIndexGuard::new(i);
+            if let Some(this) = Self::try_from_mock_row_joined(vec.last_mut(), row, 0)
+                .map_err(|error| RowError::wrap(error, i, None))?
+            {
+                vec.push(this);
+            }
+        }
+        Ok(vec)
+    }
+}
+
+/// Converts the row with `T`'s `TryFrom<&MockRow>`, like the `FromRow` implementation with `TryFrom<&Row>`.
+impl<T> FromMockRow for ViaTryFrom<T>
+where
+    T: for<'a> TryFrom<&'a tokio_postgres::Row> + for<'a> TryFrom<&'a MockRow>,
+    for<'a> <T as TryFrom<&'a tokio_postgres::Row>>::Error:
+        Into<Box<dyn std::error::Error + Send + Sync>>,
+    for<'a> <T as TryFrom<&'a MockRow>>::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    fn try_from_mock_row_joined(
+        _: Option<&mut Self>,
+        row: &MockRow,
+        index: usize,
+    ) -> Result<Option<Self>, Error> {
+        if index != 0 {
+            return Err(Error::conversion(format!(
+                "`{}` reads the whole row, it can't be read from column {index}",
+                std::any::type_name::<T>()
+            )));
+        }
+        T::try_from(row)
+            .map(|this| Some(ViaTryFrom(this)))
+            .map_err(Error::conversion)
+    }
+
+    // the columns read by the mapper are not known, so they are not checked
+    fn try_from_mock_row(row: &MockRow) -> Result<Self, Error> {
+        T::try_from(row).map(ViaTryFrom).map_err(Error::conversion)
+    }
+
+    fn from_mock_row_checked(row: &MockRow) -> Result<Self, Error> {
+        Self::try_from_mock_row(row)
+    }
+
+    fn try_from_mock_rows(rows: &[MockRow]) -> Result<Vec<Self>, Error> {
+        rows.iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let _row_index = RowIndexGuard::new(i);
+                Self::try_from_mock_row(row).map_err(|error| RowError::wrap(error, i, None))
+            })
+            .collect()
+    }
+}
+
+/// `None` when all the columns of `T` are null, like the `FromRow` implementation of `Option<T>`.
+impl<T: FromMockRow> FromMockRow for Option<T> {
+    fn try_from_mock_row_joined(
+        last: Option<&mut Self>,
+        row: &MockRow,
+        index: usize,
+    ) -> Result<Option<Self>, Error> {
+        if T::COLUMN_COUNT != 0 && columns_are_null(row, index, T::COLUMN_COUNT)? {
+            crate::__private::null_row();
+            return Ok(Some(None));
+        }
+        let this = T::try_from_mock_row_joined(last.and_then(|l| l.as_mut()), row, index)?;
+        Ok(this.map(Some))
+    }
+
+    /// Also `None` when none of the columns of `T` are present in the row.
+    fn try_from_mock_row_by_name(row: &MockRow) -> Result<Self, Error> {
+        let expected = T::report_expected_columns();
+        let absent = !expected.is_empty()
+            && expected.iter().all(|column| {
+                column
+                    .column_name()
+                    .is_some_and(|name| row.index_of(name).is_err())
+            });
+        let named = expected.iter().any(|column| column.column_name().is_some());
+        if absent || named && named_columns_are_null(row, &expected)? {
+            return Ok(None);
+        }
+        T::try_from_mock_row_by_name(row).map(Some)
+    }
+}
+
+/// Joins the rows into the last element, like the `FromRow` implementation of `Vec<T>`.
+impl<T: FromMockRow> FromMockRow for Vec<T> {
+    fn try_from_mock_row_joined(
+        mut last: Option<&mut Self>,
+        row: &MockRow,
+        index: usize,
+    ) -> Result<Option<Self>, Error> {
+        if T::COLUMN_COUNT != 0 && columns_are_null(row, index, T::COLUMN_COUNT)? {
+            crate::__private::null_row();
+            return Ok(last.is_none().then(Vec::new));
+        }
+        let last_item = last.as_deref_mut().and_then(|vec| vec.last_mut());
+        let item = match T::try_from_mock_row_joined(last_item, row, index) {
+            Err(e) if T::COLUMN_COUNT == 0 && e.is_null() => {
+                crate::__private::null_row();
+                return Ok(last.is_none().then(Vec::new));
+            }
+            result => result?,
+        };
+        match last {
+            Some(vec) => {
+                vec.extend(item);
+                Ok(None)
+            }
+            None => Ok(Some(vec![item.expect(
+                "when try_from_row_joined is called with last = None it should never return None",
+            )])),
+        }
+    }
+
+    /// Empty when the named columns of `T` are all null.
+    fn try_from_mock_row_by_name(row: &MockRow) -> Result<Self, Error> {
+        let expected = T::report_expected_columns();
+        let named = expected.iter().any(|column| column.column_name().is_some());
+        if named && named_columns_are_null(row, &expected)? {
+            return Ok(Vec::new());
+        }
+        match T::try_from_mock_row_by_name(row) {
+            Ok(this) => Ok(vec![this]),
+            Err(e) if !named && e.is_null() => Ok(Vec::new()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Groups the rows by a key, like the `FromRow` implementation of `BTreeMap<K, V>`.
+impl<K: FromMockRow + Ord, V: FromMockRow> FromMockRow for BTreeMap<K, V> {
+    fn try_from_mock_row_joined(
+        last: Option<&mut Self>,
+        row: &MockRow,
+        index: usize,
+    ) -> Result<Option<Self>, Error> {
+        let key = match K::try_from_mock_row_joined(None, row, index) {
+            Ok(key) => key.expect(
+                "when try_from_row_joined is called with last = None it should never return None",
+            ),
+            Err(e) if e.is_null() => {
+                crate::__private::null_row();
+                return Ok(last.is_none().then(BTreeMap::new));
+            }
+            Err(e) => return Err(e),
+        };
+        let index = index + K::COLUMN_COUNT;
+        let Some(map) = last else {
+            let value = V::try_from_mock_row_joined(None, row, index)?.expect(
+                "when try_from_row_joined is called with last = None it should never return None",
+            );
+            return Ok(Some(BTreeMap::from([(key, value)])));
+        };
+        if let Some(value) = V::try_from_mock_row_joined(map.get_mut(&key), row, index)? {
+            map.insert(key, value);
+        }
+        Ok(None)
+    }
+
+    fn try_from_mock_row_by_name(row: &MockRow) -> Result<Self, Error> {
+        Ok(BTreeMap::from([(
+            K::try_from_mock_row_by_name(row)?,
+            V::try_from_mock_row_by_name(row)?,
+        )]))
+    }
+}
+
+/// Joins the rows into a single page, like the `FromRow` implementation of `Paginated<T>`.
+impl<T: FromMockRow> FromMockRow for Paginated<T> {
+    fn try_from_mock_row_joined(
+        last: Option<&mut Self>,
+        row: &MockRow,
+        index: usize,
+    ) -> Result<Option<Self>, Error> {
+        let Some(page) = last else {
+            let item = T::try_from_mock_row_joined(None, row, index)?.expect(
+                "when try_from_row_joined is called with last = None it should never return None",
+            );
+            return Ok(Some(Self {
+                items: vec![item],
+                total_count: row.try_get(index + T::COLUMN_COUNT)?,
+            }));
+        };
+        if let Some(item) = T::try_from_mock_row_joined(page.items.last_mut(), row, index)? {
+            page.items.push(item);
+        }
+        Ok(None)
+    }
+
+    fn try_from_mock_row_by_name(row: &MockRow) -> Result<Self, Error> {
+        Ok(Self {
+            items: vec![T::try_from_mock_row_by_name(row)?],
+            total_count: row.try_get_by_name(crate::paginated::TOTAL_COUNT)?,
+        })
+    }
+}
+
+impl<T: FromMockRow> Paginated<T> {
+    /// Try's to convert the mock rows of a page, like [`Paginated::try_from_page`].
+    pub fn try_from_mock_page(rows: &[MockRow]) -> Result<Self, Error> {
+        Ok(Self::try_from_mock_rows(rows)?.pop().unwrap_or(Self {
+            items: Vec::new(),
+            total_count: 0,
+        }))
+    }
+}
+
+macro_rules! impl_from_mock_row_for_tuple {
+    ($($T:ident),*) => {
+        /// Reads one column per element, like the `FromRow` implementation of tuples.
+        impl<$($T: FromSqlOwned),*> FromMockRow for ($($T,)*) {
+            fn try_from_mock_row_joined(_: Option<&mut Self>, row: &MockRow, mut i: usize) -> Result<Option<Self>, Error> {
+                #[allow(unused_assignments)]
+                Ok(Some(($(
+                    row.try_get::<$T>({
+                        let j = i;
+                        i += 1;
+                        j
+                    })?,
+                )*)))
+            }
+        }
+    };
+}
+
+macro_rules! generate_from_mock_row_tuples {
+    () => {};
+    ($head:ident $(, $tail:ident)*) => {
+        impl_from_mock_row_for_tuple!($head $(, $tail)*);
+        generate_from_mock_row_tuples!($($tail),*);
+    };
+}
+
+generate_from_mock_row_tuples!(
+    T31, T30, T29, T28, T27, T26, T25, T24, T23, T22, T21, T20, T19, T18, T17, T16, T15, T14, T13,
+    T12, T11, T10, T9, T8, T7, T6, T5, T4, T3, T2, T1, T0
+);
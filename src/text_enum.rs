@@ -0,0 +1,23 @@
+/// A fieldless enum stored as text, read from `text` or `varchar` columns with `#[from_row(enum_text)]`.
+///
+/// Usually derived, each variant is stored as its name unless renamed:
+///
+/// ```ignore
+/// #[derive(TextEnum)]
+/// enum Status {
+///     #[from_row(rename = "open")]
+///     Open,
+///     #[from_row(rename = "closed")]
+///     Closed,
+/// }
+/// ```
+pub trait TextEnum: Sized {
+    /// The text of every variant, in declaration order.
+    const VARIANTS: &'static [&'static str];
+
+    /// Returns the variant stored as `text`, if any.
+    fn from_text(text: &str) -> Option<Self>;
+
+    /// Returns the text this variant is stored as.
+    fn as_text(&self) -> &'static str;
+}
@@ -0,0 +1,70 @@
+//! Spans and events around conversions, emitted with `tracing` when the `tracing` feature is enabled.
+
+use crate::{Error, MismatchReport};
+
+/// A span around the conversion of one or more rows, entered until it is finished.
+#[cfg(feature = "tracing")]
+pub(crate) struct ConversionSpan {
+    span: tracing::span::EnteredSpan,
+}
+
+#[cfg(feature = "tracing")]
+impl ConversionSpan {
+    #[inline]
+    pub(crate) fn enter<T>(operation: &'static str, rows: usize) -> Self {
+        Self {
+            span: tracing::debug_span!(
+                "postgres_from_row",
+                operation,
+                target_type = std::any::type_name::<T>(),
+                rows,
+                values = tracing::field::Empty,
+            )
+            .entered(),
+        }
+    }
+
+    /// Records the number of values converted, or the error that ended the conversion.
+    #[inline]
+    pub(crate) fn finish<V>(self, result: &Result<V, Error>, values: impl FnOnce(&V) -> usize) {
+        match result {
+            Ok(value) => {
+                self.span.record("values", values(value));
+            }
+            Err(error) => tracing::debug!(%error, "conversion failed"),
+        }
+    }
+}
+
+/// Emits an event with the report of columns that don't match the columns expected by `T`.
+#[cfg(feature = "tracing")]
+#[inline]
+pub(crate) fn mismatch<T>(report: &MismatchReport) {
+    tracing::error!(
+        target_type = std::any::type_name::<T>(),
+        %report,
+        "the columns do not match the columns expected"
+    );
+}
+
+/// A span around the conversion of one or more rows, entered until it is finished.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct ConversionSpan;
+
+#[cfg(not(feature = "tracing"))]
+impl ConversionSpan {
+    // `T` names the type in the span when the `tracing` feature is enabled
+    #[allow(clippy::extra_unused_type_parameters)]
+    #[inline(always)]
+    pub(crate) fn enter<T>(_operation: &'static str, _rows: usize) -> Self {
+        Self
+    }
+
+    #[inline(always)]
+    pub(crate) fn finish<V>(self, _result: &Result<V, Error>, _values: impl FnOnce(&V) -> usize) {}
+}
+
+/// Emits an event with the report of columns that don't match the columns expected by `T`.
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn mismatch<T>(_report: &MismatchReport) {}
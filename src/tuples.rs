@@ -1,10 +1,15 @@
 use tokio_postgres::types::{FromSqlOwned, FromSql};
 
-use crate::FromRow;
+use crate::{AsRow, FromRow};
 
+/// Consumes no columns and accepts any row, so statements that return nothing
+/// (like calls to functions returning `void`) can be used with the generic helpers of this crate.
 impl FromRow for () {
     const COLUMN_COUNT: usize = 0;
-    fn try_from_row_joined(_: Option<&mut Self>, _: &tokio_postgres::Row, _: usize) -> Result<Option<Self>, tokio_postgres::Error> {
+    const COLUMN_NAMES: Option<&'static [Option<&'static str>]> = Some(&[]);
+    const EXPECTED_COLUMNS: Option<&'static [crate::ExpectedColumn]> = Some(&[]);
+    const JOINED: bool = false;
+    fn try_from_row_joined(_: Option<&mut Self>, _: &tokio_postgres::Row, _: usize) -> Result<Option<Self>, crate::Error> {
         Ok(Some(()))
     }
     fn report_expected_columns() -> crate::ExpectedColumns {
@@ -20,11 +25,18 @@ macro_rules! count_ident {
     ($i:ident) => {1};
 }
 
+macro_rules! unnamed_column {
+    ($i:ident) => {None};
+}
+
 macro_rules! impl_from_row_for_tuple {
     ($($T:ident),*) => {
         impl<$($T: FromSqlOwned),*> FromRow for ($($T,)*) {
             const COLUMN_COUNT: usize = 0 $( + count_ident!($T))*;
-            fn try_from_row_joined(_: Option<&mut Self>, row: &tokio_postgres::Row, mut i: usize) -> Result<Option<Self>, tokio_postgres::Error> {
+            const COLUMN_NAMES: Option<&'static [Option<&'static str>]> = Some(&[$(unnamed_column!($T),)*]);
+            const EXPECTED_COLUMNS: Option<&'static [crate::ExpectedColumn]> = Some(&[$(crate::ExpectedColumn::new::<$T>(None),)*]);
+            const JOINED: bool = false;
+            fn try_from_row_joined(_: Option<&mut Self>, row: &tokio_postgres::Row, mut i: usize) -> Result<Option<Self>, crate::Error> {
                 #[allow(unused_assignments)]
                 Ok(Some(($(
                     row.try_get::<_, $T>({
@@ -64,3 +76,123 @@ generate_from_row_tuples!(
     T31, T30, T29, T28, T27, T26, T25, T24, T23, T22, T21, T20, T19, T18, T17, T16, T15, T14, T13,
     T12, T11, T10, T9, T8, T7, T6, T5, T4, T3, T2, T1, T0
 );
+
+/// Decodes a tuple of [`FromRow`] types from consecutive ranges of columns of the same row,
+/// splitting the row by the [`FromRow::COLUMN_COUNT`] of each element.
+///
+/// Tuples of [`FromSql`] types already implement [`FromRow`] one column per element,
+/// so tuples of [`FromRow`] types need this wrapper to tell them apart.
+///
+/// ```ignore
+/// let rows = client.query("SELECT u.*, o.* FROM users u JOIN orders o USING (user_id)", &[]).await?;
+/// for Flatten((user, order)) in Flatten::<(User, Order)>::from_slice(&rows) {
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Flatten<T>(pub T);
+
+impl<T> Flatten<T> {
+    /// Unwraps the tuple.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+macro_rules! impl_from_row_for_flatten {
+    ($($T:ident),*) => {
+        impl<$($T: FromRow),*> FromRow for Flatten<($($T,)*)> {
+            const COLUMN_COUNT: usize = 0 $( + $T::COLUMN_COUNT)*;
+            const JOINED: bool = false;
+            fn try_from_row_joined(_: Option<&mut Self>, row: &tokio_postgres::Row, mut i: usize) -> Result<Option<Self>, crate::Error> {
+                #[allow(unused_assignments)]
+                Ok(Some(Flatten(($(
+                    $T::try_from_row_joined(None, row, {
+                        let j = i;
+                        i += $T::COLUMN_COUNT;
+                        j
+                    })?.expect("when try_from_row_joined is called with last = None it should never return None"),
+                )*))))
+            }
+            fn try_from_row_by_name(row: impl AsRow) -> Result<Self, crate::Error> {
+                let row = row.as_row();
+                Ok(Flatten(($($T::try_from_row_by_name(row)?,)*)))
+            }
+            fn report_expected_columns() -> crate::ExpectedColumns {
+                let mut expected = Vec::with_capacity(Self::COLUMN_COUNT);
+                $(expected.extend_from_slice(&$T::report_expected_columns());)*
+                crate::ExpectedColumns::Owned(expected)
+            }
+            #[allow(unused_assignments)]
+            fn dry_run(columns: &[tokio_postgres::Column]) -> Vec<crate::FieldPlan> {
+                let mut i = 0;
+                let mut plans = Vec::with_capacity(0 $( + count_ident!($T))*);
+                $(
+                    plans.push(crate::FieldPlan::nested(
+                        None,
+                        i,
+                        $T::COLUMN_COUNT,
+                        crate::Conversion::Flatten,
+                        $T::dry_run(columns.get(i..).unwrap_or(&[])),
+                    ));
+                    i += $T::COLUMN_COUNT;
+                )*
+                plans
+            }
+            #[allow(unused_assignments)]
+            fn try_assert_matches(mut columns: &[tokio_postgres::Column]) -> Result<(), ()> {
+                if columns.len() != Self::COLUMN_COUNT {
+                    return Err(());
+                }
+                $(
+                    let (head, tail) = columns.split_at($T::COLUMN_COUNT);
+                    $T::try_assert_matches(head)?;
+                    columns = tail;
+                )*
+                Ok(())
+            }
+        }
+    };
+}
+
+macro_rules! generate_from_row_flatten {
+    () => {};
+    ($head:ident $(, $tail:ident)*) => {
+        impl_from_row_for_flatten!($head $(, $tail)*);
+        generate_from_row_flatten!($($tail),*);
+    };
+}
+
+generate_from_row_flatten!(T11, T10, T9, T8, T7, T6, T5, T4, T3, T2, T1, T0);
+
+/// Decodes each of the [`FromRow`] types of a tuple from consecutive ranges of columns of the same row,
+/// for `a.*, b.*` queries whose entities are used separately rather than nested into one another.
+///
+/// The columns of the whole row are verified at once, see [`Flatten`].
+///
+/// ```ignore
+/// let row = client.query_one("SELECT u.*, o.* FROM users u JOIN orders o USING (user_id) LIMIT 1", &[]).await?;
+/// let (user, order) = postgres_from_row::try_split_row::<(User, Order)>(&row)?;
+/// ```
+///
+/// # Panics
+///
+/// Panics if the row does not contain the expected column names.
+pub fn try_split_row<T>(row: impl AsRow) -> Result<T, crate::Error>
+where
+    Flatten<T>: FromRow,
+{
+    Flatten::<T>::try_from_row(row).map(Flatten::into_inner)
+}
+
+/// Like [`try_split_row`], panicking if a column could not be converted.
+///
+/// # Panics
+///
+/// Panics if the row does not contain the expected column names, or a column could not be converted.
+pub fn split_row<T>(row: impl AsRow) -> T
+where
+    Flatten<T>: FromRow,
+{
+    try_split_row(row).expect("could not convert column")
+}
@@ -0,0 +1,67 @@
+use std::{
+    borrow::Cow,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// How the names of rust types are shown in reports, set with [`set_type_names`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TypeNames {
+    /// The paths of the standard library are removed, and other paths are shortened to their crate and name,
+    /// so `core::option::Option<alloc::string::String>` is shown as `Option<String>`,
+    /// and `chrono::naive::datetime::NaiveDateTime` as `chrono::NaiveDateTime`.
+    #[default]
+    Short,
+    /// The names are shown as given by [`std::any::type_name`].
+    Full,
+}
+
+impl TypeNames {
+    /// Shows the name of a type, as given by [`std::any::type_name`], in this style.
+    pub fn apply(self, name: &str) -> Cow<'_, str> {
+        if self == TypeNames::Full || !name.contains("::") {
+            return name.into();
+        }
+        let mut short = String::with_capacity(name.len());
+        let mut rest = name;
+        while !rest.is_empty() {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+                .unwrap_or(rest.len());
+            let (path, tail) = rest.split_at(end);
+            short.push_str(&shorten_path(path));
+            let mut chars = tail.chars();
+            short.extend(chars.next());
+            rest = chars.as_str();
+        }
+        short.into()
+    }
+}
+
+/// Shortens a single path, without generics.
+fn shorten_path(path: &str) -> Cow<'_, str> {
+    let segments = path.split("::").collect::<Vec<_>>();
+    match segments.as_slice() {
+        // paths that don't start with a crate, like the ones of `<T as Trait>::Name`, are left alone
+        [] | [_] | ["", ..] => path.into(),
+        ["std" | "core" | "alloc", .., name] => (*name).into(),
+        [_, _] => path.into(),
+        [krate, .., name] => format!("{krate}::{name}").into(),
+    }
+}
+
+/// Whether [`TypeNames::Full`] was set, [`TypeNames::Short`] is the default.
+static FULL: AtomicBool = AtomicBool::new(false);
+
+/// Sets how the names of rust types are shown in the reports of every thread.
+pub fn set_type_names(type_names: TypeNames) {
+    FULL.store(type_names == TypeNames::Full, Ordering::Relaxed);
+}
+
+/// How the names of rust types are shown in reports, see [`set_type_names`].
+pub fn type_names() -> TypeNames {
+    if FULL.load(Ordering::Relaxed) {
+        TypeNames::Full
+    } else {
+        TypeNames::Short
+    }
+}
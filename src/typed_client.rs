@@ -0,0 +1,118 @@
+use std::{any::TypeId, collections::HashMap, ops::Deref, sync::Mutex};
+
+use tokio_postgres::{types::ToSql, GenericClient, Statement};
+
+use crate::{Error, FromRow};
+
+/// Wraps a client, caching the statements it prepares for each type they are converted to.
+///
+/// The statements are keyed by their sql and the [`TypeId`] of the type, so executing the same query again
+/// skips both preparing the statement and validating its columns.
+///
+/// Dereferences to the wrapped client, for the queries that are not converted.
+#[derive(Debug)]
+pub struct TypedClient<C> {
+    client: C,
+    statements: Mutex<HashMap<(String, TypeId), Statement>>,
+}
+
+impl<C: GenericClient> TypedClient<C> {
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            statements: Mutex::default(),
+        }
+    }
+
+    /// The wrapped client.
+    pub fn get_ref(&self) -> &C {
+        &self.client
+    }
+
+    /// Returns the wrapped client, dropping the cached statements.
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+
+    /// Forgets all the statements prepared so far.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Returns the statement prepared for `sql` and `T`, preparing it and validating its columns the first time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the statement does not return the expected column names.
+    pub async fn prepare_for<T: FromRow + 'static>(&self, sql: &str) -> Result<Statement, Error> {
+        let key = (sql.to_string(), TypeId::of::<T>());
+        if let Some(statement) = self.lock().get(&key) {
+            return Ok(statement.clone());
+        }
+        let statement = self.client.prepare(sql).await?;
+        T::assert_matches_statement(&statement);
+        self.lock().insert(key, statement.clone());
+        Ok(statement)
+    }
+
+    /// Executes `sql`, converting all the rows to `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the statement does not return the expected column names.
+    pub async fn fetch<T: FromRow + 'static>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<T>, Error> {
+        let statement = self.prepare_for::<T>(sql).await?;
+        let rows = self.client.query(&statement, params).await?;
+        T::try_from_slice_unchecked(&rows)
+    }
+
+    /// Executes `sql`, converting the only row it returns to `T`.
+    ///
+    /// Returns an error if the statement does not return exactly one row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the statement does not return the expected column names.
+    pub async fn fetch_one<T: FromRow + 'static>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<T, Error> {
+        let statement = self.prepare_for::<T>(sql).await?;
+        let row = self.client.query_one(&statement, params).await?;
+        T::try_from_row_unchecked(&row)
+    }
+
+    /// Executes `sql`, converting the row it returns to `T`, if any.
+    ///
+    /// Returns an error if the statement returns more than one row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the statement does not return the expected column names.
+    pub async fn fetch_optional<T: FromRow + 'static>(
+        &self,
+        sql: &str,
+        params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Option<T>, Error> {
+        let statement = self.prepare_for::<T>(sql).await?;
+        let row = self.client.query_opt(&statement, params).await?;
+        row.map(T::try_from_row_unchecked).transpose()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<(String, TypeId), Statement>> {
+        self.statements.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<C> Deref for TypedClient<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        &self.client
+    }
+}
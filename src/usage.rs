@@ -0,0 +1,139 @@
+//! Tracks which of the selected columns are actually used, enabled by the `usage` feature.
+//!
+//! Meant for test runs: every conversion by name records the columns of the row and the ones the type read,
+//! so queries that select columns no type ever uses can be pruned.
+//!
+//! ```ignore
+//! // at the end of the test run
+//! let report = postgres_from_row::usage::report();
+//! assert!(report.is_empty(), "{report}");
+//! ```
+//!
+//! Conversions by index are not tracked, since they already require the row to have exactly the expected columns.
+
+use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    fmt,
+    sync::{Mutex, PoisonError},
+};
+
+use tokio_postgres::Row;
+
+use crate::FromRow;
+
+/// How many times a column was selected and used, by type and column name.
+static USAGE: Mutex<BTreeMap<&'static str, TypeUsage>> = Mutex::new(BTreeMap::new());
+
+thread_local! {
+    /// How many conversions are in progress, so that only the outermost type records its columns.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Default)]
+struct TypeUsage {
+    conversions: u64,
+    columns: BTreeMap<String, (u64, u64)>,
+}
+
+/// Records the usage of the columns of `row` by `T`, unless `T` is being converted as part of another type.
+pub(crate) struct UsageGuard;
+
+impl UsageGuard {
+    pub(crate) fn enter<T: FromRow>(row: &Row) -> Self {
+        if DEPTH.with(|depth| depth.replace(depth.get() + 1)) == 0 {
+            record::<T>(row);
+        }
+        Self
+    }
+}
+
+impl Drop for UsageGuard {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+fn record<T: FromRow>(row: &Row) {
+    let expected = T::report_expected_columns();
+    let mut usage = USAGE.lock().unwrap_or_else(PoisonError::into_inner);
+    let usage = usage.entry(std::any::type_name::<T>()).or_default();
+    usage.conversions += 1;
+    for column in row.columns() {
+        let (selected, used) = usage.columns.entry(column.name().to_string()).or_default();
+        *selected += 1;
+        if expected
+            .iter()
+            .any(|e| e.column_name() == Some(column.name()))
+        {
+            *used += 1;
+        }
+    }
+}
+
+/// A column that was selected by every conversion of a type, but never used by it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedColumn {
+    /// The name of the type, as given by [`std::any::type_name`].
+    pub type_name: &'static str,
+    /// The name of the column.
+    pub column: String,
+    /// How many times the type was converted.
+    pub conversions: u64,
+}
+
+/// The columns that were always selected but never used, returned by [`report`].
+#[derive(Debug, Clone, Default)]
+pub struct UsageReport {
+    unused: Vec<UnusedColumn>,
+}
+
+impl UsageReport {
+    /// The unused columns, ordered by type and column name.
+    pub fn unused(&self) -> &[UnusedColumn] {
+        &self.unused
+    }
+
+    /// Whether no column was selected by every conversion of a type without being used by it.
+    pub fn is_empty(&self) -> bool {
+        self.unused.is_empty()
+    }
+}
+
+impl fmt::Display for UsageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for unused in &self.unused {
+            writeln!(
+                f,
+                "`{}` never used the column `{}`, selected by all of its {} conversions",
+                unused.type_name, unused.column, unused.conversions
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Reports the columns selected by every conversion of a type so far, that the type never used.
+pub fn report() -> UsageReport {
+    let usage = USAGE.lock().unwrap_or_else(PoisonError::into_inner);
+    let unused = usage
+        .iter()
+        .flat_map(|(type_name, usage)| {
+            usage
+                .columns
+                .iter()
+                .filter(|(_, (selected, used))| *selected == usage.conversions && *used == 0)
+                .map(|(column, _)| UnusedColumn {
+                    type_name,
+                    column: column.clone(),
+                    conversions: usage.conversions,
+                })
+        })
+        .collect();
+    UsageReport { unused }
+}
+
+/// Forgets all the usage recorded so far.
+pub fn reset() {
+    USAGE.lock().unwrap_or_else(PoisonError::into_inner).clear();
+}
@@ -0,0 +1,63 @@
+//! Checks that the values converted from a slice of rows account for every row, enabled by the `verify` feature.
+
+use std::cell::Cell;
+
+use crate::FromRow;
+
+thread_local! {
+    /// The rows that were null and did not become a value, while converting the current slice.
+    static NULL_ROWS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Records that a row was null and was skipped, see [`FromRow::row_span`].
+pub(crate) fn null_row() {
+    NULL_ROWS.with(|n| n.set(n.get() + 1));
+}
+
+/// The null rows counted so far while converting the current slice.
+pub(crate) fn null_rows() -> usize {
+    NULL_ROWS.with(Cell::get)
+}
+
+/// Replaces the null rows counted while converting the current slice.
+pub(crate) fn set_null_rows(null_rows: usize) {
+    NULL_ROWS.with(|n| n.set(null_rows));
+}
+
+/// Verifies the conversion of a slice of rows, restoring the count of the enclosing one when dropped.
+pub(crate) struct Verification {
+    rows: usize,
+    previous: usize,
+}
+
+impl Verification {
+    pub(crate) fn start(rows: usize) -> Self {
+        Self {
+            rows,
+            previous: NULL_ROWS.with(|n| n.replace(0)),
+        }
+    }
+
+    /// Panics if the rows spanned by `values` plus the null rows don't add up to the rows converted.
+    pub(crate) fn finish<T: FromRow>(self, values: &[T]) {
+        let null_rows = NULL_ROWS.with(|n| n.get());
+        let spanned: usize = values.iter().map(T::row_span).sum();
+        if spanned + null_rows != self.rows {
+            panic!(
+                "converting {} rows into {} values of `{}` accounted for {} rows ({} rows spanned by the values and {} null rows), some rows were lost while joining them",
+                self.rows,
+                values.len(),
+                std::any::type_name::<T>(),
+                spanned + null_rows,
+                spanned,
+                null_rows,
+            );
+        }
+    }
+}
+
+impl Drop for Verification {
+    fn drop(&mut self) {
+        NULL_ROWS.with(|n| n.set(self.previous));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{AsRow, Error, ExpectedColumns, FromRow};
+
+/// Converts rows with an existing `TryFrom<&Row>` implementation, so hand-rolled mappers can be used
+/// wherever a [`FromRow`] type is required while migrating them to the derive.
+///
+/// ```ignore
+/// impl TryFrom<&Row> for LegacyUser {
+///     type Error = tokio_postgres::Error;
+///     fn try_from(row: &Row) -> Result<Self, Self::Error> { /* ... */ }
+/// }
+///
+/// let users = ViaTryFrom::<LegacyUser>::try_from_slice(&rows)?;
+/// let users = users.into_iter().map(ViaTryFrom::into_inner);
+/// ```
+///
+/// The columns the mapper reads are not known, so they are never verified, and the wrapped type reads the whole row.
+/// It must be used on its own, not in a tuple: any type next to it would read the same columns.
+/// The derive rejects it when it is not the only field of the struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ViaTryFrom<T>(pub T);
+
+impl<T> ViaTryFrom<T> {
+    /// Unwraps the converted value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> FromRow for ViaTryFrom<T>
+where
+    T: for<'a> TryFrom<&'a tokio_postgres::Row>,
+    for<'a> <T as TryFrom<&'a tokio_postgres::Row>>::Error:
+        Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    const COLUMN_COUNT: usize = 0;
+    const JOINED: bool = false;
+    fn try_from_row_joined(
+        _: Option<&mut Self>,
+        row: &tokio_postgres::Row,
+        index: usize,
+    ) -> Result<Option<Self>, Error> {
+        if index != 0 {
+            return Err(Error::conversion(format!(
+                "`{}` reads the whole row, it can't be read from column {index}",
+                std::any::type_name::<T>()
+            )));
+        }
+        T::try_from(row)
+            .map(|this| Some(ViaTryFrom(this)))
+            .map_err(Error::conversion)
+    }
+    fn try_from_row_by_name(row: impl AsRow) -> Result<Self, Error> {
+        T::try_from(row.as_row())
+            .map(ViaTryFrom)
+            .map_err(Error::conversion)
+    }
+    fn report_expected_columns() -> ExpectedColumns {
+        ExpectedColumns::Borrowed(&[])
+    }
+    fn try_assert_matches(_: &[tokio_postgres::Column]) -> Result<(), ()> {
+        Ok(())
+    }
+}
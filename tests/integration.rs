@@ -4,6 +4,7 @@ use postgres_from_row::FromRow;
 use tokio_postgres::{types::Json, Row};
 
 #[derive(FromRow)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
 #[allow(dead_code)]
 pub struct Todo {
     todo_id: i32,
@@ -14,6 +15,7 @@ pub struct Todo {
 }
 
 #[derive(FromRow)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
 #[allow(dead_code)]
 pub struct User {
     user_id: i32,
@@ -36,3 +38,1755 @@ fn from_slice(rows: &[Row]) {
     let _ = User::from_slice(rows);
     let _ = User::try_from_slice(rows).unwrap();
 }
+
+#[allow(dead_code)]
+async fn query_as(client: &tokio_postgres::Client, user_id: i32) {
+    let _ = postgres_from_row::query_as!(
+        User,
+        "SELECT user_id FROM users WHERE user_id = $1",
+        user_id
+    )
+    .fetch_one(client)
+    .await
+    .unwrap();
+    let _ = postgres_from_row::query_as!(
+        Todo,
+        "SELECT t.todo_id, t.text, u.user_id, t.json FROM todos t JOIN users u USING (user_id)"
+    )
+    .fetch_all(client)
+    .await
+    .unwrap();
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Measurement {
+    measurement_id: i32,
+    #[from_row(flatten)]
+    point: (f64, f64),
+    #[from_row(join)]
+    tags: Vec<(String,)>,
+}
+
+#[allow(dead_code)]
+fn tuples(rows: &[Row]) {
+    let _ = <(i32, String)>::from_slice(rows);
+    let _ = <(i32, String)>::try_from_slice(rows).unwrap();
+    let _ = <Option<(i32, String)>>::try_from_slice(rows).unwrap();
+
+    let _ = Measurement::from_slice(rows);
+    let _ = Measurement::try_from_slice(rows).unwrap();
+}
+
+#[allow(dead_code)]
+fn unit(rows: &[Row]) {
+    let _: Vec<()> = <()>::from_slice(rows);
+    let _: Vec<()> = <()>::try_from_slice(rows).unwrap();
+    let _: () = <()>::try_from_row(&rows[0]).unwrap();
+}
+
+#[allow(dead_code)]
+fn flatten_tuples(rows: &[Row]) {
+    use postgres_from_row::Flatten;
+
+    let _: Vec<Flatten<(Todo, User)>> = Flatten::<(Todo, User)>::from_slice(rows);
+    let Flatten((_todo, _user, _count)) =
+        Flatten::<(Todo, User, (i64,))>::try_from_row(&rows[0]).unwrap();
+}
+
+#[allow(dead_code)]
+fn split_row(row: &Row) {
+    let (_todo, _user): (Todo, User) = postgres_from_row::try_split_row(row).unwrap();
+    let (_todo, _user, (_count,)) = postgres_from_row::split_row::<(Todo, User, (i64,))>(row);
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+#[from_row(allow_duplicate_columns)]
+pub struct TodoWithAuthor {
+    todo_id: i32,
+    #[from_row(flatten)]
+    author: Result<User, postgres_from_row::Error>,
+    #[from_row(join)]
+    assignees: Vec<Result<User, postgres_from_row::Error>>,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+#[from_row(allow_duplicate_columns)]
+pub struct TodoByKey {
+    #[from_row(key)]
+    todo_id: i32,
+    #[from_row(flatten)]
+    author: Result<User, postgres_from_row::Error>,
+    #[from_row(join)]
+    assignees: Vec<User>,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+#[from_row(key_struct)]
+pub struct TodoWithAssignees {
+    #[from_row(key)]
+    todo_id: i32,
+    text: String,
+    #[from_row(join)]
+    assignees: Vec<User>,
+}
+
+#[allow(dead_code)]
+fn key_struct(rows: &[Row]) {
+    let todos = TodoWithAssignees::from_slice(rows);
+    let by_key: std::collections::HashMap<TodoWithAssigneesKey, &TodoWithAssignees> =
+        todos.iter().map(|todo| (todo.key(), todo)).collect();
+    let _ = by_key.get(&TodoWithAssigneesKey { todo_id: 1 });
+}
+
+#[allow(dead_code)]
+fn flatten_result(rows: &[Row]) {
+    let _ = TodoWithAuthor::from_slice(rows);
+    let _: Result<User, postgres_from_row::Error> =
+        <Result<User, postgres_from_row::Error>>::try_from_row(&rows[0]).unwrap();
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+#[from_row(bound = "T: FromRow")]
+pub struct Page<T> {
+    total: i64,
+    #[from_row(join)]
+    items: Vec<T>,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Tagged<T> {
+    #[from_row(
+        try_from = "String",
+        bound = "T: TryFrom<String>, T::Error: std::error::Error + Send + Sync + 'static"
+    )]
+    tag: T,
+}
+
+#[allow(dead_code)]
+fn bound(rows: &[Row]) {
+    let _: Vec<Page<User>> = Page::from_slice(rows);
+}
+
+#[allow(dead_code)]
+async fn client_ext(client: &tokio_postgres::Client, user_id: i32) {
+    use postgres_from_row::ClientExt;
+    use tokio_postgres::types::Type;
+
+    let _: Vec<User> = client
+        .query_as("SELECT user_id FROM users", &[])
+        .await
+        .unwrap();
+    let _: User = client
+        .query_one_as("SELECT user_id FROM users WHERE user_id = $1", &[&user_id])
+        .await
+        .unwrap();
+    let _: Option<User> = client
+        .query_opt_as("SELECT user_id FROM users WHERE user_id = $1", &[&user_id])
+        .await
+        .unwrap();
+    let _: Vec<User> = client
+        .query_typed_as(
+            "SELECT user_id FROM users WHERE user_id = $1",
+            &[(&user_id, Type::INT4)],
+        )
+        .await
+        .unwrap();
+    let _: Vec<String> = client
+        .query_map("SELECT username FROM users", &[], |row| Ok(row.try_get(0)?))
+        .await
+        .unwrap();
+    let _: Vec<i32> = client
+        .query_map_as("SELECT user_id FROM users", &[], |user: User| user.user_id)
+        .await
+        .unwrap();
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Price {
+    #[from_row(try_from_fn = "parse_cents", from_fn_ty = "i64", context)]
+    cents: u32,
+}
+
+fn parse_cents(cents: i64, ctx: &postgres_from_row::ConversionContext) -> Result<u32, String> {
+    let scale = ctx.user::<u32>().copied().unwrap_or(1);
+    u32::try_from(cents * i64::from(scale)).map_err(|_| {
+        format!(
+            "`{}` is out of range in `{}`",
+            ctx.field(),
+            ctx.column_name()
+        )
+    })
+}
+
+#[allow(dead_code)]
+fn conversion_context(rows: &[Row]) {
+    let _ = postgres_from_row::with_context(&100u32, || Price::try_from_slice(rows));
+}
+
+#[cfg(feature = "test-util")]
+#[derive(FromRow)]
+#[from_row(mock)]
+#[allow(dead_code)]
+pub struct Session {
+    #[from_row(from_fn = "std::time::Duration::from_secs_f64", from_fn_ty = "f64")]
+    timeout: std::time::Duration,
+    #[from_row(from_fn = "|name: String| name.to_uppercase()")]
+    name: String,
+    #[from_row(from_fn = "<i64 as Into<i128>>::into", from_fn_ty = "i64")]
+    counter: i128,
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn from_fn() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+    use tokio_postgres::types::Type;
+
+    let row = MockRow::new()
+        .column("timeout", 1.5f64)
+        .column("name", "main")
+        .column("counter", 7i64);
+    row.assert_matches::<Session>();
+    let session = Session::try_from_mock_row(&row).unwrap();
+    assert_eq!(session.timeout, std::time::Duration::from_millis(1500));
+    assert_eq!(session.name, "MAIN");
+    assert_eq!(session.counter, 7);
+
+    let expected = Session::report_expected_columns();
+    assert!(expected[0].accepts(&Type::FLOAT8));
+    assert!(expected[2].accepts(&Type::INT8));
+}
+
+#[allow(dead_code)]
+fn dry_run(rows: &[Row]) {
+    use postgres_from_row::{Conversion, FieldPlan};
+
+    let plans: Vec<FieldPlan> = Todo::dry_run(rows[0].columns());
+    assert_eq!(plans[2].conversion, Conversion::Flatten);
+    assert!(plans.iter().all(FieldPlan::is_aligned));
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+#[from_row(validate = "Period::validate")]
+pub struct Period {
+    #[from_row(try_from = "i64")]
+    start: u32,
+    #[from_row(try_from_fn = "parse_end", from_fn_ty = "i64")]
+    end: u32,
+}
+
+impl Period {
+    fn validate(&self) -> Result<(), String> {
+        if self.start > self.end {
+            return Err("period ends before it starts".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn parse_end(end: i64) -> Result<u32, std::num::TryFromIntError> {
+    end.try_into()
+}
+
+#[allow(dead_code)]
+fn conversion_errors(rows: &[Row]) {
+    match Period::try_from_slice(rows) {
+        Ok(_) => {}
+        Err(postgres_from_row::Error::Postgres(_)) => {}
+        Err(postgres_from_row::Error::Conversion(_)) => {}
+        Err(postgres_from_row::Error::Field(error)) => {
+            let _: (String, Option<&str>) = (error.path(), error.column());
+        }
+        Err(postgres_from_row::Error::Row(error)) => {
+            let _: (usize, Option<&str>) = (error.index(), error.key_value());
+        }
+        Err(postgres_from_row::Error::Mismatch(report)) => {
+            let _ = report.mismatches().count();
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn row_span(rows: &[Row]) {
+    let measurements = Measurement::from_slice(rows);
+    let spanned: usize = measurements.iter().map(FromRow::row_span).sum();
+    assert!(spanned <= rows.len());
+}
+
+mod facade {
+    pub use postgres_from_row as renamed;
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+#[from_row(crate = "facade::renamed")]
+pub struct Renamed {
+    #[from_row(flatten)]
+    user: User,
+    #[from_row(try_from = "i64")]
+    count: u32,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+#[from_row(rename_all = "camelCase", doc)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
+pub struct CamelCase {
+    user_id: i32,
+    #[from_row(rename = "display_name")]
+    display_name: String,
+    created_at_unix: i64,
+}
+
+#[allow(dead_code)]
+fn joined() {
+    const _: () = assert!(!User::JOINED);
+    const _: () = assert!(!Todo::JOINED);
+    const _: () = assert!(Measurement::JOINED);
+    const _: () = assert!(!<(i32, String)>::JOINED);
+}
+
+#[cfg(feature = "rayon")]
+#[allow(dead_code)]
+fn from_slice_par(rows: &[Row]) {
+    let _ = Todo::from_slice_par(rows);
+    let _ = Measurement::try_from_slice_par(rows).unwrap();
+}
+
+#[allow(dead_code)]
+fn json_columns() {
+    let columns = Todo::report_expected_columns();
+    let json = columns.last().unwrap();
+    assert!(json.is_json());
+    assert_eq!(
+        json.type_name(),
+        std::any::type_name::<HashMap<String, bool>>()
+    );
+    assert!(json.accepts(&tokio_postgres::types::Type::JSONB));
+}
+
+#[allow(dead_code)]
+fn iter_from_slice(rows: &[Row]) {
+    let _: Vec<Todo> = Todo::iter_from_slice(rows)
+        .filter_map(Result::ok)
+        .take(10)
+        .collect();
+    let _: Result<Vec<Measurement>, postgres_from_row::Error> =
+        Measurement::iter_from_slice(rows).collect();
+}
+
+#[cfg(feature = "test-util")]
+#[derive(FromRow)]
+#[from_row(mock)]
+#[allow(dead_code)]
+pub struct Country {
+    #[from_row(trim, lowercase)]
+    code: String,
+    #[from_row(trim)]
+    name: Option<String>,
+    #[from_row(trim, from_fn = "|tags: Vec<String>| tags.join(\",\")")]
+    tags: String,
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn normalizers() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+    use tokio_postgres::types::Type;
+
+    let row = MockRow::new()
+        .column_with_type("code", Type::BPCHAR, "US  ")
+        .column_with_type("name", Type::BPCHAR, Some(" United States   "))
+        .column_with_type("tags", Type::TEXT_ARRAY, vec![" north ", "america   "]);
+    let country = Country::try_from_mock_row(&row).unwrap();
+    assert_eq!(country.code, "us");
+    assert_eq!(country.name.as_deref(), Some("United States"));
+    assert_eq!(country.tags, "north,america");
+
+    let row = MockRow::new()
+        .column_with_type("code", Type::BPCHAR, "FR  ")
+        .null::<String>("name")
+        .column_with_type("tags", Type::TEXT_ARRAY, Vec::<String>::new());
+    let country = Country::try_from_mock_row(&row).unwrap();
+    assert_eq!(country.code, "fr");
+    assert_eq!(country.name, None);
+}
+
+#[allow(dead_code)]
+fn cached_expected_columns() {
+    use postgres_from_row::ExpectedColumns;
+
+    assert!(matches!(
+        Todo::report_expected_columns(),
+        ExpectedColumns::Borrowed(_)
+    ));
+    assert!(matches!(
+        Measurement::report_expected_columns(),
+        ExpectedColumns::Borrowed(_)
+    ));
+}
+
+#[allow(dead_code)]
+fn const_expected_columns() {
+    const TODO: &[postgres_from_row::ExpectedColumn] = match Todo::EXPECTED_COLUMNS {
+        Some(columns) => columns,
+        None => panic!("the columns of Todo should be known at compile time"),
+    };
+    assert_eq!(TODO.len(), Todo::COLUMN_COUNT);
+    assert_eq!(TODO[2].column_name(), Some("user_id"));
+}
+
+#[derive(FromRow)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
+#[allow(dead_code)]
+pub struct TodoDetails {
+    todo_id: i32,
+    text: String,
+    #[from_row(flatten)]
+    author: Option<User>,
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn absent_optional_columns() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+
+    // `SELECT todo_id, text FROM todos` does not select the columns of the author
+    let slim = MockRow::new()
+        .column("todo_id", 1)
+        .column("text", "wash the dishes");
+    let details = TodoDetails::try_from_mock_row_by_name(&slim).unwrap();
+    assert_eq!(details.todo_id, 1);
+    assert!(details.author.is_none());
+
+    // `SELECT user_id, text, todo_id FROM todos`
+    let wide = MockRow::new()
+        .column("user_id", 2)
+        .column("text", "wash the dishes")
+        .column("todo_id", 1);
+    let details = TodoDetails::try_from_mock_row_by_name(&wide).unwrap();
+    assert_eq!(details.todo_id, 1);
+    assert_eq!(details.author.unwrap().user_id, 2);
+}
+
+#[allow(dead_code)]
+fn accepted_types() {
+    use tokio_postgres::types::Type;
+
+    let columns = User::report_expected_columns();
+    assert_eq!(columns[0].accepted_types(), vec![Type::INT4]);
+    assert_eq!(columns[0].describe_accepted_types(), "int4");
+}
+
+#[cfg(feature = "explain")]
+#[allow(dead_code)]
+async fn explain(client: &tokio_postgres::Client) {
+    use postgres_from_row::explain::explain_as;
+
+    let explain = explain_as(client, "SELECT * FROM todos WHERE todo_id = $1", &[&1])
+        .await
+        .unwrap();
+    assert!(explain
+        .plan
+        .iter()
+        .any(|node| node.relation_name.as_deref() == Some("todos")));
+}
+
+#[allow(dead_code)]
+fn mismatch_report(row: &Row) {
+    use postgres_from_row::{report_expected_columns_mismatch, ReportEntry};
+
+    // `SELECT todo_id, text, user_id FROM todos` is missing the `json` column
+    let report = report_expected_columns_mismatch(row.columns(), &Todo::report_expected_columns());
+    assert!(!report.is_match());
+    assert!(report.mismatches().all(|entry| matches!(
+        entry,
+        ReportEntry::Missing { expected } if expected.column_name() == Some("json")
+    )));
+}
+
+postgres_from_row::assert_columns!(User, ["user_id"]);
+postgres_from_row::assert_columns!(Todo, ["todo_id", "text", "user_id", "json"]);
+
+#[cfg(feature = "deadpool")]
+#[allow(dead_code)]
+async fn deadpool(pool: &deadpool_postgres::Pool) {
+    use postgres_from_row::deadpool::{ObjectExt, PoolExt};
+
+    let _: Vec<User> = pool
+        .query_as("SELECT user_id FROM users", &[])
+        .await
+        .unwrap();
+    let object = pool.get().await.unwrap();
+    let _: User = object
+        .query_one_as("SELECT user_id FROM users LIMIT 1", &[])
+        .await
+        .unwrap();
+    let _: Option<Measurement> = object
+        .query_joined(
+            "SELECT * FROM measurements WHERE measurement_id = $1",
+            &[&1],
+        )
+        .await
+        .unwrap();
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn paginated() {
+    use postgres_from_row::{test_util::MockRow, Paginated};
+
+    // `SELECT user_id, count(*) OVER () AS total_count FROM users LIMIT 2`
+    let row = |user_id: i32| {
+        MockRow::new()
+            .column("user_id", user_id)
+            .column("total_count", 5i64)
+    };
+    let page = Paginated::<User>::try_from_mock_page(&[row(1), row(2)]).unwrap();
+    let ids: Vec<_> = page.items.iter().map(|user| user.user_id).collect();
+    assert_eq!(ids, [1, 2]);
+    assert_eq!(page.total_count, 5);
+
+    let page = Paginated::<User>::try_from_mock_page(&[]).unwrap();
+    assert!(page.items.is_empty());
+    assert_eq!(page.total_count, 0);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn try_from_row_at() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+
+    // `SELECT todo_id, text, user_id, json, user_id FROM todos`
+    let row = MockRow::new()
+        .column("todo_id", 1)
+        .column("text", "wash the dishes")
+        .column("user_id", 2)
+        .column("json", Json(HashMap::from([("done".to_string(), false)])))
+        .column("user_id", 2);
+    let (todo, next) = Todo::try_from_mock_row_at(&row, 0).unwrap();
+    let (user, end) = User::try_from_mock_row_at(&row, next).unwrap();
+    assert_eq!(todo.text, "wash the dishes");
+    assert_eq!(todo.user.user_id, user.user_id);
+    assert_eq!(next, 4);
+    assert_eq!(end, row.len());
+}
+
+#[derive(FromRow)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
+#[allow(dead_code)]
+pub struct Book {
+    subtitle: Option<String>,
+    title: String,
+}
+
+#[derive(FromRow)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
+#[allow(dead_code)]
+pub struct Author {
+    author_id: i32,
+    #[from_row(join, left)]
+    books: Vec<Book>,
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn left_join() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+    use tokio_postgres::types::Type;
+
+    // `SELECT a.author_id, b.subtitle, b.title FROM authors a LEFT JOIN books b USING (author_id)`
+    let row = |author_id: i32, subtitle: Option<&str>, title: Option<&str>| {
+        MockRow::new()
+            .column("author_id", author_id)
+            .column_with_type("subtitle", Type::TEXT, subtitle)
+            .column_with_type("title", Type::TEXT, title)
+    };
+    let authors = Author::try_from_mock_rows(&[
+        row(1, None, Some("Dune")),
+        row(1, Some("Book Two"), Some("Dune Messiah")),
+        row(2, None, None),
+    ])
+    .unwrap();
+
+    assert_eq!(authors.len(), 2);
+    assert_eq!(authors[0].books.len(), 2);
+    assert_eq!(authors[0].books[0].subtitle, None);
+    assert_eq!(authors[0].books[1].title, "Dune Messiah");
+    assert_eq!(authors[1].author_id, 2);
+    assert!(authors[1].books.is_empty());
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn btree_map() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+    use std::collections::BTreeMap;
+    use tokio_postgres::types::Type;
+
+    // `SELECT username, user_id FROM users`
+    let row = |username: Option<&str>, user_id: i32| {
+        MockRow::new()
+            .column_with_type("username", Type::TEXT, username)
+            .column("user_id", user_id)
+    };
+    let by_name = BTreeMap::<(String,), Vec<User>>::try_from_mock_rows(&[
+        row(Some("bob"), 1),
+        row(Some("alice"), 2),
+        row(None, 3),
+        row(Some("bob"), 4),
+    ])
+    .unwrap()
+    .pop()
+    .unwrap();
+
+    let names: Vec<_> = by_name.keys().map(|(name,)| name.as_str()).collect();
+    assert_eq!(names, ["alice", "bob"]);
+    let bobs: Vec<_> = by_name[&("bob".to_string(),)]
+        .iter()
+        .map(|user| user.user_id)
+        .collect();
+    assert_eq!(bobs, [1, 4]);
+}
+
+#[derive(FromRow, PartialEq)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
+#[allow(dead_code)]
+pub struct Tag {
+    tag_id: i32,
+    name: String,
+}
+
+#[derive(FromRow)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
+#[allow(dead_code)]
+pub struct Post {
+    post_id: i32,
+    #[from_row(join, duplicates = "dedup", duplicates_by = "tag_id")]
+    tags: Vec<Tag>,
+}
+
+#[derive(FromRow)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
+#[allow(dead_code)]
+pub struct StrictPost {
+    post_id: i32,
+    #[from_row(join, duplicates = "error")]
+    tags: Vec<Tag>,
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn duplicates() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+
+    // `SELECT p.post_id, t.tag_id, t.name FROM posts p JOIN tags t USING (post_id)`
+    let row = |post_id: i32, tag_id: i32, name: &str| {
+        MockRow::new()
+            .column("post_id", post_id)
+            .column("tag_id", tag_id)
+            .column("name", name)
+    };
+    let rows = [row(1, 1, "rust"), row(1, 2, "sql"), row(1, 1, "Rust")];
+
+    let posts = Post::try_from_mock_rows(&rows).unwrap();
+    assert_eq!(posts.len(), 1);
+    let names: Vec<_> = posts[0].tags.iter().map(|tag| tag.name.as_str()).collect();
+    assert_eq!(names, ["rust", "sql"]);
+
+    let error = StrictPost::try_from_mock_rows(&[row(1, 1, "rust"), row(1, 1, "rust")])
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("a duplicate"), "{error}");
+    assert!(StrictPost::try_from_mock_rows(&rows).is_ok());
+}
+
+#[derive(FromRow, PartialEq)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
+#[allow(dead_code)]
+pub struct Comment {
+    comment_id: i32,
+    body: String,
+}
+
+#[derive(FromRow)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
+#[allow(dead_code)]
+pub struct DiscussedPost {
+    post_id: i32,
+    #[from_row(join, left, duplicates = "dedup")]
+    tags: Vec<Tag>,
+    #[from_row(join, left, duplicates = "dedup")]
+    comments: Vec<Comment>,
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn sibling_joins() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+    use tokio_postgres::types::Type;
+
+    // `SELECT p.post_id, t.tag_id, t.name, c.comment_id, c.body FROM posts p
+    //  LEFT JOIN tags t USING (post_id) LEFT JOIN comments c USING (post_id)`
+    let row = |post_id: i32, tag: Option<(i32, &str)>, comment: Option<(i32, &str)>| {
+        MockRow::new()
+            .column("post_id", post_id)
+            .column_with_type("tag_id", Type::INT4, tag.map(|t| t.0))
+            .column_with_type("name", Type::TEXT, tag.map(|t| t.1))
+            .column_with_type("comment_id", Type::INT4, comment.map(|c| c.0))
+            .column_with_type("body", Type::TEXT, comment.map(|c| c.1))
+    };
+    let posts = DiscussedPost::try_from_mock_rows(&[
+        row(1, Some((1, "rust")), Some((1, "first"))),
+        row(1, Some((1, "rust")), Some((2, "second"))),
+        row(1, Some((2, "sql")), Some((1, "first"))),
+        row(1, Some((2, "sql")), Some((2, "second"))),
+        row(2, None, Some((3, "third"))),
+    ])
+    .unwrap();
+
+    assert_eq!(posts.len(), 2);
+    let tags: Vec<_> = posts[0].tags.iter().map(|tag| tag.tag_id).collect();
+    assert_eq!(tags, [1, 2]);
+    let comments: Vec<_> = posts[0].comments.iter().map(|c| c.comment_id).collect();
+    assert_eq!(comments, [1, 2]);
+    assert!(posts[1].tags.is_empty());
+    assert_eq!(posts[1].comments.len(), 1);
+}
+
+#[derive(postgres_from_row::TextEnum)]
+#[allow(dead_code)]
+pub enum Priority {
+    #[from_row(rename = "low")]
+    Low,
+    #[from_row(rename = "high")]
+    High,
+    Urgent,
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Task {
+    task_id: i32,
+    #[from_row(enum_text)]
+    priority: Priority,
+    #[from_row(enum_text, trim)]
+    previous_priority: Option<Priority>,
+}
+
+#[derive(FromRow)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
+#[allow(dead_code)]
+pub struct Reviewer {
+    reviewer_id: Option<i32>,
+    nickname: Option<String>,
+}
+
+#[derive(FromRow)]
+#[cfg_attr(feature = "test-util", from_row(mock))]
+#[allow(dead_code)]
+pub struct ReviewedTodo {
+    todo_id: i32,
+    #[from_row(flatten)]
+    reviewer: Option<Reviewer>,
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn optional_flatten_all_null() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+    use tokio_postgres::types::Type;
+
+    // `SELECT todo_id, r.reviewer_id, r.nickname FROM todos LEFT JOIN reviewers r USING (reviewer_id)`
+    let row = |reviewer_id: Option<i32>, nickname: Option<&str>| {
+        MockRow::new()
+            .column("todo_id", 1)
+            .column_with_type("reviewer_id", Type::INT4, reviewer_id)
+            .column_with_type("nickname", Type::TEXT, nickname)
+    };
+
+    // `None` instead of a reviewer with only `None` fields when there is no match
+    let todo = ReviewedTodo::try_from_mock_row(&row(None, None)).unwrap();
+    assert!(todo.reviewer.is_none());
+
+    let todo = ReviewedTodo::try_from_mock_row(&row(Some(2), None)).unwrap();
+    let reviewer = todo.reviewer.unwrap();
+    assert_eq!(reviewer.reviewer_id, Some(2));
+    assert_eq!(reviewer.nickname, None);
+}
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct Counter {
+    counter_id: i32,
+    #[from_row(from_fn = "|hits: Option<i64>| hits.unwrap_or_default()", nullable)]
+    hits: i64,
+}
+
+#[test]
+fn nullable() {
+    use tokio_postgres::types::Type;
+
+    let expected = Counter::report_expected_columns();
+    assert!(!expected[0].nullable(&Type::INT4));
+    assert!(expected[1].nullable(&Type::INT8));
+}
+
+#[allow(dead_code)]
+fn result_sets(users: Vec<Row>, todos: Vec<Row>) {
+    use postgres_from_row::FromResultSets;
+
+    let (users, todos) = <(Vec<User>, Vec<Todo>)>::from_result_sets(&[users, todos]);
+    let _ = (users.len(), todos.len());
+}
+
+#[cfg(feature = "simple")]
+#[derive(FromRow)]
+#[from_row(simple_query)]
+#[allow(dead_code)]
+pub struct AccountOwner {
+    owner_id: i32,
+    nickname: Option<String>,
+}
+
+#[cfg(feature = "simple")]
+#[derive(FromRow)]
+#[from_row(simple_query)]
+#[allow(dead_code)]
+pub struct Account {
+    account_id: i32,
+    #[from_row(trim)]
+    email: String,
+    verified: bool,
+    #[from_row(flatten)]
+    owner: Option<AccountOwner>,
+}
+
+#[cfg(feature = "simple")]
+#[allow(dead_code)]
+async fn simple_query(client: &tokio_postgres::Client) {
+    use postgres_from_row::simple::FromSimpleRow;
+
+    let messages = client
+        .simple_query("SELECT account_id, email, verified, owner_id, nickname FROM accounts")
+        .await
+        .unwrap();
+    let _ = Account::try_from_simple_messages(&messages).unwrap();
+}
+
+#[cfg(feature = "copy")]
+#[derive(FromRow)]
+#[from_row(copy_out)]
+#[allow(dead_code)]
+pub struct CopiedTodo {
+    todo_id: i32,
+    text: String,
+    #[from_row(try_from = "i64")]
+    views: u64,
+}
+
+#[cfg(feature = "copy")]
+#[allow(dead_code)]
+async fn copy_out(client: &tokio_postgres::Client) {
+    use futures_util::TryStreamExt;
+    use postgres_from_row::copy::copy_out_as;
+
+    let stream = client
+        .copy_out("COPY todos (todo_id, text, views) TO STDOUT (FORMAT binary)")
+        .await
+        .unwrap();
+    let _: Vec<CopiedTodo> = copy_out_as::<CopiedTodo>(stream)
+        .try_collect()
+        .await
+        .unwrap();
+}
+
+#[cfg(feature = "copy")]
+#[derive(FromRow)]
+#[from_row(copy_out, copy_in)]
+#[allow(dead_code)]
+pub struct Event {
+    event_id: i64,
+    name: String,
+    #[from_row(flatten)]
+    location: Option<Location>,
+}
+
+#[cfg(feature = "copy")]
+#[derive(FromRow)]
+#[from_row(copy_out, copy_in)]
+#[allow(dead_code)]
+pub struct Location {
+    latitude: f64,
+    longitude: f64,
+}
+
+#[cfg(feature = "copy")]
+#[allow(dead_code)]
+async fn copy_in(client: &tokio_postgres::Client, events: &[Event]) {
+    use postgres_from_row::copy::{copy_in_from, copy_in_statement};
+
+    let sink = client
+        .copy_in(&copy_in_statement::<Event>("events"))
+        .await
+        .unwrap();
+    let _ = copy_in_from(sink, events).await.unwrap();
+}
+
+#[derive(FromRow, postgres_from_row::ToParams)]
+#[allow(dead_code)]
+#[from_row(allow_duplicate_columns)]
+pub struct NewTodo {
+    text: String,
+    #[to_params(rename = "author_id")]
+    user_id: i32,
+    #[to_params(skip)]
+    #[from_row(flatten)]
+    author: Option<User>,
+}
+
+#[allow(dead_code)]
+async fn to_params(client: &tokio_postgres::Client, todo: &NewTodo) {
+    use postgres_from_row::ToParams;
+
+    assert_eq!(NewTodo::PARAM_NAMES, ["text", "author_id"]);
+    client
+        .execute(
+            "INSERT INTO todos (text, author_id) VALUES ($1, $2)",
+            &todo.params(),
+        )
+        .await
+        .unwrap();
+}
+
+#[derive(FromRow)]
+#[from_row(table = "accounts", rename_all = "camelCase")]
+#[allow(dead_code)]
+pub struct TableAccount {
+    #[from_row(primary_key)]
+    account_id: i32,
+    user: String,
+    #[from_row(flatten)]
+    owner: User,
+}
+
+#[test]
+fn insert_sql() {
+    use postgres_from_row::Table;
+
+    assert_eq!(
+        TableAccount::insert_sql(),
+        r#"INSERT INTO accounts ("accountId", "user") VALUES ($1, $2)"#
+    );
+    assert_eq!(
+        TableAccount::insert_returning_sql(),
+        r#"INSERT INTO accounts ("accountId", "user") VALUES ($1, $2) RETURNING "accountId", "user", "user_id""#
+    );
+}
+
+#[test]
+fn select_sql() {
+    use postgres_from_row::Table;
+
+    assert_eq!(
+        TableAccount::select()
+            .join_raw("JOIN users USING (user_id)")
+            .where_raw("account_id = $1")
+            .where_raw("user_id = $2 OR $2 IS NULL")
+            .limit(1)
+            .to_sql(),
+        r#"SELECT accounts."accountId", accounts."user", "user_id" FROM accounts JOIN users USING (user_id) WHERE (account_id = $1) AND (user_id = $2 OR $2 IS NULL) LIMIT 1"#
+    );
+}
+
+#[test]
+fn update_sql() {
+    use postgres_from_row::{FieldMask, Table};
+
+    assert_eq!(TableAccount::update_sql(&FieldMask::new()), None);
+    assert_eq!(
+        TableAccount::update_sql(&FieldMask::new().with("user")).as_deref(),
+        Some(r#"UPDATE accounts SET "user" = $1 WHERE "accountId" = $2"#)
+    );
+}
+
+#[cfg(feature = "test-util")]
+#[derive(FromRow)]
+#[from_row(mock)]
+#[allow(dead_code)]
+pub struct MockTag {
+    tag_id: i32,
+    #[from_row(from_fn = "|name: String| name.to_uppercase()")]
+    name: String,
+}
+
+#[cfg(feature = "test-util")]
+#[derive(FromRow)]
+#[from_row(mock)]
+#[allow(dead_code)]
+pub struct MockPost {
+    post_id: i32,
+    #[from_row(join, left)]
+    tags: Vec<MockTag>,
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn mock_rows() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+    use tokio_postgres::types::Type;
+
+    let row = |post_id: i32, tag: Option<(i32, &str)>| {
+        MockRow::new()
+            .column("post_id", post_id)
+            .column_with_type("tag_id", Type::INT4, tag.map(|t| t.0))
+            .column_with_type("name", Type::TEXT, tag.map(|t| t.1))
+    };
+    let posts = MockPost::try_from_mock_rows(&[
+        row(1, Some((1, "rust"))),
+        row(1, Some((2, "sql"))),
+        row(2, None),
+    ])
+    .unwrap();
+
+    assert_eq!(posts.len(), 2);
+    assert_eq!(posts[0].post_id, 1);
+    assert_eq!(posts[0].tags.len(), 2);
+    assert_eq!(posts[0].tags[1].name, "SQL");
+    assert!(posts[1].tags.is_empty());
+}
+
+#[cfg(feature = "test-util")]
+#[derive(FromRow)]
+#[from_row(mock)]
+#[allow(dead_code)]
+pub struct MockNote {
+    body: Option<String>,
+    note_id: Option<i32>,
+}
+
+#[cfg(feature = "test-util")]
+#[derive(FromRow)]
+#[from_row(mock)]
+#[allow(dead_code)]
+pub struct MockThread {
+    thread_id: i32,
+    #[from_row(join)]
+    notes: Vec<MockNote>,
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn vec_all_null() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+    use tokio_postgres::types::Type;
+
+    let row = |thread_id: i32, body: Option<&str>, note_id: Option<i32>| {
+        MockRow::new()
+            .column("thread_id", thread_id)
+            .column_with_type("body", Type::TEXT, body)
+            .column_with_type("note_id", Type::INT4, note_id)
+    };
+    let threads =
+        MockThread::try_from_mock_rows(&[row(1, None, None), row(2, None, Some(3))]).unwrap();
+
+    assert_eq!(threads.len(), 2);
+    assert!(threads[0].notes.is_empty());
+    assert_eq!(threads[1].notes.len(), 1);
+    assert_eq!(threads[1].notes[0].note_id, Some(3));
+}
+
+#[cfg(feature = "test-util")]
+#[derive(FromRow)]
+#[from_row(mock)]
+#[allow(dead_code)]
+pub struct MockContact {
+    contact_id: i32,
+    phone: Option<String>,
+}
+
+#[cfg(feature = "test-util")]
+#[derive(FromRow)]
+#[from_row(mock, allow_duplicate_columns)]
+#[allow(dead_code)]
+pub struct MockCustomer {
+    customer_id: i32,
+    #[from_row(flatten, none_when = "any_null")]
+    complete: Option<MockContact>,
+    #[from_row(flatten, none_when = "key_null")]
+    keyed: Option<MockContact>,
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn none_when() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+    use tokio_postgres::types::Type;
+
+    let row = |contact_id: Option<i32>, phone: Option<&str>| {
+        MockRow::new()
+            .column("customer_id", 1i32)
+            .column_with_type("contact_id", Type::INT4, contact_id)
+            .column_with_type("phone", Type::TEXT, phone)
+            .column_with_type("contact_id", Type::INT4, contact_id)
+            .column_with_type("phone", Type::TEXT, phone)
+    };
+
+    let customer = MockCustomer::try_from_mock_row(&row(Some(2), None)).unwrap();
+    assert!(customer.complete.is_none());
+    assert_eq!(customer.keyed.unwrap().contact_id, 2);
+
+    let customer = MockCustomer::try_from_mock_row(&row(None, None)).unwrap();
+    assert!(customer.keyed.is_none());
+
+    assert!(MockCustomer::try_from_mock_row(&row(None, Some("555"))).is_err());
+}
+
+#[allow(dead_code)]
+fn erased(row: &Row) {
+    use postgres_from_row::{erased, ErasedFromRow};
+
+    let mappers: HashMap<&str, Box<dyn ErasedFromRow>> =
+        HashMap::from([("users", erased::<User>()), ("todos", erased::<Todo>())]);
+    assert_eq!(mappers["users"].column_count(), User::COLUMN_COUNT);
+    let value = mappers["users"].erased_try_from_row(row).unwrap();
+    let _: Box<User> = value.downcast().unwrap();
+}
+
+#[derive(FromRow)]
+#[from_row(partial)]
+#[allow(dead_code)]
+pub struct Profile {
+    pub profile_id: i32,
+    #[from_row(rename = "display_name")]
+    pub name: String,
+    pub bio: Option<String>,
+    #[from_row(flatten)]
+    pub user: User,
+}
+
+#[allow(dead_code)]
+fn partial(row: &Row, profile: &mut Profile) {
+    // `SELECT display_name FROM profiles`
+    let partial = ProfilePartial::try_from_row(row).unwrap();
+    assert!(partial.profile_id.is_none());
+    assert!(partial.field_mask().contains("display_name"));
+    partial.apply_to(profile);
+
+    let _ = ProfilePartial::default();
+}
+
+#[derive(FromRow)]
+#[from_row(offsets)]
+#[allow(dead_code)]
+pub struct OffsetTodo {
+    todo_id: i32,
+    #[from_row(flatten)]
+    user: User,
+    #[from_row(flatten)]
+    period: Period,
+    text: String,
+}
+
+#[allow(dead_code)]
+fn column_offsets() {
+    const _: () = assert!(offset_todo_columns::TODO_ID == 0);
+    const _: () = assert!(offset_todo_columns::USER == 1);
+    const _: () = assert!(offset_todo_columns::PERIOD == 2);
+    const _: () = assert!(offset_todo_columns::TEXT == 4);
+}
+
+#[allow(dead_code)]
+fn aliases(row: &Row) {
+    // `SELECT todo_id, text, author_id, json FROM todos`
+    let aliases = HashMap::from([("author_id", "user_id")]);
+    let _ = Todo::try_from_row_with_aliases(row, &aliases).unwrap();
+}
+
+#[test]
+fn case_mapping() {
+    use postgres_from_row::Case;
+
+    assert_eq!(Case::Snake.apply("createdAtUnix"), "created_at_unix");
+    assert_eq!(Case::Snake.apply("HTTPStatus"), "http_status");
+    assert_eq!(Case::Camel.apply("created_at_unix"), "createdAtUnix");
+    assert_eq!(Case::ScreamingKebab.apply("userId"), "USER-ID");
+    assert_eq!(Case::AsIs.apply("userId"), "userId");
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn match_policy() {
+    use postgres_from_row::{
+        test_util::{FromMockRow, MockRow},
+        Case, MatchPolicy,
+    };
+
+    // `SELECT user_id, display_name, created_at_unix FROM users`
+    let row = MockRow::new()
+        .column("user_id", 1)
+        .column("display_name", "Ada")
+        .column("created_at_unix", 1_700_000_000i64);
+    let policy = MatchPolicy {
+        case: Case::Snake,
+        ..MatchPolicy::default()
+    };
+    let user = CamelCase::try_from_mock_row_with_policy(&row, &policy).unwrap();
+    assert_eq!(user.created_at_unix, 1_700_000_000);
+    let error = row
+        .check_matches_with::<CamelCase>(&MatchPolicy::STRICT)
+        .unwrap_err();
+    assert_eq!(error.index(), 0);
+    assert_eq!(error.found_name(), Some("user_id"));
+
+    // `SELECT json, user_id, todo_id, text, created_at FROM todos`
+    let row = MockRow::new()
+        .column("json", Json(HashMap::from([("done".to_string(), true)])))
+        .column("user_id", 2)
+        .column("todo_id", 1)
+        .column("text", "wash the dishes")
+        .column("created_at", 1_700_000_000i64);
+    let policy = MatchPolicy {
+        extra_columns: true,
+        order: false,
+        ..MatchPolicy::STRICT
+    };
+    let todo = Todo::try_from_mock_row_with_policy(&row, &policy).unwrap();
+    assert_eq!(todo.todo_id, 1);
+    assert_eq!(todo.user.user_id, 2);
+    assert!(todo.json.0["done"]);
+
+    let error = row
+        .check_matches_with::<Todo>(&MatchPolicy::SUBSET)
+        .unwrap_err();
+    // `user_id` was expected after `text`
+    assert_eq!(error.index(), 4);
+    assert_eq!(error.expected().unwrap().column_name(), Some("user_id"));
+    assert_eq!(error.found_name(), Some("created_at"));
+}
+
+#[test]
+fn short_type_names() {
+    use postgres_from_row::TypeNames;
+
+    let short = |name| TypeNames::Short.apply(name).into_owned();
+    assert_eq!(short(std::any::type_name::<String>()), "String");
+    assert_eq!(short(std::any::type_name::<Option<i64>>()), "Option<i64>");
+    assert_eq!(
+        short(std::any::type_name::<Vec<Option<String>>>()),
+        "Vec<Option<String>>"
+    );
+    assert_eq!(
+        short("chrono::naive::datetime::NaiveDateTime"),
+        "chrono::NaiveDateTime"
+    );
+    assert_eq!(short("(i32, &alloc::string::String)"), "(i32, &String)");
+    assert_eq!(
+        TypeNames::Full.apply("alloc::string::String"),
+        "alloc::string::String"
+    );
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn match_error() {
+    use postgres_from_row::{test_util::MockRow, MatchPolicy};
+    use tokio_postgres::types::Type;
+
+    let row = MockRow::new()
+        .column("todo_id", 1)
+        .column_with_type("title", Type::TEXT, "wash the dishes")
+        .column("user_id", 2)
+        .column("json", Json(HashMap::<String, bool>::new()));
+    let error = row
+        .check_matches_with::<Todo>(&MatchPolicy::STRICT)
+        .unwrap_err();
+    assert_eq!(error.index(), 1);
+    assert_eq!(error.expected().unwrap().column_name(), Some("text"));
+    assert_eq!(error.found_name(), Some("title"));
+    assert_eq!(error.found_type(), Some(&Type::TEXT));
+    assert!(!error.is_type_mismatch());
+    assert_eq!(
+        error.to_string(),
+        "column 1 is `title` of type `text`, expected `text` of type `String`"
+    );
+
+    let row = MockRow::new()
+        .column("todo_id", 1)
+        .column("text", 2)
+        .column("user_id", 2)
+        .column("json", Json(HashMap::<String, bool>::new()));
+    let error = row
+        .check_matches_with::<Todo>(&MatchPolicy::STRICT)
+        .unwrap_err();
+    assert!(error.is_type_mismatch());
+    assert!(
+        error
+            .to_string()
+            .starts_with("column 1 `text` has type `int"),
+        "{error}"
+    );
+}
+
+#[allow(dead_code)]
+fn checked(row: &Row, rows: Vec<Row>) {
+    use postgres_from_row::{Error, MatchPolicy};
+
+    let _: Result<Todo, Error> = Todo::from_row_checked(row);
+    let _: Result<(Todo, usize), Error> = Todo::from_row_at_checked(row, 0);
+    let _: Result<Todo, Error> = Todo::from_row_with_policy_checked(row, &MatchPolicy::SUBSET);
+    let _: Result<Vec<Todo>, Error> = Todo::from_slice_checked(&rows);
+    let _: Result<Vec<Todo>, Error> = Todo::from_slice_with_columns_checked(row.columns(), &rows);
+    let _: Result<Vec<Todo>, Error> = Todo::from_rows_checked(rows);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn checked_report() {
+    use postgres_from_row::{
+        test_util::{FromMockRow, MockRow},
+        Error, ReportEntry,
+    };
+
+    // `SELECT todo_id, text, user_id, done FROM todos` lacks `json`
+    let row = MockRow::new()
+        .column("todo_id", 1)
+        .column("text", "wash the dishes")
+        .column("user_id", 2)
+        .column("done", false);
+    let Err(Error::Mismatch(report)) = Todo::from_mock_row_checked(&row) else {
+        panic!("expected a mismatch");
+    };
+    assert!(!report.is_match());
+    let mismatches: Vec<_> = report.mismatches().collect();
+    assert!(matches!(
+        mismatches[..],
+        [ReportEntry::Missing { expected }, ReportEntry::Extra { name, .. }]
+            if expected.column_name() == Some("json") && name == "done"
+    ));
+    let text = report.to_string();
+    assert!(text.contains("json") && text.contains("done"), "{text}");
+}
+
+#[test]
+fn expected_columns_builder() {
+    use postgres_from_row::ExpectedColumnsBuilder;
+    use tokio_postgres::types::Type;
+
+    let columns = ExpectedColumnsBuilder::new()
+        .push_column::<i32>("todo_id")
+        .push_nested::<User>()
+        .mark_nullable()
+        .push_unnamed::<String>()
+        .build();
+
+    let names = columns
+        .iter()
+        .map(|column| column.column_name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, [Some("todo_id"), Some("user_id"), None]);
+    assert!(!columns[0].nullable(&Type::INT4));
+    assert!(columns[1].nullable(&Type::INT4));
+    assert!(!columns[2].nullable(&Type::TEXT));
+}
+
+#[allow(dead_code)]
+pub struct Label {
+    label_id: i32,
+    color: Option<String>,
+}
+
+postgres_from_row::impl_from_row!(Label {
+    label_id: i32,
+    color: Option<String>,
+});
+
+#[derive(FromRow)]
+#[allow(dead_code)]
+pub struct LabeledTodo {
+    todo_id: i32,
+    #[from_row(flatten)]
+    label: Label,
+}
+
+#[test]
+fn impl_from_row() {
+    assert_eq!(Label::COLUMN_COUNT, 2);
+    assert_eq!(
+        Label::COLUMN_NAMES,
+        Some(&[Some("label_id"), Some("color")][..])
+    );
+    let paths = LabeledTodo::report_expected_columns()
+        .iter()
+        .map(|column| column.path().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            "LabeledTodo::todo_id",
+            "LabeledTodo::label::label_id",
+            "LabeledTodo::label::color"
+        ]
+    );
+}
+
+pub struct LegacyUser {
+    pub user_id: i32,
+}
+
+impl TryFrom<&Row> for LegacyUser {
+    type Error = tokio_postgres::Error;
+
+    fn try_from(row: &Row) -> Result<Self, Self::Error> {
+        Ok(Self {
+            user_id: row.try_get("user_id")?,
+        })
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl TryFrom<&postgres_from_row::test_util::MockRow> for LegacyUser {
+    type Error = postgres_from_row::Error;
+
+    fn try_from(row: &postgres_from_row::test_util::MockRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            user_id: row.try_get(0)?,
+        })
+    }
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn via_try_from() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+    use postgres_from_row::ViaTryFrom;
+
+    assert_eq!(ViaTryFrom::<LegacyUser>::COLUMN_COUNT, 0);
+    assert!(ViaTryFrom::<LegacyUser>::report_expected_columns().is_empty());
+
+    let rows = [
+        MockRow::new().column("user_id", 1i32),
+        MockRow::new().column("user_id", 2i32),
+    ];
+    let users: Vec<LegacyUser> = ViaTryFrom::<LegacyUser>::try_from_mock_rows(&rows)
+        .unwrap()
+        .into_iter()
+        .map(ViaTryFrom::into_inner)
+        .collect();
+    assert_eq!(
+        users.iter().map(|user| user.user_id).collect::<Vec<_>>(),
+        [1, 2]
+    );
+
+    assert!(ViaTryFrom::<LegacyUser>::try_from_mock_row_joined(None, &rows[0], 1).is_err());
+}
+
+#[test]
+fn column_paths() {
+    let paths = Todo::report_expected_columns()
+        .iter()
+        .map(|column| column.path().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            "Todo::todo_id",
+            "Todo::text",
+            "Todo::user::user_id",
+            "Todo::json"
+        ]
+    );
+}
+
+#[cfg(feature = "miette")]
+#[allow(dead_code)]
+fn miette_diagnostic(row: &Row) {
+    use postgres_from_row::diagnostic::Diagnostic;
+
+    let sql = "SELECT todo_id, text, user_id, json FROM todos";
+    if let Err(error) = Todo::check_matches(row.columns()) {
+        let error = error.with_sql(sql);
+        let _ = (error.code(), error.help(), error.labels());
+    }
+}
+
+#[cfg(feature = "test-util")]
+#[derive(FromRow)]
+#[from_row(mock)]
+#[allow(dead_code)]
+pub struct Address {
+    zip: i32,
+}
+
+#[cfg(feature = "test-util")]
+#[derive(FromRow)]
+#[from_row(mock)]
+#[allow(dead_code)]
+pub struct Invoice {
+    invoice_id: i32,
+    #[from_row(flatten)]
+    address: Address,
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn field_error_path() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+
+    let row = MockRow::new().column("invoice_id", 1i32).null::<i32>("zip");
+    let Err(postgres_from_row::Error::Field(error)) = Invoice::try_from_mock_row(&row) else {
+        panic!("expected a field error");
+    };
+    assert_eq!(error.path(), "address.zip");
+    assert_eq!(error.column(), Some("zip"));
+    assert!(error.error().is_null());
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn row_error_index() {
+    use postgres_from_row::test_util::{FromMockRow, MockRow};
+
+    let rows = [
+        MockRow::new()
+            .column("invoice_id", 1i32)
+            .column("zip", 1000i32),
+        MockRow::new().column("invoice_id", 2i32).null::<i32>("zip"),
+    ];
+    let Err(postgres_from_row::Error::Row(error)) = Invoice::try_from_mock_rows(&rows) else {
+        panic!("expected a row error");
+    };
+    assert_eq!(error.index(), 1);
+    assert!(matches!(error.error(), postgres_from_row::Error::Field(_)));
+}
+
+#[allow(dead_code)]
+fn diff_columns(row: &Row) {
+    let expected = Todo::report_expected_columns();
+    let _: Vec<postgres_from_row::ColumnMismatch> =
+        postgres_from_row::diff_columns(row.columns(), &expected);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn mock_diff_columns() {
+    use postgres_from_row::{test_util::MockRow, ColumnMismatch};
+    use tokio_postgres::types::Type;
+
+    // `SELECT todo_id, title, user_id, json, done FROM todos` renamed `text` and changed the type of `json`
+    let row = MockRow::new()
+        .column("todo_id", 1)
+        .column_with_type("title", Type::TEXT, "wash the dishes")
+        .column("user_id", 2)
+        .column("json", 3)
+        .column("done", false);
+    let mismatches = row.diff_columns::<Todo>();
+    assert_eq!(mismatches.len(), 3, "{mismatches:?}");
+    assert!(matches!(
+        &mismatches[0],
+        ColumnMismatch::Renamed { from: "text", to, found, .. } if to == "title" && *found == Type::TEXT
+    ));
+    assert!(matches!(
+        &mismatches[1],
+        ColumnMismatch::TypeMismatch { expected, found }
+            if expected.column_name() == Some("json") && *found == Type::INT4
+    ));
+    assert!(matches!(
+        &mismatches[2],
+        ColumnMismatch::Extra { name, found } if name == "done" && *found == Type::BOOL
+    ));
+
+    let row = MockRow::new()
+        .column("todo_id", 1)
+        .column_with_type("text", Type::TEXT, "wash the dishes")
+        .column("user_id", 2);
+    let mismatches = row.diff_columns::<Todo>();
+    assert!(matches!(
+        &mismatches[..],
+        [ColumnMismatch::Missing { expected }] if expected.column_name() == Some("json")
+    ));
+}
+
+#[allow(dead_code)]
+fn report_options(row: &Row) {
+    use postgres_from_row::{report_expected_columns_mismatch, ReportLayout, ReportOptions};
+
+    let report = report_expected_columns_mismatch(row.columns(), &Todo::report_expected_columns());
+    let options = ReportOptions {
+        layout: ReportLayout::Plain,
+        show_nullable: false,
+        ..ReportOptions::default()
+    };
+    let _: String = report.display(&options).to_string();
+}
+
+#[allow(dead_code)]
+async fn typed_statement(client: &tokio_postgres::Client, user_id: i32) {
+    use postgres_from_row::prepare_as;
+
+    let statement = prepare_as::<User, _>(client, "SELECT user_id FROM users WHERE user_id = $1")
+        .await
+        .unwrap();
+    let _: Vec<User> = statement.query(&[&user_id]).await.unwrap();
+    let _: Option<User> = statement.query_opt(&[&user_id]).await.unwrap();
+}
+
+#[allow(dead_code)]
+async fn typed_client(client: tokio_postgres::Client, user_id: i32) {
+    use postgres_from_row::TypedClient;
+
+    let client = TypedClient::new(client);
+    let sql = "SELECT user_id FROM users WHERE user_id = $1";
+    let _: Vec<User> = client.fetch(sql, &[&user_id]).await.unwrap();
+    let _: User = client.fetch_one(sql, &[&user_id]).await.unwrap();
+}
+
+#[allow(dead_code)]
+async fn portal_batches(client: &mut tokio_postgres::Client) {
+    use postgres_from_row::bind_as;
+
+    let transaction = client.transaction().await.unwrap();
+    let mut batches = bind_as::<Measurement>(&transaction, "SELECT * FROM measurements", &[], 100)
+        .await
+        .unwrap();
+    while let Some(measurements) = batches.next_batch().await.unwrap() {
+        assert!(!measurements.is_empty());
+    }
+}
+
+#[allow(dead_code)]
+async fn cursor_reader(client: &mut tokio_postgres::Client) {
+    use postgres_from_row::CursorReader;
+
+    let transaction = client.transaction().await.unwrap();
+    let mut cursor = CursorReader::<Measurement, _>::declare(
+        &transaction,
+        "SELECT * FROM measurements",
+        &[],
+        100,
+    )
+    .await
+    .unwrap();
+    while let Some(measurements) = cursor.next_batch().await.unwrap() {
+        assert!(!measurements.is_empty());
+    }
+    cursor.close().await.unwrap();
+}
+
+#[cfg(feature = "sync")]
+#[allow(dead_code)]
+fn rows_as(rows: impl postgres_from_row::sync::RowIterExt) {
+    use postgres_from_row::sync::FallibleIterator;
+
+    let mut measurements = rows.rows_as::<Measurement>();
+    while let Some(measurement) = measurements.next().unwrap() {
+        assert!(measurement.row_span() > 0);
+    }
+}
+
+#[cfg(feature = "stream")]
+#[allow(dead_code)]
+async fn chunks_as(client: &tokio_postgres::Client) {
+    use futures_util::TryStreamExt;
+    use postgres_from_row::stream::chunks_as;
+
+    let rows = client
+        .query_raw("SELECT * FROM measurements", std::iter::empty::<i32>())
+        .await
+        .unwrap();
+    let mut chunks = std::pin::pin!(chunks_as::<Measurement, _>(rows, 10));
+    while let Some(measurements) = chunks.try_next().await.unwrap() {
+        assert!(measurements.len() <= 10);
+    }
+}
+
+#[cfg(feature = "schema-tests")]
+postgres_from_row::schema_tests! {
+    table_account_matches_schema: TableAccount,
+    user_matches_schema: User => "users",
+}
+
+#[allow(dead_code)]
+async fn report_statement(client: &tokio_postgres::Client) {
+    let statement = client.prepare("SELECT user_id FROM users").await.unwrap();
+    let report = postgres_from_row::report_statement::<User>(client, &statement)
+        .await
+        .unwrap();
+    assert!(report.is_match(), "{report}");
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn subset() {
+    use postgres_from_row::{
+        test_util::{FromMockRow, MockRow},
+        MatchPolicy,
+    };
+
+    // `SELECT * FROM todos`
+    let row = MockRow::new()
+        .column("todo_id", 1)
+        .column("created_at", 1_700_000_000i64)
+        .column("text", "wash the dishes")
+        .column("user_id", 2)
+        .column("json", Json(HashMap::<String, bool>::new()))
+        .column("done", false);
+    row.check_matches_with::<Todo>(&MatchPolicy::SUBSET)
+        .unwrap();
+    let todo = Todo::try_from_mock_row_subset(&row).unwrap();
+    assert_eq!(todo.text, "wash the dishes");
+    assert_eq!(todo.user.user_id, 2);
+
+    // `SELECT text, todo_id, user_id, json FROM todos` has the columns out of order
+    let row = MockRow::new()
+        .column("text", "wash the dishes")
+        .column("todo_id", 1)
+        .column("user_id", 2)
+        .column("json", Json(HashMap::<String, bool>::new()));
+    let error = row
+        .check_matches_with::<Todo>(&MatchPolicy::SUBSET)
+        .unwrap_err();
+    assert_eq!(error.expected().unwrap().column_name(), Some("text"));
+}
+
+#[test]
+fn aggregate_joined() {
+    let rows = [
+        (1, "a", Some(10)),
+        (1, "a", Some(11)),
+        (2, "b", None),
+        (1, "c", Some(12)),
+    ];
+    let users = postgres_from_row::aggregate_joined(
+        rows,
+        |row| Ok(row.1),
+        |row| Ok(row.2),
+        |row| Ok(row.0),
+    )
+    .unwrap();
+    assert_eq!(users, [("a", vec![10, 11]), ("b", vec![]), ("c", vec![12])]);
+
+    let error = postgres_from_row::aggregate_joined(
+        rows,
+        |row| Ok(row.1),
+        |row| {
+            row.2
+                .map(Some)
+                .ok_or_else(|| postgres_from_row::Error::conversion("no child"))
+        },
+        |row| Ok(row.0),
+    )
+    .unwrap_err();
+    let postgres_from_row::Error::Row(error) = error else {
+        panic!("expected a row error");
+    };
+    assert_eq!(error.index(), 2);
+}